@@ -7,6 +7,22 @@ pub enum CalcType {
     DownPayment,
     #[clap(name = "next-disbursement-date", alias = "nd")]
     NextDisbursementDate,
+    Compare,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Provider {
+    Simple,
+    Iterative,
+}
+
+impl From<Provider> for core_payment_plan::Provider {
+    fn from(value: Provider) -> Self {
+        match value {
+            Provider::Simple => core_payment_plan::Provider::Simple,
+            Provider::Iterative => core_payment_plan::Provider::Iterative,
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -14,4 +30,10 @@ pub enum CalcType {
 pub struct Args {
     #[clap(short = 't', long = "type", default_value_t, value_enum)]
     pub calc_type: CalcType,
+    /// The provider of the calculation, the default of the build when not given
+    #[clap(short = 'p', long = "provider", value_enum)]
+    pub provider: Option<Provider>,
+    /// The relative difference of the providers that is material on the compare type (0.01 for 1%)
+    #[clap(long = "tolerance", default_value_t = 0.01)]
+    pub tolerance: f64,
 }
@@ -1,9 +1,17 @@
 use chrono::NaiveTime;
-use core_payment_plan::{Invoice, Params, Response};
+use core_payment_plan::{
+    AmortizationSystem, BusinessDayConvention, DueDayPolicy, GracePeriod, Invoice, IofConvergence,
+    Params, Periodicity, Provider, ProviderComparison, ResidualAllocation, Response, RoundingMode,
+    RoundingPolicy,
+};
 use prost::Message;
 use types::{
-    DownPaymentParams, DownPaymentResponse, DownPaymentResponses, Invoice as CliInvoice,
-    PlanParams, PlanResponse, PlanResponses,
+    AmortizationSystem as CliAmortizationSystem, BusinessDayConvention as CliBusinessDayConvention,
+    DownPaymentParams, DownPaymentResponse, DownPaymentResponses, DueDayPolicy as CliDueDayPolicy,
+    FieldComparison as CliFieldComparison, GracePeriod as CliGracePeriod, Invoice as CliInvoice,
+    Periodicity as CliPeriodicity, PlanParams, PlanResponse, PlanResponses,
+    Provider as CliProvider, ProviderComparison as CliProviderComparison, ProviderComparisons,
+    ResidualAllocation as CliResidualAllocation, RoundingMode as CliRoundingMode,
 };
 
 use crate::types::Invoices;
@@ -34,6 +42,55 @@ impl TryInto<Params> for PlanParams {
             }
         };
 
+        let amortization_system = match self.amortization_system() {
+            CliAmortizationSystem::Price => AmortizationSystem::Price,
+            CliAmortizationSystem::Sac => AmortizationSystem::Sac,
+        };
+
+        let grace_period = match self.grace_period() {
+            CliGracePeriod::Capitalized => GracePeriod::Capitalized,
+            CliGracePeriod::InterestOnly => GracePeriod::InterestOnly,
+        };
+
+        let periodicity = match self.periodicity() {
+            CliPeriodicity::Monthly => Periodicity::Monthly,
+            CliPeriodicity::Biweekly => Periodicity::Biweekly,
+            CliPeriodicity::Weekly => Periodicity::Weekly,
+        };
+
+        let due_day_policy = match self.due_day_policy() {
+            CliDueDayPolicy::Stepped => DueDayPolicy::Stepped,
+            CliDueDayPolicy::Anchored => DueDayPolicy::Anchored,
+            CliDueDayPolicy::EndOfMonth => DueDayPolicy::EndOfMonth,
+            CliDueDayPolicy::FixedDay => DueDayPolicy::FixedDay(self.due_day),
+        };
+
+        let rounding_mode = match self.rounding_mode() {
+            CliRoundingMode::HalfUp => RoundingMode::HalfUp,
+            CliRoundingMode::HalfEven => RoundingMode::HalfEven,
+            CliRoundingMode::Truncate => RoundingMode::Truncate,
+        };
+
+        let residual_allocation = match self.residual_allocation() {
+            CliResidualAllocation::None => ResidualAllocation::None,
+            CliResidualAllocation::FirstInstallment => ResidualAllocation::FirstInstallment,
+            CliResidualAllocation::LastInstallment => ResidualAllocation::LastInstallment,
+        };
+
+        let business_day_convention = match self.business_day_convention() {
+            CliBusinessDayConvention::Following => BusinessDayConvention::Following,
+            CliBusinessDayConvention::ModifiedFollowing => BusinessDayConvention::ModifiedFollowing,
+            CliBusinessDayConvention::Preceding => BusinessDayConvention::Preceding,
+        };
+
+        let default_convergence = IofConvergence::default();
+        let iof_convergence = IofConvergence {
+            tolerance: self.iof_tolerance.unwrap_or(default_convergence.tolerance),
+            max_iterations: self
+                .iof_max_iterations
+                .unwrap_or(default_convergence.max_iterations),
+        };
+
         let params = Params {
             max_total_amount: self.max_total_amount,
             min_installment_amount: self.min_installment_amount,
@@ -48,6 +105,17 @@ impl TryInto<Params> for PlanParams {
             first_payment_date,
             disbursement_date: disbursement_date,
             disbursement_only_on_business_days: self.disbursement_only_on_business_days,
+            amortization_system,
+            grace_period,
+            balloon_percentage: self.balloon_percentage,
+            periodicity,
+            due_day_policy,
+            rounding_policy: RoundingPolicy {
+                mode: rounding_mode,
+                residual_allocation,
+            },
+            business_day_convention,
+            iof_convergence,
         };
         return Ok(params);
     }
@@ -89,6 +157,8 @@ impl From<Response> for PlanResponse {
             total_effective_cost: value.total_effective_cost,
             tec_yearly: value.tec_yearly,
             tec_monthly: value.tec_monthly,
+            eir_periodic: value.eir_periodic,
+            tec_periodic: value.tec_periodic,
             eir_yearly: value.eir_yearly,
             eir_monthly: value.eir_monthly,
             settled_to_merchant: value.settled_to_merchant,
@@ -100,6 +170,20 @@ impl From<Response> for PlanResponse {
             iof_percentage: value.iof_percentage,
             disbursement_date_millis: disbursement_date,
             paid_contract_amount: value.paid_contract_amount,
+            due_day_policy: match value.due_day_policy {
+                DueDayPolicy::Stepped => CliDueDayPolicy::Stepped,
+                DueDayPolicy::Anchored => CliDueDayPolicy::Anchored,
+                DueDayPolicy::EndOfMonth => CliDueDayPolicy::EndOfMonth,
+                DueDayPolicy::FixedDay(_) => CliDueDayPolicy::FixedDay,
+            }
+            .into(),
+            due_day: match value.due_day_policy {
+                DueDayPolicy::FixedDay(day) => day,
+                _ => 0,
+            },
+            rounding_residual: value.rounding_residual,
+            iof_iterations: value.iof_iterations,
+            iof_residual: value.iof_residual,
             paid_total_iof: value.paid_total_iof,
             pre_disbursement_amount: value.pre_disbursement_amount,
             invoices: Some(value.invoices.into()),
@@ -128,6 +212,13 @@ impl From<Invoice> for CliInvoice {
             due_date_millis: due_date,
             debit_service: value.debit_service,
             main_iof_tac: value.main_iof_tac,
+            amount: value.amount,
+            opening_balance: value.opening_balance,
+            interest: value.interest,
+            principal: value.principal,
+            iof: value.iof,
+            tac: value.tac,
+            closing_balance: value.closing_balance,
         }
     }
 }
@@ -234,3 +325,45 @@ pub fn serialize_down_payment_responses(responses: DownPaymentResponses) -> Vec<
     responses.encode(&mut buf).unwrap();
     buf
 }
+
+impl From<ProviderComparison> for CliProviderComparison {
+    fn from(value: ProviderComparison) -> Self {
+        let missing = value.missing.map(|provider| match provider {
+            Provider::Simple => CliProvider::Simple.into(),
+            Provider::Iterative => CliProvider::Iterative.into(),
+        });
+        let fields = value
+            .fields
+            .into_iter()
+            .map(|field| CliFieldComparison {
+                field: field.field.to_string(),
+                simple: field.simple,
+                iterative: field.iterative,
+                absolute_delta: field.absolute_delta,
+                relative_delta: field.relative_delta,
+                material: field.material,
+            })
+            .collect();
+
+        CliProviderComparison {
+            installment: value.installment,
+            missing,
+            fields,
+            material: value.material,
+        }
+    }
+}
+
+impl From<Vec<ProviderComparison>> for ProviderComparisons {
+    fn from(value: Vec<ProviderComparison>) -> Self {
+        let comparisons = value.into_iter().map(|c| c.into()).collect();
+        ProviderComparisons { comparisons }
+    }
+}
+
+pub fn serialize_comparisons(comparisons: ProviderComparisons) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(comparisons.encoded_len());
+    // Unwrap is safe, since we have reserved sufficient capacity in the vector.
+    comparisons.encode(&mut buf).unwrap();
+    buf
+}
@@ -7,10 +7,13 @@ mod args;
 use args::{Args, CalcType};
 use chrono::NaiveTime;
 use clap::Parser;
-use core_payment_plan::{DownPaymentParams, Params};
+use core_payment_plan::{
+    err::{ErrorKind, PaymentPlanError},
+    DownPaymentParams, Params, Provider,
+};
 use payment_plan_cli::{
     deserialize_down_payment_params,
-    types::{DownPaymentResponses, PlanResponses},
+    types::{DownPaymentResponses, PlanResponses, ProviderComparisons},
 };
 
 fn main() -> ExitCode {
@@ -22,17 +25,36 @@ fn main() -> ExitCode {
     //Read the input from stdin, this will be binary non human readable data
     std::io::stdin().read_to_end(&mut buf).unwrap();
     let c_type = args.calc_type;
+    let provider = args.provider.map(Into::into).unwrap_or_default();
 
     let code = match c_type {
-        CalcType::Normal => calc(buf),
-        CalcType::DownPayment => down_calc(buf),
+        CalcType::Normal => calc(buf, provider),
+        CalcType::DownPayment => down_calc(buf, provider),
         CalcType::NextDisbursementDate => next_disbursement_date(buf),
+        CalcType::Compare => compare(buf, args.tolerance),
     };
 
     return code;
 }
 
-fn calc(buf: Vec<u8>) -> ExitCode {
+/*
+    The exit code of the errors of the calculation, by their kind:
+        - 2: a param out of its domain
+        - 3: a date out of its range
+        - 4: the rates of the cash flows could not be calculated
+        - 5: the target of the solver can not be reached
+    The invalid inputs exit with 1.
+*/
+fn error_exit_code(error: &PaymentPlanError) -> ExitCode {
+    match error.kind() {
+        ErrorKind::Validation => ExitCode::from(2),
+        ErrorKind::DateRange => ExitCode::from(3),
+        ErrorKind::Convergence => ExitCode::from(4),
+        ErrorKind::Unreachable => ExitCode::from(5),
+    }
+}
+
+fn calc(buf: Vec<u8>, provider: Provider) -> ExitCode {
     let params = payment_plan_cli::deserialize_params(&buf).unwrap();
     let params: Result<Params, _> = params.try_into();
     let params = match params {
@@ -44,14 +66,14 @@ fn calc(buf: Vec<u8>) -> ExitCode {
         }
     };
 
-    let response = core_payment_plan::calculate_payment_plan(params);
+    let response = core_payment_plan::calculate_payment_plan_with(provider, params);
 
     let response = match response {
         Ok(response) => response,
         Err(e) => {
             //eprintln! is a macro that prints to stderr
-            eprintln!("Error: {}", e);
-            return ExitCode::FAILURE;
+            eprintln!("Error: [{}] {}", e.code(), e);
+            return error_exit_code(&e);
         }
     };
 
@@ -65,7 +87,7 @@ fn calc(buf: Vec<u8>) -> ExitCode {
     return ExitCode::SUCCESS;
 }
 
-fn down_calc(buf: Vec<u8>) -> ExitCode {
+fn down_calc(buf: Vec<u8>, provider: Provider) -> ExitCode {
     let params = deserialize_down_payment_params(&buf).unwrap();
     let params: Result<DownPaymentParams, _> = params.try_into();
     let params = match params {
@@ -77,14 +99,14 @@ fn down_calc(buf: Vec<u8>) -> ExitCode {
         }
     };
 
-    let response = core_payment_plan::calculate_down_payment_plan(params);
+    let response = core_payment_plan::calculate_down_payment_plan_with(provider, params);
 
     let response = match response {
         Ok(response) => response,
         Err(e) => {
             //eprintln! is a macro that prints to stderr
-            eprintln!("Error: {}", e);
-            return ExitCode::FAILURE;
+            eprintln!("Error: [{}] {}", e.code(), e);
+            return error_exit_code(&e);
         }
     };
 
@@ -98,6 +120,39 @@ fn down_calc(buf: Vec<u8>) -> ExitCode {
     return ExitCode::SUCCESS;
 }
 
+fn compare(buf: Vec<u8>, tolerance: f64) -> ExitCode {
+    let params = payment_plan_cli::deserialize_params(&buf).unwrap();
+    let params: Result<Params, _> = params.try_into();
+    let params = match params {
+        Ok(params) => params,
+        Err(e) => {
+            //eprintln! is a macro that prints to stderr
+            eprintln!("Error: Invalid input: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let response = core_payment_plan::compare_providers(params, tolerance);
+
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            //eprintln! is a macro that prints to stderr
+            eprintln!("Error: [{}] {}", e.code(), e);
+            return error_exit_code(&e);
+        }
+    };
+
+    let response: ProviderComparisons = response.into();
+
+    let response = payment_plan_cli::serialize_comparisons(response);
+
+    //Write the response to stdout
+    std::io::stdout().write_all(&response).unwrap();
+
+    ExitCode::SUCCESS
+}
+
 fn next_disbursement_date(buf: Vec<u8>) -> ExitCode {
     let buf: [u8; 8] = match buf.as_slice().try_into() {
         Ok(value) => value,
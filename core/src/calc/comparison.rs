@@ -0,0 +1,258 @@
+use crate::{
+    calculate_payment_plan_with,
+    err::{FieldError, PaymentPlanError},
+    FieldComparison, Params, Provider, ProviderComparison, Response,
+};
+
+type FieldValue = fn(&Response) -> f64;
+
+// The values of the `Response` calculated by both providers, the TAC amounts are only calculated by the Simple provider
+const FIELDS: [(&str, FieldValue); 16] = [
+    ("installment_amount", |r| r.installment_amount),
+    ("total_amount", |r| r.total_amount),
+    ("total_iof", |r| r.total_iof),
+    ("contract_amount", |r| r.contract_amount),
+    ("debit_service", |r| r.debit_service),
+    ("customer_debit_service_amount", |r| {
+        r.customer_debit_service_amount
+    }),
+    ("customer_amount", |r| r.customer_amount),
+    ("calculation_basis_for_effective_interest_rate", |r| {
+        r.calculation_basis_for_effective_interest_rate
+    }),
+    ("eir_monthly", |r| r.eir_monthly),
+    ("eir_yearly", |r| r.eir_yearly),
+    ("tec_monthly", |r| r.tec_monthly),
+    ("tec_yearly", |r| r.tec_yearly),
+    ("mdr_amount", |r| r.mdr_amount),
+    ("merchant_debit_service_amount", |r| {
+        r.merchant_debit_service_amount
+    }),
+    ("merchant_total_amount", |r| r.merchant_total_amount),
+    ("settled_to_merchant", |r| r.settled_to_merchant),
+];
+
+/*
+    The plans of both providers for each number of installments, from 1 to `params.installments`.
+    A number of installments without a plan of a provider (ex: under the minimum installment amount)
+    has no fields and is material, and the numbers without a plan of both are left out.
+*/
+pub fn compare_providers(
+    params: Params,
+    tolerance: f64,
+) -> Result<Vec<ProviderComparison>, PaymentPlanError> {
+    if tolerance.is_nan() || tolerance < 0.0 {
+        return Err(PaymentPlanError::InvalidParams(vec![FieldError::new(
+            "tolerance",
+            tolerance,
+            "must not be negative",
+        )]));
+    }
+
+    let simple = calculate_payment_plan_with(Provider::Simple, params)?;
+    let iterative = calculate_payment_plan_with(Provider::Iterative, params)?;
+
+    let mut comparisons = Vec::new();
+    for installment in 1..=params.installments {
+        let simple = simple.iter().find(|plan| plan.installment == installment);
+        let iterative = iterative
+            .iter()
+            .find(|plan| plan.installment == installment);
+
+        let (simple, iterative) = match (simple, iterative) {
+            (Some(simple), Some(iterative)) => (simple, iterative),
+            (None, None) => continue,
+            (simple, _) => {
+                let missing = match simple {
+                    Some(_) => Provider::Iterative,
+                    None => Provider::Simple,
+                };
+                comparisons.push(ProviderComparison {
+                    installment,
+                    missing: Some(missing),
+                    fields: Vec::new(),
+                    material: true,
+                });
+                continue;
+            }
+        };
+
+        let fields: Vec<FieldComparison> = FIELDS
+            .iter()
+            .map(|(field, value)| compare_field(field, value(simple), value(iterative), tolerance))
+            .collect();
+        let material = fields.iter().any(|field| field.material);
+
+        comparisons.push(ProviderComparison {
+            installment,
+            missing: None,
+            fields,
+            material,
+        });
+    }
+
+    Ok(comparisons)
+}
+
+fn compare_field(
+    field: &'static str,
+    simple: f64,
+    iterative: f64,
+    tolerance: f64,
+) -> FieldComparison {
+    let absolute_delta = iterative - simple;
+    let relative_delta = if absolute_delta == 0.0 {
+        0.0
+    } else {
+        absolute_delta / simple.abs()
+    };
+
+    FieldComparison {
+        field,
+        simple,
+        iterative,
+        absolute_delta,
+        relative_delta,
+        material: relative_delta.abs() > tolerance,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{err::PaymentPlanError, Params, Provider};
+
+    use super::{compare_field, compare_providers};
+
+    #[test]
+    fn test_compare_providers() {
+        let params = Params::builder()
+            .requested_amount(2900.0)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap())
+            .installments(6)
+            .mdr(0.05)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0235)
+            .build()
+            .unwrap();
+
+        let comparisons = compare_providers(params, 0.01).unwrap();
+
+        assert_eq!(comparisons.len(), 6);
+        for (i, comparison) in comparisons.iter().enumerate() {
+            assert_eq!(comparison.installment, i as u32 + 1);
+            assert_eq!(comparison.missing, None);
+
+            let mdr_amount = comparison
+                .fields
+                .iter()
+                .find(|field| field.field == "mdr_amount")
+                .unwrap();
+            assert_eq!(mdr_amount.absolute_delta, 0.0);
+            assert!(!mdr_amount.material);
+
+            let installment_amount = &comparison.fields[0];
+            assert_eq!(installment_amount.field, "installment_amount");
+            assert_eq!(
+                installment_amount.absolute_delta,
+                installment_amount.iterative - installment_amount.simple
+            );
+        }
+
+        // Without tolerance every difference is material
+        let comparisons = compare_providers(params, 0.0).unwrap();
+        assert!(comparisons.iter().all(|comparison| comparison.material));
+    }
+
+    #[test]
+    fn test_compare_providers_missing_plan() {
+        let params = Params::builder()
+            .requested_amount(2900.0)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap())
+            .installments(24)
+            .mdr(0.05)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0235)
+            .min_installment_amount(180.0)
+            .build()
+            .unwrap();
+
+        // The Simple installments are under the minimum from 21 installments on
+        let comparisons = compare_providers(params, 0.01).unwrap();
+        let missing: Vec<(u32, Option<Provider>)> = comparisons
+            .iter()
+            .filter(|comparison| comparison.material && comparison.fields.is_empty())
+            .map(|comparison| (comparison.installment, comparison.missing))
+            .collect();
+
+        assert_eq!(comparisons.len(), 24);
+        assert_eq!(
+            missing,
+            vec![
+                (21, Some(Provider::Simple)),
+                (22, Some(Provider::Simple)),
+                (23, Some(Provider::Simple)),
+                (24, Some(Provider::Simple)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_providers_tolerance() {
+        let params = Params::builder()
+            .requested_amount(2900.0)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap())
+            .installments(6)
+            .mdr(0.05)
+            .iof_overall(0.0038)
+            .iof_percentage(0.0)
+            .interest_rate(0.0235)
+            .disbursement_only_on_business_days(true)
+            .build()
+            .unwrap();
+
+        // On business days and with the overall IOF only, the providers are a few percent apart
+        let comparisons = compare_providers(params, 0.1).unwrap();
+        assert_eq!(comparisons.len(), 6);
+        assert!(comparisons.iter().all(|comparison| !comparison.material));
+
+        // The TAC is only calculated by the Simple provider
+        assert!(comparisons[0]
+            .fields
+            .iter()
+            .all(|field| !field.field.contains("tac")));
+    }
+
+    #[test]
+    fn test_compare_field() {
+        let field = compare_field("total_iof", 40.0, 41.0, 0.02);
+        assert_eq!(field.absolute_delta, 1.0);
+        assert_eq!(field.relative_delta, 0.025);
+        assert!(field.material);
+
+        let field = compare_field("mdr_amount", 0.0, 0.0, 0.0);
+        assert_eq!(field.relative_delta, 0.0);
+        assert!(!field.material);
+
+        let params = Params::builder()
+            .requested_amount(2900.0)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap())
+            .installments(6)
+            .mdr(0.05)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0235)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            compare_providers(params, -0.01),
+            Err(PaymentPlanError::InvalidParams(_))
+        ));
+    }
+}
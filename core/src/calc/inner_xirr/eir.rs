@@ -1,6 +1,9 @@
 use xirr::{compute, Payment};
 
-use crate::{err::PaymentPlanError, Params};
+use crate::{
+    err::{ConvergenceError, PaymentPlanError, XirrRate},
+    Params,
+};
 
 pub fn calculate_eir_monthly(
     params: Params,
@@ -47,14 +50,21 @@ pub fn calculate_eir_monthly(
                     })
                     .collect();
 
-                let xir_result = compute(&converged_eir_params)?;
+                let xir_result = compute(&converged_eir_params).map_err(|_| {
+                    PaymentPlanError::CalculationError(ConvergenceError::new(
+                        XirrRate::Eir,
+                        &effective_interest_rate_xirr,
+                    ))
+                })?;
                 eir_monthly = xir_result + 1.0;
                 eir_monthly = eir_monthly.powf(calculation_basis_for_effective_interest_rate) - 1.0;
             }
         }
     }
     if eir_monthly.is_nan() {
-        return Err(PaymentPlanError::XirCalculationError(params));
+        return Err(PaymentPlanError::XirCalculationError(
+            ConvergenceError::new(XirrRate::Eir, &effective_interest_rate_xirr),
+        ));
     }
     return Ok(eir_monthly);
 }
@@ -81,6 +91,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.035,
+            ..Default::default()
         };
         let customer_debit_service_proportion = 1.0;
 
@@ -1,9 +1,22 @@
 use chrono::NaiveDate;
 use xirr::Payment;
 
+use crate::Periodicity;
+
 pub mod eir;
 pub mod tec;
 
+/*
+    The monthly rate (from the XIRR with the 1/12 calculation basis) over the periodicity of the installments.
+    The XIRR is on a 365 days year, so a period of N days is 12 * N / 365 of a month.
+*/
+pub fn periodic_rate(monthly_rate: f64, periodicity: Periodicity) -> f64 {
+    match periodicity {
+        Periodicity::Monthly => monthly_rate,
+        _ => (1.0 + monthly_rate).powf(12.0 * periodicity.days() as f64 / 365.0) - 1.0,
+    }
+}
+
 pub fn prepare_xirr_params(
     installments: u32,
     due_dates: &Vec<NaiveDate>,
@@ -32,6 +45,32 @@ pub fn prepare_xirr_params(
     return (eir_params, tec_params);
 }
 
+/*
+    Same as `prepare_xirr_params`, but each due date has its own cash flow.
+    Used when the installments are not constant (ex: SAC amortization).
+*/
+pub fn prepare_xirr_params_with_amounts(
+    due_dates: &[NaiveDate],
+    eir_amounts: &[f64],
+    tec_amounts: &[f64],
+) -> (Vec<Payment>, Vec<Payment>) {
+    let mut eir_params = Vec::with_capacity(due_dates.len());
+    let mut tec_params = Vec::with_capacity(due_dates.len());
+
+    for (i, date) in due_dates.iter().enumerate() {
+        eir_params.push(Payment {
+            amount: -eir_amounts[i],
+            date: *date,
+        });
+        tec_params.push(Payment {
+            amount: -tec_amounts[i],
+            date: *date,
+        });
+    }
+
+    (eir_params, tec_params)
+}
+
 #[cfg(test)]
 mod test {
     use chrono::NaiveDate;
@@ -1,6 +1,9 @@
 use xirr::{compute, Payment};
 
-use crate::{err::PaymentPlanError, Params};
+use crate::{
+    err::{ConvergenceError, PaymentPlanError, XirrRate},
+    Params,
+};
 
 pub fn calculate_tec_monthly(
     params: Params,
@@ -41,14 +44,21 @@ pub fn calculate_tec_monthly(
                 })
                 .collect();
 
-            let xir_result = compute(&converged_tec_params)?;
+            let xir_result = compute(&converged_tec_params).map_err(|_| {
+                PaymentPlanError::CalculationError(ConvergenceError::new(
+                    XirrRate::Tec,
+                    &total_effective_cost_xirr,
+                ))
+            })?;
             tec_monthly = xir_result + 1.0;
             tec_monthly = tec_monthly.powf(calculation_basis_for_effective_interest_rate) - 1.0;
         }
     }
 
     if tec_monthly.is_nan() {
-        return Err(PaymentPlanError::XirCalculationError(params));
+        return Err(PaymentPlanError::XirCalculationError(
+            ConvergenceError::new(XirrRate::Tec, &total_effective_cost_xirr),
+        ));
     }
     return Ok(tec_monthly);
 }
@@ -57,7 +67,11 @@ pub fn calculate_tec_monthly(
 mod test {
     use xirr::Payment;
 
-    use crate::{calc::inner_xirr::tec::calculate_tec_monthly, Params};
+    use crate::{
+        calc::inner_xirr::tec::calculate_tec_monthly,
+        err::{CashFlow, ConvergenceError, PaymentPlanError, XirrRate},
+        Params,
+    };
 
     #[test]
     fn test_calculate_tec_monthly_test_7() {
@@ -75,6 +89,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let tec_params = vec![Payment {
@@ -201,4 +216,39 @@ mod test {
 
         assert_eq!(tec_monthly, 0.03875204347989669);
     }
+
+    #[test]
+    fn test_calculate_tec_monthly_convergence_error() {
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap();
+        let due_date = chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap();
+        let params = Params {
+            requested_amount: 2900.0,
+            disbursement_date,
+            first_payment_date: due_date,
+            installments: 1,
+            ..Default::default()
+        };
+        // The payment has the same sign as the requested amount
+        let tec_params = vec![Payment {
+            amount: 3000.0,
+            date: due_date,
+        }];
+
+        let resp = calculate_tec_monthly(params, tec_params, 0.0821917808219178);
+
+        let expected = ConvergenceError {
+            rate: XirrRate::Tec,
+            cash_flows: vec![
+                CashFlow {
+                    amount: 2900.0,
+                    date: disbursement_date,
+                },
+                CashFlow {
+                    amount: 3000.0,
+                    date: due_date,
+                },
+            ],
+        };
+        assert_eq!(resp, Err(PaymentPlanError::CalculationError(expected)));
+    }
 }
@@ -0,0 +1,147 @@
+use crate::{
+    err::PaymentPlanError, util::round_decimal_cases, LateChargesParams, LateChargesResponse,
+};
+
+const MAX_IOF_DAYS: i64 = 365;
+
+/*
+    Charges of an invoice paid after its due date, for the calendar days late:
+        - Fine (multa): a one-time percentage of the invoice amount (2% is the CDC limit).
+        - Mora interest (juros de mora): simple interest, pro rata of the monthly rate per day late.
+        - Remunerative interest (juros remuneratórios): the contract rate, compounded pro rata per day late.
+        - Additional IOF: the daily IOF on the principal of the invoice for the days late,
+            limited to 365 days of IOF since the disbursement.
+*/
+pub fn calculate_late_charges(
+    params: &LateChargesParams,
+) -> Result<LateChargesResponse, PaymentPlanError> {
+    if params.interest_rate < 0.0 || params.fine_rate < 0.0 || params.mora_rate < 0.0 {
+        return Err(PaymentPlanError::InvalidInterestRate);
+    }
+
+    let invoice = params.invoice;
+    let amount = invoice.amount;
+
+    let days_late = params
+        .payment_date
+        .signed_duration_since(invoice.due_date)
+        .num_days();
+
+    if days_late <= 0 {
+        return Ok(LateChargesResponse {
+            amount,
+            total_amount: amount,
+            ..Default::default()
+        });
+    }
+
+    let months_late = days_late as f64 / 30.0;
+
+    let fine = round_decimal_cases(amount * params.fine_rate, 2);
+    let mora_interest = round_decimal_cases(amount * params.mora_rate * months_late, 2);
+    let remunerative_interest = amount * ((1.0 + params.interest_rate).powf(months_late) - 1.0);
+    let remunerative_interest = round_decimal_cases(remunerative_interest, 2);
+
+    let iof_days = days_late
+        .min(MAX_IOF_DAYS - invoice.accumulated_days)
+        .max(0);
+    let iof = invoice.main_iof_tac * params.iof_percentage * iof_days as f64;
+    let iof = round_decimal_cases(iof, 2);
+
+    let total_amount = amount + fine + mora_interest + remunerative_interest + iof;
+    let total_amount = round_decimal_cases(total_amount, 2);
+
+    Ok(LateChargesResponse {
+        days_late,
+        amount,
+        fine,
+        mora_interest,
+        remunerative_interest,
+        iof,
+        total_amount,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        err::PaymentPlanError, Invoice, LateChargesParams, LateChargesResponse,
+        DEFAULT_LATE_FINE_RATE, DEFAULT_LATE_MORA_RATE,
+    };
+
+    use super::calculate_late_charges;
+
+    fn params(payment_date: chrono::NaiveDate) -> LateChargesParams {
+        LateChargesParams {
+            invoice: Invoice {
+                accumulated_days: 28,
+                main_iof_tac: 348.98,
+                debit_service: 174.37,
+                amount: 523.35,
+                due_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+                ..Default::default()
+            },
+            interest_rate: 0.0449,
+            payment_date,
+            fine_rate: DEFAULT_LATE_FINE_RATE,
+            mora_rate: DEFAULT_LATE_MORA_RATE,
+            iof_percentage: 0.000082,
+        }
+    }
+
+    #[test]
+    fn test_calculate_late_charges() {
+        let payment_date = chrono::NaiveDate::from_ymd_opt(2025, 10, 3).unwrap();
+
+        let resp = calculate_late_charges(&params(payment_date)).unwrap();
+
+        let expected = LateChargesResponse {
+            days_late: 15,
+            amount: 523.35,
+            fine: 10.47,
+            mora_interest: 2.62,
+            remunerative_interest: 11.62,
+            iof: 0.43,
+            total_amount: 548.49,
+        };
+
+        assert_eq!(resp, expected);
+    }
+
+    #[test]
+    fn test_calculate_late_charges_iof_limit() {
+        // 400 days late, but the invoice already had 28 days of IOF
+        let payment_date = chrono::NaiveDate::from_ymd_opt(2026, 10, 23).unwrap();
+
+        let resp = calculate_late_charges(&params(payment_date)).unwrap();
+
+        assert_eq!(resp.days_late, 400);
+        assert_eq!(resp.iof, 9.64);
+    }
+
+    #[test]
+    fn test_calculate_late_charges_on_time() {
+        let payment_date = chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap();
+
+        let resp = calculate_late_charges(&params(payment_date)).unwrap();
+
+        let expected = LateChargesResponse {
+            amount: 523.35,
+            total_amount: 523.35,
+            ..Default::default()
+        };
+
+        assert_eq!(resp, expected);
+    }
+
+    #[test]
+    fn test_calculate_late_charges_invalid_rate() {
+        let payment_date = chrono::NaiveDate::from_ymd_opt(2025, 10, 3).unwrap();
+        let mut params = params(payment_date);
+        params.mora_rate = -0.01;
+
+        let resp = calculate_late_charges(&params);
+
+        assert_eq!(resp, Err(PaymentPlanError::InvalidInterestRate));
+    }
+}
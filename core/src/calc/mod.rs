@@ -1,14 +1,50 @@
 use crate::{
+    calendar::HolidayCalendar,
     err::PaymentPlanError,
     util::{add_days, add_months},
-    DownPaymentParams, DownPaymentResponse, Params, Response,
+    DownPaymentParams, DownPaymentResponse, Params, Response, SelectedPlans,
 };
 
-mod inner_xirr;
+pub mod comparison;
+pub(crate) mod inner_xirr;
+pub mod late_charges;
+pub mod prepayment;
 pub mod providers;
+pub(crate) mod selection;
+pub mod settlement;
+pub mod solver;
 
+/*
+    The calculation of the plans of a provider, public to implement a provider outside of the crate.
+    Only `calculate_payment_plan` is required, the down payment plan is calculated with its plans.
+*/
 pub trait PaymentPlan {
     fn calculate_payment_plan(&self, params: Params) -> Result<Vec<Response>, PaymentPlanError>;
+    /*
+        The same plans, with the business days of `calendar` instead of the Brazilian bank holidays.
+        By default the calendar is rejected, since the providers that do not move the dates to business days
+        would calculate the same plans without it.
+    */
+    fn calculate_payment_plan_with_calendar(
+        &self,
+        _params: Params,
+        _calendar: &dyn HolidayCalendar,
+    ) -> Result<Vec<Response>, PaymentPlanError> {
+        Err(PaymentPlanError::UnsupportedHolidayCalendar)
+    }
+    /*
+        The plans of the numbers of installments in `installments` only, instead of every one up to
+        `params.installments` (which is ignored). Each plan is checked on its own against the minimum
+        installment amount and the maximum total amount, and the installments out of them are excluded with the reason.
+        By default the plans up to the largest number are calculated without the limits and the selected ones are picked.
+    */
+    fn calculate_selected_payment_plans(
+        &self,
+        params: Params,
+        installments: &[u32],
+    ) -> Result<SelectedPlans, PaymentPlanError> {
+        selection::pick_selected_payment_plans(self, params, installments, 1)
+    }
     /*
         A down payment plan is a payment plan that is made before the actual payment plan.
         It is much simpler than the actual payment plan in terms of calculations.
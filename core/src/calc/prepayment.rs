@@ -0,0 +1,403 @@
+use xirr::Payment;
+
+use crate::{
+    calc::{
+        inner_xirr::{eir::calculate_eir_monthly, tec::calculate_tec_monthly},
+        providers::iterative::{
+            daily_interest_rate, insert_price_table_on_invoices, insert_schedule_on_invoices,
+            CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+        },
+        settlement::{days_until, discount_factor},
+    },
+    calendar::HolidayCalendar,
+    err::{DateRangeError, PaymentPlanError},
+    util::{round_decimal_cases, to_decimal},
+    DecimalInvoice, Invoice, PrepaymentParams, PrepaymentResponse, PrepaymentStrategy, Response,
+};
+
+/*
+    Partial prepayment (amortização extraordinária) of a calculated plan.
+
+    The invoices due up to the prepayment date are considered paid, and the balance of the ones left
+    is their present value at the contract rate on the prepayment date (the same discount as the early settlement).
+    The prepayment lowers this balance, and the invoices left are regenerated:
+        - ReduceTerm: the invoices keep their amounts and the last ones are dropped,
+            the last invoice kept only covers what is left of the balance.
+        - ReduceInstallment: the invoices keep their due dates and the amounts are lowered
+            in proportion to the new balance.
+
+    The schedule of the invoices left starts from the new balance on the prepayment date.
+    The EIR is calculated over the contract amount and the TEC over the requested amount,
+    both considering every payment of the contract (invoices paid, prepayment and invoices left).
+    The days are counted on the business days of `calendar` when the plan was calculated on business days.
+*/
+pub fn calculate_prepayment_with_calendar(
+    plan: &Response,
+    params: &PrepaymentParams,
+    calendar: &dyn HolidayCalendar,
+) -> Result<PrepaymentResponse, PaymentPlanError> {
+    let contract = params.params;
+    let prepayment_date = params.prepayment_date;
+    let on_business_days = contract.disbursement_only_on_business_days;
+
+    if prepayment_date < plan.disbursement_date || prepayment_date >= plan.due_date {
+        return Err(PaymentPlanError::InvalidDate(DateRangeError {
+            date: prepayment_date,
+            min_date: Some(plan.disbursement_date),
+            max_date: plan.due_date.pred_opt(),
+        }));
+    }
+
+    let (paid_invoices, open_invoices): (Vec<Invoice>, Vec<Invoice>) = plan
+        .invoices
+        .iter()
+        .partition(|invoice| invoice.due_date <= prepayment_date);
+
+    let factors: Vec<f64> = open_invoices
+        .iter()
+        .map(|invoice| {
+            let days = days_until(
+                prepayment_date,
+                invoice.due_date,
+                on_business_days,
+                calendar,
+            );
+            discount_factor(contract.interest_rate, days)
+        })
+        .collect();
+
+    let outstanding_balance: f64 = open_invoices
+        .iter()
+        .zip(&factors)
+        .map(|(invoice, factor)| invoice.amount * factor)
+        .sum();
+    let outstanding_balance = round_decimal_cases(outstanding_balance, 2);
+
+    if params.amount <= 0.0 || params.amount >= outstanding_balance {
+        return Err(PaymentPlanError::InvalidPrepaymentAmount);
+    }
+
+    let remaining_balance = round_decimal_cases(outstanding_balance - params.amount, 2);
+
+    let amounts = match params.strategy {
+        PrepaymentStrategy::ReduceTerm => reduce_term(&open_invoices, &factors, remaining_balance),
+        PrepaymentStrategy::ReduceInstallment => {
+            reduce_installment(&open_invoices, outstanding_balance, remaining_balance)
+        }
+    };
+
+    let mut accumulated_factor = 0.0;
+    let mut invoices: Vec<DecimalInvoice> = open_invoices
+        .iter()
+        .zip(&factors)
+        .take(amounts.len())
+        .map(|(invoice, factor)| {
+            accumulated_factor += factor;
+            DecimalInvoice {
+                factor: *factor,
+                accumulated_factor,
+                ..(*invoice).into()
+            }
+        })
+        .collect();
+
+    let decimal_amounts: Vec<_> = amounts.iter().copied().map(to_decimal).collect();
+    insert_price_table_on_invoices(
+        &mut invoices,
+        contract.amortization_system,
+        to_decimal(remaining_balance),
+        &decimal_amounts,
+        0,
+        contract.interest_rate,
+    );
+
+    // The balance left is amortized from the prepayment date, the IOF and TAC shares are kept from the original invoices
+    let mut last_date = prepayment_date;
+    let business_diffs: Vec<i64> = invoices
+        .iter()
+        .map(|invoice| {
+            let days = days_until(last_date, invoice.due_date, on_business_days, calendar);
+            last_date = invoice.due_date;
+            days
+        })
+        .collect();
+    insert_schedule_on_invoices(
+        &mut invoices,
+        to_decimal(remaining_balance),
+        &business_diffs,
+        daily_interest_rate(contract.interest_rate),
+        contract.rounding_policy.mode,
+    );
+    let invoices: Vec<Invoice> = invoices.into_iter().map(Invoice::from).collect();
+
+    let mut payments: Vec<Payment> = paid_invoices
+        .iter()
+        .map(|invoice| Payment {
+            amount: -invoice.amount,
+            date: invoice.due_date,
+        })
+        .collect();
+    payments.push(Payment {
+        amount: -params.amount,
+        date: prepayment_date,
+    });
+    payments.extend(invoices.iter().map(|invoice| Payment {
+        amount: -invoice.amount,
+        date: invoice.due_date,
+    }));
+
+    let total_amount: f64 = payments.iter().map(|payment| -payment.amount).sum();
+    let total_amount = round_decimal_cases(total_amount, 2);
+
+    let mut eir_params = contract;
+    eir_params.requested_amount = plan.contract_amount;
+
+    let eir_monthly = calculate_eir_monthly(
+        eir_params,
+        payments.clone(),
+        1.0,
+        CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+    )?;
+    let tec_monthly = calculate_tec_monthly(
+        contract,
+        payments,
+        CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+    )?;
+
+    let eir_yearly = (1.0 + eir_monthly).powf(12.0) - 1.0;
+    let tec_yearly = (1.0 + tec_monthly).powf(12.0) - 1.0;
+
+    Ok(PrepaymentResponse {
+        prepayment_date,
+        prepayment_amount: params.amount,
+        outstanding_balance,
+        remaining_balance,
+        installment: invoices.len() as u32,
+        installment_amount: amounts[0],
+        total_amount,
+        eir_monthly: round_decimal_cases(eir_monthly, 4),
+        tec_monthly: round_decimal_cases(tec_monthly, 4),
+        eir_yearly: round_decimal_cases(eir_yearly, 6),
+        tec_yearly: round_decimal_cases(tec_yearly, 6),
+        invoices,
+    })
+}
+
+fn reduce_term(open_invoices: &[Invoice], factors: &[f64], remaining_balance: f64) -> Vec<f64> {
+    let mut amounts = Vec::with_capacity(open_invoices.len());
+    let mut balance = remaining_balance;
+
+    for (invoice, factor) in open_invoices.iter().zip(factors) {
+        let present_value = invoice.amount * factor;
+        if present_value >= balance {
+            amounts.push(round_decimal_cases(balance / factor, 2));
+            break;
+        }
+        amounts.push(invoice.amount);
+        balance -= present_value;
+    }
+
+    amounts
+}
+
+fn reduce_installment(
+    open_invoices: &[Invoice],
+    outstanding_balance: f64,
+    remaining_balance: f64,
+) -> Vec<f64> {
+    let proportion = remaining_balance / outstanding_balance;
+
+    open_invoices
+        .iter()
+        .map(|invoice| round_decimal_cases(invoice.amount * proportion, 2))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        calc::{providers::iterative::Iterative, PaymentPlan},
+        calendar::BrazilianBankHolidays,
+        err::{DateRangeError, PaymentPlanError},
+        Params, PrepaymentParams, PrepaymentStrategy, Response,
+    };
+
+    use super::calculate_prepayment_with_calendar;
+
+    fn plan(params: Params) -> Response {
+        Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prepayment_reduce_term() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+        let plan = plan(params);
+
+        let prepayment = PrepaymentParams {
+            params,
+            prepayment_date: chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap(),
+            amount: 1000.0,
+            strategy: PrepaymentStrategy::ReduceTerm,
+        };
+
+        let resp =
+            calculate_prepayment_with_calendar(&plan, &prepayment, &BrazilianBankHolidays).unwrap();
+
+        assert_eq!(resp.outstanding_balance, 2875.19);
+        assert_eq!(resp.remaining_balance, 1875.19);
+
+        // 8 invoices were left, the prepayment covers the last 3 and part of the 5th
+        let amounts: Vec<f64> = resp.invoices.iter().map(|i| i.amount).collect();
+        assert_eq!(amounts, vec![433.18, 433.18, 433.18, 433.18, 387.46]);
+        assert_eq!(
+            resp.invoices.last().unwrap().due_date,
+            chrono::NaiveDate::from_ymd_opt(2026, 5, 18).unwrap()
+        );
+
+        let last_invoice = resp.invoices.last().unwrap();
+        assert_eq!(resp.invoices[0].opening_balance, 1875.19);
+        assert_eq!(last_invoice.closing_balance, 0.0);
+
+        assert_eq!(resp.installment, 5);
+        assert_eq!(resp.installment_amount, plan.installment_amount);
+        assert_eq!(resp.total_amount, 4852.9);
+        assert_eq!(resp.eir_monthly, 0.0451);
+        assert_eq!(resp.tec_monthly, 0.0502);
+    }
+
+    #[test]
+    fn test_prepayment_reduce_installment() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+        let plan = plan(params);
+
+        let prepayment = PrepaymentParams {
+            params,
+            prepayment_date: chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap(),
+            amount: 1000.0,
+            strategy: PrepaymentStrategy::ReduceInstallment,
+        };
+
+        let resp =
+            calculate_prepayment_with_calendar(&plan, &prepayment, &BrazilianBankHolidays).unwrap();
+
+        assert_eq!(resp.outstanding_balance, 2875.19);
+        assert_eq!(resp.remaining_balance, 1875.19);
+
+        // The 8 invoices left are kept with a lower amount
+        assert_eq!(resp.installment, 8);
+        assert_eq!(resp.installment_amount, 282.52);
+        for invoice in &resp.invoices {
+            assert_eq!(invoice.amount, 282.52);
+        }
+
+        assert_eq!(resp.total_amount, 4992.88);
+        assert_eq!(resp.eir_monthly, 0.0451);
+        assert_eq!(resp.tec_monthly, 0.0496);
+    }
+
+    #[test]
+    fn test_prepayment_invalid_amount() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+        let plan = plan(params);
+
+        let prepayment = PrepaymentParams {
+            params,
+            prepayment_date: chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap(),
+            amount: 10000.0,
+            strategy: PrepaymentStrategy::ReduceTerm,
+        };
+
+        let resp = calculate_prepayment_with_calendar(&plan, &prepayment, &BrazilianBankHolidays);
+
+        assert_eq!(resp, Err(PaymentPlanError::InvalidPrepaymentAmount));
+    }
+
+    #[test]
+    fn test_prepayment_invalid_date() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+        let plan = plan(params);
+
+        let prepayment_date = chrono::NaiveDate::from_ymd_opt(2026, 9, 18).unwrap();
+        let prepayment = PrepaymentParams {
+            params,
+            prepayment_date,
+            amount: 100.0,
+            strategy: PrepaymentStrategy::ReduceTerm,
+        };
+
+        let resp = calculate_prepayment_with_calendar(&plan, &prepayment, &BrazilianBankHolidays);
+
+        assert_eq!(
+            resp,
+            Err(PaymentPlanError::InvalidDate(DateRangeError {
+                date: prepayment_date,
+                min_date: Some(plan.disbursement_date),
+                max_date: plan.due_date.pred_opt(),
+            }))
+        );
+    }
+}
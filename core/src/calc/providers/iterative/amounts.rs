@@ -1,28 +1,30 @@
-use crate::Params;
+use rust_decimal::Decimal;
+
+use crate::{util::to_decimal, Params};
 
 #[derive(Debug, PartialEq)]
 pub struct AmountsResponse {
-    pub debit_service: f64,
-    pub customer_debit_service_amount: f64,
-    pub customer_amount: f64,
-    pub calculation_basis_for_effective_interest_rate: f64,
-    pub mdr_amount: f64,
-    pub merchant_debit_service_amount: f64,
-    pub merchant_total_amount: f64,
-    pub settled_to_merchant: f64,
+    pub debit_service: Decimal,
+    pub customer_debit_service_amount: Decimal,
+    pub customer_amount: Decimal,
+    pub calculation_basis_for_effective_interest_rate: Decimal,
+    pub mdr_amount: Decimal,
+    pub merchant_debit_service_amount: Decimal,
+    pub merchant_total_amount: Decimal,
+    pub settled_to_merchant: Decimal,
 }
 
 pub fn calc(
     params: Params,
-    installments: f64,
-    customer_debit_service_proportion: f64,
-    total_iof: f64,
-    total_amount: f64,
+    installments: Decimal,
+    customer_debit_service_proportion: Decimal,
+    total_iof: Decimal,
+    total_amount: Decimal,
 ) -> AmountsResponse {
     let debit_service_percentage = params.debit_service_percentage;
     // TOTAL FINANCIADO NA PLANILHA BPM
-    let requested_amount = params.requested_amount;
-    let tac_amount = params.tac_percentage;
+    let requested_amount = to_decimal(params.requested_amount);
+    let tac_amount = to_decimal(params.tac_percentage);
 
     let debit_service = total_amount - requested_amount - tac_amount - total_iof;
     let customer_debit_service_amount = debit_service * customer_debit_service_proportion;
@@ -36,10 +38,10 @@ pub fn calc(
     let calculation_basis_for_effective_interest_rate =
         (requested_amount + debit_service * customer_debit_service_proportion) / installments;
 
-    let mdr_amount = requested_amount * params.mdr;
+    let mdr_amount = requested_amount * to_decimal(params.mdr);
 
     let merchant_debit_service_amount =
-        (debit_service + tac_amount) * debit_service_percentage as f64;
+        (debit_service + tac_amount) * Decimal::from(debit_service_percentage);
 
     let merchant_total_amount = merchant_debit_service_amount + mdr_amount;
 
@@ -59,19 +61,21 @@ pub fn calc(
 
 #[cfg(test)]
 mod test {
+    use rust_decimal_macros::dec;
+
     use crate::{calc::providers::iterative::amounts::AmountsResponse, Params};
 
     #[test]
     fn test_calc() {
         let expected = AmountsResponse {
-            debit_service: 3264.9940111656333,
-            customer_debit_service_amount: 3264.9940111656333,
-            customer_amount: 605.4000559686463,
-            calculation_basis_for_effective_interest_rate: 594.2218895092018,
-            mdr_amount: 371.55,
-            merchant_debit_service_amount: 0.0,
-            merchant_total_amount: 371.55,
-            settled_to_merchant: 7059.45,
+            debit_service: dec!(3264.994011165633),
+            customer_debit_service_amount: dec!(3264.994011165633),
+            customer_amount: dec!(605.40005596864627777777777778),
+            calculation_basis_for_effective_interest_rate: dec!(594.22188950920183333333333333),
+            mdr_amount: dec!(371.55),
+            merchant_debit_service_amount: dec!(0.0),
+            merchant_total_amount: dec!(371.55),
+            settled_to_merchant: dec!(7059.45),
         };
 
         let disbursement_date = chrono::NaiveDate::from_ymd_opt(2024, 09, 24).unwrap();
@@ -92,15 +96,16 @@ mod test {
             interest_rate: 0.04,
             min_installment_amount: 100.0,
             max_total_amount: f64::MAX,
+            ..Default::default()
         };
         let installments = 18;
-        let debit_service_proportion = 1.0;
-        let iof = 201.20699627;
-        let total_amount = 10897.201007435633;
+        let debit_service_proportion = dec!(1.0);
+        let iof = dec!(201.20699627);
+        let total_amount = dec!(10897.201007435633);
 
         let amounts = super::calc(
             params,
-            installments as f64,
+            installments.into(),
             debit_service_proportion,
             iof,
             total_amount,
@@ -1,205 +1,350 @@
 use chrono::NaiveDate;
+use rust_decimal::Decimal;
 
 use crate::{
-    util::{add_months, diff_in_business_days, get_next_business_day, round_decimal_cases},
-    Invoice,
+    util::{round_decimal_with, to_decimal},
+    AmortizationSystem, DecimalInvoice, ResidualAllocation,
 };
 
-use super::InnerParams;
+use super::{timeline::Timeline, InnerParams};
 
 #[derive(Debug, PartialEq)]
 pub struct InstallmentData {
-    pub accumulated_days: Vec<i64>,
-    pub accumulated_business_days: Vec<i64>,
-    pub diffs: Vec<i64>,
-    pub business_diffs: Vec<i64>,
-    pub amount: f64,
-    pub factor: f64,
-    pub accumulated_factor: f64,
-    pub last_due_date: NaiveDate,
-    pub due_dates: Vec<NaiveDate>,
-    pub invoices: Vec<Invoice>,
+    pub(super) accumulated_days: Vec<i64>,
+    pub(super) accumulated_business_days: Vec<i64>,
+    pub(super) diffs: Vec<i64>,
+    pub(super) business_diffs: Vec<i64>,
+    pub(super) amount: Decimal,
+    pub(super) amounts: Vec<Decimal>,
+    pub(super) factor: f64,
+    pub(super) accumulated_factor: f64,
+    pub(super) last_due_date: NaiveDate,
+    pub(super) due_dates: Vec<NaiveDate>,
+    pub(super) invoices: Vec<DecimalInvoice>,
+    pub(super) grace_periods: usize, // Number of interest-only entries before the installments
+    pub(super) residual: Decimal,    // The rounding residual allocated to an installment
+}
+
+impl InstallmentData {
+    /// The first installment, with the residual of the first installment allocation.
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn amounts(&self) -> &[Decimal] {
+        &self.amounts
+    }
+
+    pub fn due_dates(&self) -> &[NaiveDate] {
+        &self.due_dates
+    }
+
+    pub fn invoices(&self) -> &[DecimalInvoice] {
+        &self.invoices
+    }
+
+    pub fn residual(&self) -> Decimal {
+        self.residual
+    }
 }
 
 pub fn insert_price_table_on_invoices(
-    invoices: &mut Vec<Invoice>,
-    contract_amount: f64,
-    installment_amount: f64,
+    invoices: &mut [DecimalInvoice],
+    amortization_system: AmortizationSystem,
+    contract_amount: Decimal,
+    amounts: &[Decimal],
+    grace_periods: usize,
     interest_rate: f64,
 ) {
+    let interest_rate = to_decimal(interest_rate);
     let mut amortization_amount = contract_amount;
+    let constant_amortization = contract_amount / Decimal::from(invoices.len() - grace_periods);
+
+    for (i, amount) in invoices.iter_mut().zip(amounts).take(grace_periods) {
+        i.main_iof_tac = Decimal::ZERO;
+        i.debit_service = *amount;
+        i.amount = *amount;
+    }
 
-    for i in invoices {
-        let debit_service = interest_rate * amortization_amount;
-        let main_iof_tac = installment_amount - debit_service;
+    for (i, amount) in invoices.iter_mut().zip(amounts).skip(grace_periods) {
+        let (main_iof_tac, debit_service) = match amortization_system {
+            AmortizationSystem::Price => {
+                let debit_service = interest_rate * amortization_amount;
+                (amount - debit_service, debit_service)
+            }
+            AmortizationSystem::Sac => (constant_amortization, *amount - constant_amortization),
+        };
         i.main_iof_tac = main_iof_tac;
         i.debit_service = debit_service;
+        i.amount = *amount;
         amortization_amount -= main_iof_tac;
     }
 }
 
-pub fn calc(inner_params: &InnerParams) -> InstallmentData {
-    if inner_params.params.disbursement_only_on_business_days {
-        return calc_installments_on_business_days(inner_params);
-    } else {
-        return calc_installments(inner_params);
-    }
-}
-
-fn calc_installments(inner_params: &InnerParams) -> InstallmentData {
-    let daily_interest_rate = inner_params.daily_interest_rate;
+/*
+    Price: every installment is its weight times the base amount, which is the main value (less the present value of the balloon)
+        divided by the sum of the discount factors weighted the same way.
+    SAC: the main value (less the balloon) is amortized in parts proportional to the weights and each installment carries the interest
+        accrued on the outstanding balance since the previous due date.
+    Without weights every installment weighs 1, and the balloon (a percentage of the main value) is paid with the last installment.
+    The installments are rounded to cents with the rounding mode, and the rounding residual (the exact total rounded
+    less the sum of the rounded installments) is returned with them.
+*/
+fn calc_amounts(
+    inner_params: &InnerParams,
+    business_diffs: &[i64],
+    invoices: &[DecimalInvoice],
+    accumulated_factor: f64,
+) -> (Vec<Decimal>, Decimal) {
     let main_value = inner_params.main_value;
-
-    let params = inner_params.params;
-
-    let disbursement_date = params.disbursement_date;
-    let first_payment_date = params.first_payment_date;
-    let installments = params.installments;
-
-    let mut last_due_date = disbursement_date;
-    let mut due_date = first_payment_date;
-    let mut accumulated_days = 0;
-    let mut accumulated_factor = 0.0;
-
-    let mut diffs = Vec::with_capacity(installments as usize);
-    let mut accumulated_days_v = Vec::with_capacity(installments as usize);
-    let mut due_dates = Vec::with_capacity(installments as usize);
-    let mut invoices = Vec::with_capacity(installments as usize);
-
-    let mut factor = 0.0;
-
-    let base_factor = 1.0 / (1.0 + daily_interest_rate);
-
-    for i in 0..installments {
-        if i != 0 {
-            last_due_date = due_date;
-            due_date = add_months(due_date, 1);
+    let installments = business_diffs.len();
+    let last = installments - 1;
+
+    let weights = inner_params.weights;
+    let weight = |i: usize| to_decimal(weights.get(i).copied().unwrap_or(1.0));
+    let balloon = main_value * to_decimal(inner_params.params.balloon_percentage);
+
+    let exact_amounts: Vec<Decimal> = match inner_params.params.amortization_system {
+        AmortizationSystem::Price => {
+            let weighted_factor = if weights.is_empty() {
+                to_decimal(accumulated_factor)
+            } else {
+                invoices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, invoice)| weight(i) * to_decimal(invoice.factor))
+                    .sum()
+            };
+            let installment_amount =
+                (main_value - balloon * to_decimal(invoices[last].factor)) / weighted_factor;
+
+            (0..installments)
+                .map(|i| {
+                    let mut amount = weight(i) * installment_amount;
+                    if i == last {
+                        amount += balloon;
+                    }
+                    amount
+                })
+                .collect()
         }
+        AmortizationSystem::Sac => {
+            let daily_interest_rate = inner_params.daily_interest_rate;
+            let total_weight = if weights.is_empty() {
+                Decimal::from(installments)
+            } else {
+                (0..installments).map(weight).sum()
+            };
+            let mut balance = main_value;
+
+            let mut amounts = Vec::with_capacity(installments);
+            for (i, diff) in business_diffs.iter().enumerate() {
+                let mut amortization = (main_value - balloon) * weight(i) / total_weight;
+                if i == last {
+                    amortization += balloon;
+                }
+                let interest =
+                    balance * to_decimal((1.0 + daily_interest_rate).powf(*diff as f64) - 1.0);
+                amounts.push(amortization + interest);
+                balance -= amortization;
+            }
+            amounts
+        }
+    };
 
-        due_dates.push(due_date);
-
-        let diff = due_date.signed_duration_since(last_due_date).num_days();
-        diffs.push(diff);
-        accumulated_days += diff;
-        factor = base_factor.powf(accumulated_days as f64);
-        factor = round_decimal_cases(factor, 15);
-
-        accumulated_factor += factor;
+    let mode = inner_params.params.rounding_policy.mode;
+    let amounts: Vec<Decimal> = exact_amounts
+        .iter()
+        .map(|amount| round_decimal_with(*amount, 2, mode))
+        .collect();
+    let exact_total: Decimal = exact_amounts.iter().sum();
+    let residual = round_decimal_with(exact_total, 2, mode) - amounts.iter().sum::<Decimal>();
 
-        accumulated_days_v.push(accumulated_days);
+    (amounts, residual)
+}
 
-        invoices.push(Invoice {
-            accumulated_days: accumulated_days,
-            factor,
-            accumulated_factor,
-            main_iof_tac: 0.0,
-            debit_service: 0.0,
-            due_date,
-        });
+// Adds the rounding residual to the installment of the allocation, returns the residual allocated
+fn allocate_residual(
+    amounts: &mut [Decimal],
+    residual: Decimal,
+    allocation: ResidualAllocation,
+) -> Decimal {
+    let installment = match allocation {
+        ResidualAllocation::None => return Decimal::ZERO,
+        ResidualAllocation::FirstInstallment => amounts.first_mut(),
+        ResidualAllocation::LastInstallment => amounts.last_mut(),
+    };
+    match installment {
+        Some(amount) => {
+            *amount += residual;
+            residual
+        }
+        None => Decimal::ZERO,
     }
+}
 
-    let installment_amount = main_value / accumulated_factor;
-    let installment_amount = round_decimal_cases(installment_amount, 2);
-    let amount = installment_amount;
+/*
+    The installments of the plan on the first due dates of the timeline, with the interest-only
+    grace invoices before them when there is a grace window.
+*/
+pub fn calc(inner_params: &InnerParams, timeline: &Timeline) -> InstallmentData {
+    let amortization = calc_installments(inner_params, timeline);
+    if timeline.grace_due_dates.is_empty() {
+        return amortization;
+    }
 
-    return InstallmentData {
-        business_diffs: diffs.clone(),
-        accumulated_business_days: accumulated_days_v.clone(),
-        accumulated_days: accumulated_days_v,
-        diffs,
-        amount,
-        factor,
-        accumulated_factor,
-        last_due_date: due_date,
-        due_dates,
-        invoices,
-    };
+    calc_with_interest_only_grace(inner_params, timeline, amortization)
 }
 
-fn calc_installments_on_business_days(inner_params: &InnerParams) -> InstallmentData {
-    let daily_interest_rate = inner_params.daily_interest_rate;
-
+/*
+    The installments are calculated as if the contract was disbursed on the last grace due date,
+    since the balance is the same after the interest-only invoices are paid.
+    The grace invoices are then placed before them, and the accumulated days of the installments
+    are shifted to count from the real disbursement date (used by the IOF and the XIRR).
+*/
+fn calc_with_interest_only_grace(
+    inner_params: &InnerParams,
+    timeline: &Timeline,
+    amortization: InstallmentData,
+) -> InstallmentData {
     let params = inner_params.params;
+    let daily_interest_rate = inner_params.daily_interest_rate;
     let main_value = inner_params.main_value;
 
-    let disbursement_date = params.disbursement_date;
-    let first_payment_date = params.first_payment_date;
-    let installments = params.installments;
+    let grace_periods = timeline.grace_due_dates.len();
+    let len = grace_periods + amortization.due_dates.len();
 
-    let mut last_due_date = disbursement_date;
-    let mut due_date = first_payment_date;
-    let base_due_date = inner_params.base_date;
     let mut accumulated_days = 0;
     let mut accumulated_business_days = 0;
-    let mut accumulated_factor = 0.0;
-
-    let mut diffs = Vec::with_capacity(installments as usize);
-    let mut business_diffs = Vec::with_capacity(installments as usize);
-    let mut accumulated_days_v = Vec::with_capacity(installments as usize);
-    let mut accumulated_business_days_v = Vec::with_capacity(installments as usize);
-    let mut due_dates = Vec::with_capacity(installments as usize);
-    let mut invoices = Vec::with_capacity(installments as usize);
-
-    let mut factor = 0.0;
 
-    let base_factor = 1.0 / (1.0 + daily_interest_rate);
-    for i in 0..installments {
-        due_date = add_months(base_due_date, i);
-        due_date = get_next_business_day(due_date);
+    let mut diffs = Vec::with_capacity(len);
+    let mut business_diffs = Vec::with_capacity(len);
+    let mut accumulated_days_v = Vec::with_capacity(len);
+    let mut accumulated_business_days_v = Vec::with_capacity(len);
+    let mut amounts = Vec::with_capacity(len);
+    let mut due_dates = Vec::with_capacity(len);
+    let mut invoices = Vec::with_capacity(len);
 
-        due_dates.push(due_date);
-
-        let diff = due_date.signed_duration_since(last_due_date).num_days();
-        let b_diff = diff_in_business_days(last_due_date, due_date);
-
-        diffs.push(diff);
-        business_diffs.push(b_diff);
+    for i in 0..grace_periods {
+        let due_date = timeline.grace_due_dates[i];
+        let diff = timeline.grace_diffs[i];
+        let b_diff = timeline.grace_business_diffs[i];
 
         accumulated_days += diff;
         accumulated_business_days += b_diff;
 
-        factor = base_factor.powf(accumulated_business_days as f64);
-        factor = round_decimal_cases(factor, 15);
+        let interest =
+            main_value * to_decimal((1.0 + daily_interest_rate).powf(b_diff as f64) - 1.0);
 
-        accumulated_factor += factor;
+        diffs.push(diff);
+        business_diffs.push(b_diff);
         accumulated_days_v.push(accumulated_days);
         accumulated_business_days_v.push(accumulated_business_days);
-
-        invoices.push(Invoice {
+        amounts.push(round_decimal_with(interest, 2, params.rounding_policy.mode));
+        due_dates.push(due_date);
+        invoices.push(DecimalInvoice {
             accumulated_days,
-            factor,
-            accumulated_factor,
-            main_iof_tac: 0.0,
-            debit_service: 0.0,
+            factor: 0.0,
+            accumulated_factor: 0.0,
             due_date,
+            ..Default::default()
         });
-
-        last_due_date = due_date;
     }
 
-    let installment_amount = main_value / accumulated_factor;
-    let installment_amount = round_decimal_cases(installment_amount, 2);
-    let amount = installment_amount;
-
-    return InstallmentData {
+    diffs.extend(amortization.diffs);
+    business_diffs.extend(amortization.business_diffs);
+    accumulated_days_v.extend(
+        amortization
+            .accumulated_days
+            .iter()
+            .map(|days| days + accumulated_days),
+    );
+    accumulated_business_days_v.extend(
+        amortization
+            .accumulated_business_days
+            .iter()
+            .map(|days| days + accumulated_business_days),
+    );
+    amounts.extend(amortization.amounts);
+    due_dates.extend(amortization.due_dates);
+    invoices.extend(amortization.invoices.into_iter().map(|mut invoice| {
+        invoice.accumulated_days += accumulated_days;
+        invoice
+    }));
+
+    InstallmentData {
         accumulated_days: accumulated_days_v,
-        diffs,
         accumulated_business_days: accumulated_business_days_v,
+        diffs,
         business_diffs,
+        amount: amortization.amount,
+        amounts,
+        factor: amortization.factor,
+        accumulated_factor: amortization.accumulated_factor,
+        last_due_date: amortization.last_due_date,
+        due_dates,
+        invoices,
+        grace_periods,
+        residual: amortization.residual,
+    }
+}
+
+// The installments on the first due dates of the timeline, counting from the last grace due date
+fn calc_installments(inner_params: &InnerParams, timeline: &Timeline) -> InstallmentData {
+    let params = inner_params.params;
+    let installments = params.installments as usize;
+    let last = installments - 1;
+
+    let invoices: Vec<DecimalInvoice> = (0..installments)
+        .map(|i| DecimalInvoice {
+            accumulated_days: timeline.accumulated_days[i],
+            factor: timeline.factors[i],
+            accumulated_factor: timeline.accumulated_factors[i],
+            due_date: timeline.due_dates[i],
+            ..Default::default()
+        })
+        .collect();
+
+    let business_diffs = &timeline.business_diffs[..installments];
+    let accumulated_factor = timeline.accumulated_factors[last];
+
+    let (mut amounts, residual) =
+        calc_amounts(inner_params, business_diffs, &invoices, accumulated_factor);
+    let residual = allocate_residual(
+        &mut amounts,
+        residual,
+        params.rounding_policy.residual_allocation,
+    );
+    // Read after the allocation, so the first invoice and the installment amount match
+    let amount = amounts[0];
+
+    return InstallmentData {
+        accumulated_days: timeline.accumulated_days[..installments].to_vec(),
+        accumulated_business_days: timeline.accumulated_business_days[..installments].to_vec(),
+        diffs: timeline.diffs[..installments].to_vec(),
+        business_diffs: business_diffs.to_vec(),
         amount,
-        factor,
+        amounts,
+        factor: timeline.factors[last],
         accumulated_factor,
-        last_due_date: due_date,
-        due_dates,
+        last_due_date: timeline.due_dates[last],
+        due_dates: timeline.due_dates[..installments].to_vec(),
         invoices,
+        grace_periods: 0,
+        residual,
     };
 }
 
 #[cfg(test)]
 mod test {
+    use rust_decimal_macros::dec;
+
     use crate::{
-        calc::providers::iterative::{installment::InstallmentData, InnerParams},
-        Invoice, Params,
+        calc::providers::iterative::{
+            installment::InstallmentData, timeline::Timeline, InnerParams,
+        },
+        calendar::BrazilianBankHolidays,
+        DecimalInvoice, Params,
     };
 
     #[test]
@@ -242,157 +387,196 @@ mod test {
             business_diffs: vec![
                 30, 31, 30, 31, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31, 31, 28,
             ],
-            amount: 589.44,
+            amount: dec!(589.44),
+            amounts: vec![dec!(589.44); 18],
             factor: 0.489771731149302,
             accumulated_factor: 12.60688188087214,
             last_due_date,
             due_dates,
             invoices: vec![
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 30,
                     factor: 0.961538521141742,
                     accumulated_factor: 0.961538521141742,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 10, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 61,
                     factor: 0.923348394036885,
                     accumulated_factor: 1.884886915178627,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 11, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 91,
                     factor: 0.887835049300829,
                     accumulated_factor: 2.772721964479456,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 122,
                     factor: 0.852572256770495,
                     accumulated_factor: 3.625294221249951,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 01, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 153,
                     factor: 0.818710022303302,
                     accumulated_factor: 4.444004243553253,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 02, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 181,
                     factor: 0.789282272705526,
                     accumulated_factor: 5.2332865162587785,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 03, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 212,
                     factor: 0.757933772719854,
                     accumulated_factor: 5.991220288978632,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 04, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 242,
                     factor: 0.72878251894443,
                     accumulated_factor: 6.720002807923063,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 05, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 273,
                     factor: 0.699836931827195,
                     accumulated_factor: 7.419839739750258,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 06, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 303,
                     factor: 0.672920168469495,
                     accumulated_factor: 8.092759908219753,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 07, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 334,
                     factor: 0.646193307090341,
                     accumulated_factor: 8.738953215310094,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 08, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 365,
                     factor: 0.620527975967898,
                     accumulated_factor: 9.359481191277991,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 09, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 395,
                     factor: 0.596661552339251,
                     accumulated_factor: 9.956142743617242,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 10, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 426,
                     factor: 0.572963510064917,
                     accumulated_factor: 10.529106253682158,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 11, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 456,
                     factor: 0.550926486136002,
                     accumulated_factor: 11.08003273981816,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 487,
                     factor: 0.529044936860178,
                     accumulated_factor: 11.609077676678337,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 01, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 518,
                     factor: 0.5080324730445,
                     accumulated_factor: 12.117110149722837,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 02, 24).unwrap(),
+                    ..Default::default()
                 },
-                Invoice {
+                DecimalInvoice {
                     accumulated_days: 546,
                     factor: 0.489771731149302,
                     accumulated_factor: 12.60688188087214,
-                    main_iof_tac: 0.0,
-                    debit_service: 0.0,
+                    main_iof_tac: dec!(0.0),
+                    debit_service: dec!(0.0),
+                    amount: dec!(0.0),
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 03, 24).unwrap(),
+                    ..Default::default()
                 },
             ],
+            grace_periods: 0,
+            residual: dec!(0.0),
         };
 
         let disbursement_date = chrono::NaiveDate::from_ymd_opt(2024, 09, 24).unwrap();
@@ -413,13 +597,17 @@ mod test {
                 interest_rate: 0.04,
                 min_installment_amount: 100.0,
                 max_total_amount: f64::MAX,
+                ..Default::default()
             },
-            main_value: 7431.0,
+            main_value: dec!(7431.0),
             daily_interest_rate: 0.00130821,
             base_date: first_payment_date,
+            due_dates: &[],
+            weights: &[],
+            calendar: &BrazilianBankHolidays,
         };
 
-        let data = super::calc(&params);
+        let data = super::calc(&params, &Timeline::new(&params));
 
         assert_eq!(data, expected);
     }
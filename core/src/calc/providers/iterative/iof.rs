@@ -1,67 +1,171 @@
-use crate::util::round_decimal_cases;
+use rust_decimal::Decimal;
+
+use crate::{
+    err::PaymentPlanError,
+    util::{round_decimal_with, to_decimal, to_f64},
+};
+
+use super::{installment, installment::InstallmentData, timeline::Timeline, InnerParams};
+
+/// The IOF of a plan once it converged, with the installments that finance it.
+pub struct ConvergedIof {
+    pub(super) iof: Decimal,
+    pub(super) data: InstallmentData,
+    pub(super) amounts_without_iof: Vec<Decimal>,
+    pub(super) iterations: u32,
+    pub(super) residual: Decimal,
+}
+
+impl ConvergedIof {
+    /// The total IOF, rounded with the rounding policy.
+    pub fn iof(&self) -> Decimal {
+        self.iof
+    }
+
+    /// The installments with the IOF in the main value.
+    pub fn data(&self) -> &InstallmentData {
+        &self.data
+    }
+
+    /// The installments of the requested amount alone, before the IOF is known.
+    pub fn amounts_without_iof(&self) -> &[Decimal] {
+        &self.amounts_without_iof
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// The change of the IOF on the last iteration.
+    pub fn residual(&self) -> Decimal {
+        self.residual
+    }
+}
 
-use super::{installment::InstallmentData, InnerParams};
+/*
+    The IOF is financed, so the installments are recalculated with it in the main value until it converges
+    within the tolerance of the IOF convergence, leaving the requested amount plus the IOF as the main value.
+*/
+pub fn converge(
+    inner_params: &mut InnerParams,
+    timeline: &Timeline,
+) -> Result<ConvergedIof, PaymentPlanError> {
+    let requested_amount = to_decimal(inner_params.params.requested_amount);
+    let convergence = inner_params.params.iof_convergence;
+    let tolerance = to_decimal(convergence.tolerance);
+
+    let data = installment::calc(inner_params, timeline);
+    let mut iof = calc(inner_params, &data);
+    let amounts_without_iof = data.amounts;
+
+    let mut iterations = 1;
+    let residual = loop {
+        inner_params.main_value = requested_amount + iof;
+        let data = installment::calc(inner_params, timeline);
+        let next_iof = calc(inner_params, &data);
+        let residual = (next_iof - iof).abs();
+        iof = next_iof;
+        iterations += 1;
+
+        if residual <= tolerance {
+            break residual;
+        }
+        if iterations >= convergence.max_iterations {
+            return Err(PaymentPlanError::IofConvergenceError {
+                iterations,
+                residual: to_f64(residual),
+            });
+        }
+    };
+    inner_params.main_value = requested_amount + iof;
+
+    Ok(ConvergedIof {
+        iof: round_decimal_with(iof, 2, inner_params.params.rounding_policy.mode),
+        data: installment::calc(inner_params, timeline),
+        amounts_without_iof,
+        iterations,
+        residual,
+    })
+}
 
-pub fn calc(inner_params: &InnerParams, data: &InstallmentData) -> f64 {
-    let mut total_iof = 0.0;
+pub fn calc(inner_params: &InnerParams, data: &InstallmentData) -> Decimal {
+    let total_iof: Decimal = calc_installments(inner_params, data).iter().sum();
+    let total_iof = round_decimal_with(total_iof, 2, inner_params.params.rounding_policy.mode);
+    return total_iof;
+}
+
+/*
+    The IOF of each installment: the overall IOF plus the daily IOF (limited to 365 days)
+    over the part of the main value amortized by the installment.
+*/
+pub fn calc_installments(inner_params: &InnerParams, data: &InstallmentData) -> Vec<Decimal> {
     let params = inner_params.params;
     let installments = params.installments;
 
-    let iof_percentage = params.iof_percentage;
+    let iof_percentage = to_decimal(params.iof_percentage);
+
+    let iof_overall = to_decimal(params.iof_overall);
 
-    let iof_overall = params.iof_overall;
+    let mode = params.rounding_policy.mode;
 
     let daily_interest_rate = inner_params.daily_interest_rate;
 
     let main_value = inner_params.main_value;
     let mut main_value_l = main_value;
 
-    main_value_l = round_decimal_cases(main_value_l, 8);
+    main_value_l = round_decimal_with(main_value_l, 8, mode);
 
-    let installment_amount = data.amount;
-    let mut acc_installment_amount_without_fee = 0.0;
+    let mut iofs = Vec::with_capacity(installments as usize);
+    let mut acc_installment_amount_without_fee = Decimal::ZERO;
+    // The interest-only grace entries don't amortize anything, so they are skipped
     for j in 0..installments {
-        let mut accumulated_days = data.accumulated_days[j as usize];
-        let business_diff = data.business_diffs[j as usize];
-        let fee = main_value_l * ((1.0 + daily_interest_rate).powf(business_diff as f64) - 1.0);
+        let k = j as usize + data.grace_periods;
+        let installment_amount = data.amounts[k];
+        let mut accumulated_days = data.accumulated_days[k];
+        let business_diff = data.business_diffs[k];
+        let fee =
+            main_value_l * to_decimal((1.0 + daily_interest_rate).powf(business_diff as f64) - 1.0);
 
-        let fee = round_decimal_cases(fee, 7);
+        let fee = round_decimal_with(fee, 7, mode);
 
-        let installment_amount_without_fee: f64;
+        let installment_amount_without_fee: Decimal;
         if j == installments - 1 {
             installment_amount_without_fee = main_value - acc_installment_amount_without_fee;
         } else {
             installment_amount_without_fee = installment_amount - fee;
         }
 
-        let installment_amount_without_fee = round_decimal_cases(installment_amount_without_fee, 8);
+        let installment_amount_without_fee =
+            round_decimal_with(installment_amount_without_fee, 8, mode);
 
         let main_iof = installment_amount_without_fee * iof_overall;
         if accumulated_days >= 365 {
             accumulated_days = 365;
         }
-        let main_iof = round_decimal_cases(main_iof, 2);
+        let main_iof = round_decimal_with(main_iof, 2, mode);
 
         let installment_iof =
-            installment_amount_without_fee * accumulated_days as f64 * iof_percentage;
+            installment_amount_without_fee * Decimal::from(accumulated_days) * iof_percentage;
 
-        let installment_iof = round_decimal_cases(installment_iof, 8);
+        let installment_iof = round_decimal_with(installment_iof, 8, mode);
 
         let iof = main_iof + installment_iof;
 
-        total_iof += iof;
+        iofs.push(iof);
         main_value_l = main_value_l + fee - installment_amount;
-        main_value_l = round_decimal_cases(main_value_l, 8);
+        main_value_l = round_decimal_with(main_value_l, 8, mode);
         acc_installment_amount_without_fee += installment_amount_without_fee;
     }
-    let total_iof = round_decimal_cases(total_iof, 2);
-    return total_iof;
+    iofs
 }
 
 #[cfg(test)]
 mod test {
+    use rust_decimal_macros::dec;
+
     use crate::{
         calc::providers::iterative::{installment::InstallmentData, InnerParams},
+        calendar::BrazilianBankHolidays,
         Params,
     };
 
@@ -85,10 +189,14 @@ mod test {
                 interest_rate: 0.04,
                 min_installment_amount: 100.0,
                 max_total_amount: f64::MAX,
+                ..Default::default()
             },
-            main_value: 7431.0,
+            main_value: dec!(7431.0),
             daily_interest_rate: 0.00130821,
             base_date: first_payment_date,
+            due_dates: &[],
+            weights: &[],
+            calendar: &BrazilianBankHolidays,
         };
 
         let due_dates = vec![
@@ -129,16 +237,19 @@ mod test {
             business_diffs: vec![
                 30, 31, 30, 31, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31, 31, 28,
             ],
-            amount: 589.4399638402917,
+            amount: dec!(589.4399638402917),
+            amounts: vec![dec!(589.4399638402917); 18],
             factor: 0.48977173114928746,
             accumulated_factor: 12.606881880871965,
             last_due_date,
             due_dates,
             invoices: vec![],
+            grace_periods: 0,
+            residual: dec!(0.0),
         };
 
         let iof = super::calc(&params, &i_cal);
 
-        assert_eq!(iof, 195.9);
+        assert_eq!(iof, dec!(195.9));
     }
 }
@@ -1,30 +1,73 @@
 use installment::InstallmentData;
+use rust_decimal::Decimal;
+use timeline::Timeline;
 
 use crate::{
     calc::{
-        inner_xirr::{eir::calculate_eir_monthly, prepare_xirr_params, tec::calculate_tec_monthly},
+        inner_xirr::{
+            eir::calculate_eir_monthly, periodic_rate, prepare_xirr_params_with_amounts,
+            tec::calculate_tec_monthly,
+        },
+        selection::{exclusion_reason, selected_installments},
         PaymentPlan,
     },
-    err::PaymentPlanError,
-    util::{get_next_business_day, round_decimal_cases},
-    Params, Response,
+    calendar::{BrazilianBankHolidays, HolidayCalendar},
+    err::{DateRangeError, PaymentPlanError},
+    util::{
+        adjust_to_business_day, first_due_date, round_decimal_cases, round_decimal_cases_with,
+        round_decimal_with, to_decimal, to_f64,
+    },
+    CustomScheduleParams, DecimalResponse, ExcludedInstallment, Params, Response, SelectedPlans,
 };
 
 const POTENCY: f64 = 0.003968253968253968; // 1/252
-const CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE: f64 = 0.08333333333333333; // 1/12
-
-const NUM_OF_RUNS: u32 = 7;
+pub(crate) const CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE: f64 = 0.08333333333333333; // 1/12
 
 mod amounts;
-mod installment;
-mod iof;
-
-#[derive(Default, Debug, Clone, Copy)]
-struct InnerParams {
+pub mod installment;
+pub mod iof;
+mod schedule;
+pub mod timeline;
+
+pub(crate) use installment::insert_price_table_on_invoices;
+pub(crate) use schedule::insert_schedule_on_invoices;
+
+/*
+    The params of the Iterative building blocks: the main value is the amount financed,
+    the requested amount plus the IOF once it is known.
+*/
+#[derive(Clone, Copy)]
+pub struct InnerParams<'a> {
     params: Params,
-    main_value: f64,
+    main_value: Decimal,
     daily_interest_rate: f64,
     base_date: chrono::NaiveDate,
+    due_dates: &'a [chrono::NaiveDate], // The explicit due dates of the installments, empty to step monthly from the base date
+    weights: &'a [f64], // The relative size of each installment, empty for equal installments
+    calendar: &'a dyn HolidayCalendar, // The holidays of the business days
+}
+
+impl<'a> InnerParams<'a> {
+    /// The params of the monthly plans of `params` on `calendar`, with the requested amount as the main value.
+    pub fn new(
+        params: Params,
+        calendar: &'a dyn HolidayCalendar,
+    ) -> Result<Self, PaymentPlanError> {
+        inner_params(params, calendar)
+    }
+
+    /// The params with the dates moved to the due day policy and the business days.
+    pub fn params(&self) -> Params {
+        self.params
+    }
+
+    pub fn main_value(&self) -> Decimal {
+        self.main_value
+    }
+
+    pub fn daily_interest_rate(&self) -> f64 {
+        self.daily_interest_rate
+    }
 }
 
 /**
@@ -33,55 +76,67 @@ struct InnerParams {
  * It calculates the payment plan by iterating through the installments over and over again to better approximate the real iof value.
  * It's slower than the simple implementation, but it provides a more accurate result.
  * This is the recommended implementation for most cases.
+ * The monetary values are calculated with exact decimals, the f64 of the `Response` are converted from them.
  */
 pub struct Iterative;
 
 impl PaymentPlan for Iterative {
-    fn calculate_payment_plan(
+    fn calculate_payment_plan(&self, params: Params) -> Result<Vec<Response>, PaymentPlanError> {
+        self.calculate_payment_plan_with_calendar(params, &BrazilianBankHolidays)
+    }
+
+    fn calculate_payment_plan_with_calendar(
         &self,
-        mut params: Params,
+        params: Params,
+        calendar: &dyn HolidayCalendar,
     ) -> Result<Vec<Response>, PaymentPlanError> {
-        let base_date = params.first_payment_date;
-        if params.requested_amount <= 0.0 {
-            return Err(PaymentPlanError::InvalidRequestedAmount);
-        }
-        if params.installments == 0 {
-            return Err(PaymentPlanError::InvalidNumberOfInstallments);
-        }
-
-        if params.disbursement_only_on_business_days {
-            //Change the base date to the next business day
-            params.disbursement_date = get_next_business_day(params.disbursement_date);
-            params.first_payment_date = get_next_business_day(params.first_payment_date);
-        }
-
-        let mut response = Vec::with_capacity(params.installments as usize);
+        let plans = self.calculate_decimal_payment_plan_with_calendar(params, calendar)?;
+        Ok(plans.into_iter().map(Response::from).collect())
+    }
 
-        let interest_rate = params.interest_rate;
+    fn calculate_selected_payment_plans(
+        &self,
+        params: Params,
+        installments: &[u32],
+    ) -> Result<SelectedPlans, PaymentPlanError> {
+        self.calculate_selected_payment_plans_with_calendar(
+            params,
+            installments,
+            &BrazilianBankHolidays,
+        )
+    }
+}
 
-        let min_installment_amount = params.min_installment_amount;
-        let max_total_amount = params.max_total_amount;
+impl Iterative {
+    pub fn calculate_decimal_payment_plan(
+        &self,
+        params: Params,
+    ) -> Result<Vec<DecimalResponse>, PaymentPlanError> {
+        self.calculate_decimal_payment_plan_with_calendar(params, &BrazilianBankHolidays)
+    }
 
-        let annual_interest_rate = (1.0 + interest_rate).powf(12.0) - 1.0;
-        let daily_interest_rate = (1.0 + annual_interest_rate).powf(POTENCY) - 1.0;
+    pub fn calculate_decimal_payment_plan_with_calendar(
+        &self,
+        params: Params,
+        calendar: &dyn HolidayCalendar,
+    ) -> Result<Vec<DecimalResponse>, PaymentPlanError> {
+        let mut inner_params = inner_params(params, calendar)?;
+        let mut response = Vec::with_capacity(params.installments as usize);
 
-        let daily_interest_rate = round_decimal_cases(daily_interest_rate, 10);
-        let main_value = params.requested_amount;
+        // The due dates, days and factors of the longest plan are shared by the shorter ones
+        let timeline = Timeline::new(&inner_params);
 
         for i in 1..=params.installments {
-            params.installments = i;
-            let params = InnerParams {
-                params,
-                main_value,
-                daily_interest_rate,
-                base_date,
-            };
-
-            let resp = calc(params)?;
-            if resp.installment_amount < min_installment_amount {
-                break;
-            }
-            if resp.total_amount > max_total_amount {
+            inner_params.params.installments = i;
+
+            let resp = calc(inner_params, &timeline)?;
+            if exclusion_reason(
+                &params,
+                smallest_installment_amount(&resp),
+                to_f64(resp.total_amount),
+            )
+            .is_some()
+            {
                 break;
             }
             response.push(resp);
@@ -89,60 +144,236 @@ impl PaymentPlan for Iterative {
 
         Ok(response)
     }
-}
 
-fn calc(mut params: InnerParams) -> Result<Response, PaymentPlanError> {
-    let debit_service_percentage = params.params.debit_service_percentage;
-    let requested_amount = params.params.requested_amount;
+    /*
+        The plans of the selected installments only, on the timeline of the largest one, so a single plan
+        costs the same as the last plan of `calculate_payment_plan` and not all the plans before it.
+    */
+    pub fn calculate_selected_payment_plans_with_calendar(
+        &self,
+        mut params: Params,
+        installments: &[u32],
+        calendar: &dyn HolidayCalendar,
+    ) -> Result<SelectedPlans, PaymentPlanError> {
+        let installments = selected_installments(installments)?;
+        params.installments = installments[installments.len() - 1];
+
+        let mut inner_params = inner_params(params, calendar)?;
+        let timeline = Timeline::new(&inner_params);
+
+        let mut selected = SelectedPlans::default();
+        for installment in installments {
+            inner_params.params.installments = installment;
+
+            let resp = calc(inner_params, &timeline)?;
+            match exclusion_reason(
+                &params,
+                smallest_installment_amount(&resp),
+                to_f64(resp.total_amount),
+            ) {
+                Some(reason) => selected.excluded.push(ExcludedInstallment {
+                    installment,
+                    reason,
+                }),
+                None => selected.plans.push(resp.into()),
+            }
+        }
 
-    let mut data = installment::calc(&params);
+        Ok(selected)
+    }
 
-    let mut iof = iof::calc(&params, &data);
+    /*
+        A single plan with one installment due on each of the explicit due dates, instead of monthly from the first payment date.
+        The installments are sized by the weights (Price: each one is its weight times the base amount,
+        SAC: each one amortizes the principal in proportion to its weight) and the balloon is paid with the last one,
+        the IOF and the XIRR then run on these cash flows as on any other plan.
+        With disbursement only on business days, each due date is moved to a business day with the business day convention.
+    */
+    pub fn calculate_custom_payment_plan(
+        &self,
+        params: &CustomScheduleParams,
+    ) -> Result<Response, PaymentPlanError> {
+        self.calculate_custom_payment_plan_with_calendar(params, &BrazilianBankHolidays)
+    }
 
-    let debit_service = data.amount;
+    pub fn calculate_custom_payment_plan_with_calendar(
+        &self,
+        params: &CustomScheduleParams,
+        calendar: &dyn HolidayCalendar,
+    ) -> Result<Response, PaymentPlanError> {
+        let due_dates = params.due_dates.as_slice();
+        let weights = params.weights.as_slice();
+        let mut params = params.params;
 
-    params.main_value = requested_amount + iof;
-    for _ in 1..NUM_OF_RUNS {
-        data = installment::calc(&params);
-        iof = iof::calc(&params, &data);
-        params.main_value = requested_amount + iof;
+        if params.requested_amount <= 0.0 {
+            return Err(PaymentPlanError::InvalidRequestedAmount);
+        }
+        if due_dates.is_empty() {
+            return Err(PaymentPlanError::InvalidNumberOfInstallments);
+        }
+        if !weights.is_empty()
+            && (weights.len() != due_dates.len() || weights.iter().any(|weight| *weight <= 0.0))
+        {
+            return Err(PaymentPlanError::InvalidInstallmentWeights);
+        }
+        validate_balloon_percentage(&params)?;
+
+        let mut last_due_date = params.disbursement_date;
+        for due_date in due_dates {
+            if *due_date <= last_due_date {
+                return Err(PaymentPlanError::InvalidDate(DateRangeError {
+                    date: *due_date,
+                    min_date: last_due_date.succ_opt(),
+                    max_date: None,
+                }));
+            }
+            last_due_date = *due_date;
+        }
+
+        params.installments = due_dates.len() as u32;
+        params.first_payment_date = due_dates[0];
+        let base_date = params.first_payment_date;
+
+        if params.disbursement_only_on_business_days {
+            params.disbursement_date = adjust_to_business_day(
+                params.disbursement_date,
+                params.business_day_convention,
+                calendar,
+            );
+            params.first_payment_date = adjust_to_business_day(
+                params.first_payment_date,
+                params.business_day_convention,
+                calendar,
+            );
+        }
+
+        let params = InnerParams {
+            params,
+            main_value: to_decimal(params.requested_amount),
+            daily_interest_rate: daily_interest_rate(params.interest_rate),
+            base_date,
+            due_dates,
+            weights,
+            calendar,
+        };
+        let resp = calc(params, &Timeline::new(&params))?;
+        Ok(resp.into())
+    }
+}
+
+/*
+    Checks the params and moves the first payment date to the due day policy, and with disbursement only on
+    business days, the disbursement and the first payment dates to business days with the business day convention.
+    The base date is the first payment date before it is moved to a business day.
+*/
+fn inner_params(
+    mut params: Params,
+    calendar: &dyn HolidayCalendar,
+) -> Result<InnerParams<'_>, PaymentPlanError> {
+    if params.requested_amount <= 0.0 {
+        return Err(PaymentPlanError::InvalidRequestedAmount);
+    }
+    if params.installments == 0 {
+        return Err(PaymentPlanError::InvalidNumberOfInstallments);
+    }
+    if !params.due_day_policy.is_valid() {
+        return Err(PaymentPlanError::InvalidDueDay);
+    }
+    validate_balloon_percentage(&params)?;
+
+    params.first_payment_date = first_due_date(
+        params.first_payment_date,
+        params.periodicity,
+        params.due_day_policy,
+    );
+    let base_date = params.first_payment_date;
+
+    if params.disbursement_only_on_business_days {
+        //Move the base date to a business day with the business day convention
+        params.disbursement_date = adjust_to_business_day(
+            params.disbursement_date,
+            params.business_day_convention,
+            calendar,
+        );
+        params.first_payment_date = adjust_to_business_day(
+            params.first_payment_date,
+            params.business_day_convention,
+            calendar,
+        );
+    }
+
+    Ok(InnerParams {
+        params,
+        main_value: to_decimal(params.requested_amount),
+        daily_interest_rate: daily_interest_rate(params.interest_rate),
+        base_date,
+        due_dates: &[],
+        weights: &[],
+        calendar,
+    })
+}
+
+fn validate_balloon_percentage(params: &Params) -> Result<(), PaymentPlanError> {
+    if !(0.0..1.0).contains(&params.balloon_percentage) {
+        return Err(PaymentPlanError::InvalidBalloonPercentage);
     }
+    Ok(())
+}
 
-    let iof = round_decimal_cases(iof, 2);
-    let mut data = installment::calc(&params);
+// The monthly interest rate compounded to the year and taken back to a business day (1/252)
+pub(crate) fn daily_interest_rate(interest_rate: f64) -> f64 {
+    let annual_interest_rate = (1.0 + interest_rate).powf(12.0) - 1.0;
+    let daily_interest_rate = (1.0 + annual_interest_rate).powf(POTENCY) - 1.0;
+
+    round_decimal_cases(daily_interest_rate, 10)
+}
+
+fn calc(mut params: InnerParams, timeline: &Timeline) -> Result<DecimalResponse, PaymentPlanError> {
+    let debit_service_percentage = params.params.debit_service_percentage;
+    let requested_amount = to_decimal(params.params.requested_amount);
+
+    let iof::ConvergedIof {
+        iof,
+        mut data,
+        amounts_without_iof: debit_service,
+        iterations: iof_iterations,
+        residual: iof_residual,
+    } = iof::converge(&mut params, timeline)?;
+
+    let mode = params.params.rounding_policy.mode;
+    let iofs = iof::calc_installments(&params, &data);
+    let daily_interest_rate = params.daily_interest_rate;
 
     let customer_amount = data.amount;
 
     let installment_amount = data.amount;
     let installments = params.params.installments;
-    let total_amount = installment_amount * installments as f64;
-    let total_amount = round_decimal_cases(total_amount, 2);
-    let contract_amount = params.params.requested_amount + iof;
+    let total_amount: Decimal = data.amounts.iter().sum();
+    let contract_amount = requested_amount + iof;
     let accumulated_days = data.accumulated_days.pop().unwrap();
     let accumulated_days_index = data.accumulated_factor;
-    let customer_debit_service_proportion = 1.0 - debit_service_percentage as f64 / 100.0;
+    let customer_debit_service_proportion =
+        Decimal::ONE - Decimal::from(debit_service_percentage) / Decimal::ONE_HUNDRED;
 
     let params = params.params;
 
     let amounts = amounts::calc(
         params,
-        installments as f64,
+        Decimal::from(installments),
         customer_debit_service_proportion,
         iof,
         total_amount,
     );
 
-    let (eir_params, tec_params) = prepare_xirr_params(
-        installments,
-        &data.due_dates,
-        debit_service,
-        customer_amount,
-    );
+    let debit_service: Vec<f64> = debit_service.into_iter().map(to_f64).collect();
+    let amounts_f64: Vec<f64> = data.amounts.iter().copied().map(to_f64).collect();
+    let (eir_params, tec_params) =
+        prepare_xirr_params_with_amounts(&data.due_dates, &debit_service, &amounts_f64);
 
     let eir_monthly = calculate_eir_monthly(
         params,
         eir_params,
-        customer_debit_service_proportion,
+        to_f64(customer_debit_service_proportion),
         CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
     )?;
 
@@ -156,33 +387,52 @@ fn calc(mut params: InnerParams) -> Result<Response, PaymentPlanError> {
 
     let tec_yearly = (1.0 + tec_monthly).powf(12.0) - 1.0;
 
-    let eir_monthly = round_decimal_cases(eir_monthly, 4);
-    let tec_monthly = round_decimal_cases(tec_monthly, 4);
+    let eir_periodic = periodic_rate(eir_monthly, params.periodicity);
+    let tec_periodic = periodic_rate(tec_monthly, params.periodicity);
 
-    let eir_yearly = round_decimal_cases(eir_yearly, 6);
-    let tec_yearly = round_decimal_cases(tec_yearly, 6);
+    let eir_monthly = round_decimal_cases_with(eir_monthly, 4, mode);
+    let tec_monthly = round_decimal_cases_with(tec_monthly, 4, mode);
+    let eir_periodic = round_decimal_cases_with(eir_periodic, 4, mode);
+    let tec_periodic = round_decimal_cases_with(tec_periodic, 4, mode);
+
+    let eir_yearly = round_decimal_cases_with(eir_yearly, 6, mode);
+    let tec_yearly = round_decimal_cases_with(tec_yearly, 6, mode);
 
     let interest_rate = params.interest_rate;
 
-    let present_value = present_value(installment_amount, &data, params.interest_rate);
-    let present_value = round_decimal_cases(present_value, 2);
+    let present_value = present_value(&data, params.interest_rate);
+    let present_value = round_decimal_with(present_value, 2, mode);
     let pre_disbursement_amount = present_value - iof;
-    let pre_disbursement_amount = round_decimal_cases(pre_disbursement_amount, 2);
     let diff = pre_disbursement_amount - requested_amount;
-    let diff = round_decimal_cases(diff, 2);
 
     let paid_iof = iof + diff;
-    let paid_iof = round_decimal_cases(paid_iof, 2);
 
     let mut invoices = data.invoices;
     installment::insert_price_table_on_invoices(
         &mut invoices,
+        params.amortization_system,
         contract_amount,
-        installment_amount,
+        &data.amounts,
+        data.grace_periods,
         interest_rate,
     );
+    schedule::insert_schedule_on_invoices(
+        &mut invoices,
+        contract_amount,
+        &data.business_diffs,
+        daily_interest_rate,
+        mode,
+    );
+    schedule::insert_iof_and_tac_on_invoices(
+        &mut invoices,
+        &iofs,
+        iof,
+        to_decimal(params.tac_percentage),
+        data.grace_periods,
+        mode,
+    );
 
-    let resp = Response {
+    let resp = DecimalResponse {
         contract_amount,
         total_amount,
         installment_amount,
@@ -208,12 +458,18 @@ fn calc(mut params: InnerParams) -> Result<Response, PaymentPlanError> {
         tec_yearly,
         eir_monthly,
         tec_monthly,
+        eir_periodic,
+        tec_periodic,
         effective_interest_rate: eir_monthly,
         total_effective_cost: tec_monthly,
         disbursement_date: params.disbursement_date,
         pre_disbursement_amount,
         paid_total_iof: paid_iof,
         paid_contract_amount: requested_amount + paid_iof,
+        due_day_policy: params.due_day_policy,
+        rounding_residual: data.residual,
+        iof_iterations,
+        iof_residual,
         invoices,
         ..Default::default()
     };
@@ -221,19 +477,31 @@ fn calc(mut params: InnerParams) -> Result<Response, PaymentPlanError> {
     return Ok(resp);
 }
 
-fn present_value(
-    installment_amount: f64,
-    installments: &InstallmentData,
-    interest_rate: f64,
-) -> f64 {
-    let mut present_value = 0.0;
+// The interest-only grace invoices come first and are not bound by the minimum installment amount
+fn smallest_installment_amount(resp: &DecimalResponse) -> f64 {
+    let grace_periods = resp.invoices.len() - resp.installment as usize;
+    let smallest = resp
+        .invoices
+        .iter()
+        .skip(grace_periods)
+        .map(|invoice| invoice.amount)
+        .fold(resp.installment_amount, Decimal::min);
+    to_f64(smallest)
+}
+
+fn present_value(installments: &InstallmentData, interest_rate: f64) -> Decimal {
+    let mut present_value = Decimal::ZERO;
     let annual_interest_rate = (1.0 + interest_rate).powf(12.0);
-    for days in &installments.accumulated_business_days {
+    for (days, installment_amount) in installments
+        .accumulated_business_days
+        .iter()
+        .zip(&installments.amounts)
+    {
         let days = *days as f64;
 
         let days_diff = days / 252.0;
         let potency = annual_interest_rate.powf(days_diff);
-        let installment_value = installment_amount / potency;
+        let installment_value = installment_amount / to_decimal(potency);
         present_value += installment_value;
     }
     return present_value;
@@ -244,7 +512,11 @@ mod test {
     use chrono::Datelike;
 
     use super::*;
-    use crate::{Invoice, Params};
+    use crate::{
+        calendar::HolidayList, AmortizationSystem, BusinessDayConvention, DueDayPolicy,
+        ExcludedInstallment, ExclusionReason, GracePeriod, Invoice, IofConvergence, Params,
+        Periodicity, ResidualAllocation, RoundingMode, RoundingPolicy,
+    };
 
     fn print_expected(resp: &Response) {
         // Print the actual response values for copying
@@ -291,6 +563,8 @@ mod test {
         println!("    tec_yearly: {},", resp.tec_yearly);
         println!("    eir_monthly: {},", resp.eir_monthly);
         println!("    tec_monthly: {},", resp.tec_monthly);
+        println!("    eir_periodic: {},", resp.eir_periodic);
+        println!("    tec_periodic: {},", resp.tec_periodic);
         println!("    total_iof: {},", resp.total_iof);
         println!("    contract_amount: {},", resp.contract_amount);
         println!(
@@ -306,6 +580,13 @@ mod test {
         );
         println!("    paid_total_iof: {},", resp.paid_total_iof);
         println!("    paid_contract_amount: {},", resp.paid_contract_amount);
+        println!(
+            "    due_day_policy: DueDayPolicy::{:?},",
+            resp.due_day_policy
+        );
+        println!("    rounding_residual: {:?},", resp.rounding_residual);
+        println!("    iof_iterations: {},", resp.iof_iterations);
+        println!("    iof_residual: {:?},", resp.iof_residual);
         println!("    invoices: vec![");
         for invoice in &resp.invoices {
             println!("        Invoice {{");
@@ -320,12 +601,19 @@ mod test {
             );
             println!("            main_iof_tac: {},", invoice.main_iof_tac);
             println!("            debit_service: {},", invoice.debit_service);
+            println!("            amount: {},", invoice.amount);
             println!(
                 "            due_date: chrono::NaiveDate::from_ymd_opt({}, {}, {}).unwrap(),",
                 invoice.due_date.year(),
                 invoice.due_date.month(),
                 invoice.due_date.day()
             );
+            println!("            opening_balance: {},", invoice.opening_balance);
+            println!("            interest: {},", invoice.interest);
+            println!("            principal: {},", invoice.principal);
+            println!("            iof: {},", invoice.iof);
+            println!("            tac: {},", invoice.tac);
+            println!("            closing_balance: {},", invoice.closing_balance);
             println!("        }},");
         }
         println!("    ],");
@@ -352,6 +640,7 @@ mod test {
             iof_overall: 0.0038,      // %0.38
             iof_percentage: 0.000082, // 0.0082%
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let iterative = Iterative;
@@ -387,6 +676,8 @@ mod test {
             tec_yearly: 0.544357,
             eir_monthly: 0.035,
             tec_monthly: 0.0369,
+            eir_periodic: 0.035,
+            tec_periodic: 0.0369,
             total_iof: 428.55,
             contract_amount: 13281.98,
             contract_amount_without_tac: 0.0,
@@ -396,102 +687,190 @@ mod test {
             pre_disbursement_amount: 12853.53,
             paid_total_iof: 428.65,
             paid_contract_amount: 13282.08,
+            due_day_policy: DueDayPolicy::Stepped,
+            rounding_residual: 0.0,
+            iof_iterations: 4,
+            iof_residual: 0.01,
             invoices: vec![
                 Invoice {
                     accumulated_days: 33,
                     factor: 0.96302322215506,
                     accumulated_factor: 0.96302322215506,
-                    main_iof_tac: 110.63069999999999,
+                    main_iof_tac: 110.6307,
                     debit_service: 464.8693,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 11, 25).unwrap(),
+                    opening_balance: 13281.98,
+                    interest: 509.98,
+                    principal: 65.52,
+                    iof: 0.43,
+                    tac: 0.0,
+                    closing_balance: 13216.46,
                 },
                 Invoice {
                     accumulated_days: 61,
                     factor: 0.931982709374806,
                     accumulated_factor: 1.895005931529866,
-                    main_iof_tac: 114.50277449999999,
+                    main_iof_tac: 114.5027745,
                     debit_service: 460.9972255,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 12, 23).unwrap(),
+                    opening_balance: 13216.46,
+                    interest: 440.19,
+                    principal: 135.31,
+                    iof: 1.19,
+                    tac: 0.0,
+                    closing_balance: 13081.15,
                 },
                 Invoice {
                     accumulated_days: 92,
                     factor: 0.897520991774929,
                     accumulated_factor: 2.792526923304795,
-                    main_iof_tac: 118.51037160749996,
-                    debit_service: 456.98962839250004,
+                    main_iof_tac: 118.5103716075,
+                    debit_service: 456.9896283925,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 1, 23).unwrap(),
+                    opening_balance: 13081.15,
+                    interest: 502.27,
+                    principal: 73.23,
+                    iof: 0.83,
+                    tac: 0.0,
+                    closing_balance: 13007.92,
                 },
                 Invoice {
                     accumulated_days: 124,
                     factor: 0.865750637245039,
                     accumulated_factor: 3.658277560549834,
-                    main_iof_tac: 122.65823461376243,
-                    debit_service: 452.84176538623757,
+                    main_iof_tac: 122.6582346137625,
+                    debit_service: 452.8417653862375,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 2, 24).unwrap(),
+                    opening_balance: 13007.92,
+                    interest: 477.35,
+                    principal: 98.15,
+                    iof: 1.37,
+                    tac: 0.0,
+                    closing_balance: 12909.77,
                 },
                 Invoice {
                     accumulated_days: 152,
                     factor: 0.840595006492485,
                     accumulated_factor: 4.498872567042319,
-                    main_iof_tac: 126.95127282524413,
-                    debit_service: 448.54872717475587,
+                    main_iof_tac: 126.95127282524417,
+                    debit_service: 448.54872717475575,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 3, 24).unwrap(),
+                    opening_balance: 12909.77,
+                    interest: 386.34,
+                    principal: 189.16,
+                    iof: 3.08,
+                    tac: 0.0,
+                    closing_balance: 12720.61,
                 },
                 Invoice {
                     accumulated_days: 182,
                     factor: 0.813500644236445,
                     accumulated_factor: 5.312373211278764,
-                    main_iof_tac: 131.3945673741277,
-                    debit_service: 444.1054326258723,
+                    main_iof_tac: 131.39456737412775,
+                    debit_service: 444.10543262587225,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 4, 23).unwrap(),
+                    opening_balance: 12720.61,
+                    interest: 423.67,
+                    principal: 151.83,
+                    iof: 2.85,
+                    tac: 0.0,
+                    closing_balance: 12568.78,
                 },
                 Invoice {
                     accumulated_days: 212,
                     factor: 0.78599096060344,
                     accumulated_factor: 6.098364171882205,
-                    main_iof_tac: 135.99337723222214,
-                    debit_service: 439.50662276777786,
+                    main_iof_tac: 135.9933772322222,
+                    debit_service: 439.50662276777774,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 5, 23).unwrap(),
+                    opening_balance: 12568.78,
+                    interest: 439.91,
+                    principal: 135.59,
+                    iof: 2.88,
+                    tac: 0.0,
+                    closing_balance: 12433.19,
                 },
                 Invoice {
                     accumulated_days: 243,
                     factor: 0.760656615702412,
                     accumulated_factor: 6.859020787584616,
-                    main_iof_tac: 140.7531454353499,
-                    debit_service: 434.7468545646501,
+                    main_iof_tac: 140.75314543534998,
+                    debit_service: 434.74685456465,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 6, 23).unwrap(),
+                    opening_balance: 12433.19,
+                    interest: 414.1,
+                    principal: 161.4,
+                    iof: 3.83,
+                    tac: 0.0,
+                    closing_balance: 12271.79,
                 },
                 Invoice {
                     accumulated_days: 273,
                     factor: 0.733730972093141,
                     accumulated_factor: 7.592751759677757,
-                    main_iof_tac: 145.67950552558716,
-                    debit_service: 429.82049447441284,
+                    main_iof_tac: 145.67950552558722,
+                    debit_service: 429.8204944744128,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 7, 23).unwrap(),
+                    opening_balance: 12271.79,
+                    interest: 450.34,
+                    principal: 125.16,
+                    iof: 3.28,
+                    tac: 0.0,
+                    closing_balance: 12146.63,
                 },
                 Invoice {
                     accumulated_days: 306,
                     factor: 0.706599964940101,
                     accumulated_factor: 8.299351724617859,
-                    main_iof_tac: 150.77828821898277,
-                    debit_service: 424.72171178101723,
+                    main_iof_tac: 150.7782882189828,
+                    debit_service: 424.7217117810172,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 25).unwrap(),
+                    opening_balance: 12146.63,
+                    interest: 466.39,
+                    principal: 109.11,
+                    iof: 3.15,
+                    tac: 0.0,
+                    closing_balance: 12037.52,
                 },
                 Invoice {
                     accumulated_days: 335,
                     factor: 0.68270528012539,
                     accumulated_factor: 8.982057004743249,
-                    main_iof_tac: 156.05552830664715,
-                    debit_service: 419.44447169335285,
+                    main_iof_tac: 156.05552830664718,
+                    debit_service: 419.4444716933528,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 23).unwrap(),
+                    opening_balance: 12037.52,
+                    interest: 421.31,
+                    principal: 154.19,
+                    iof: 4.83,
+                    tac: 0.0,
+                    closing_balance: 11883.33,
                 },
                 Invoice {
                     accumulated_days: 365,
                     factor: 0.658538949769018,
                     accumulated_factor: 9.640595954512266,
-                    main_iof_tac: 161.5174717973798,
-                    debit_service: 413.9825282026202,
+                    main_iof_tac: 161.51747179737984,
+                    debit_service: 413.98252820262013,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 10, 23).unwrap(),
+                    opening_balance: 11883.33,
+                    interest: 436.08,
+                    principal: 139.42,
+                    iof: 4.7,
+                    tac: 0.0,
+                    closing_balance: 11743.91,
                 },
                 Invoice {
                     accumulated_days: 397,
@@ -499,15 +878,29 @@ mod test {
                     accumulated_factor: 10.276865471188012,
                     main_iof_tac: 167.17058331028812,
                     debit_service: 408.3294166897119,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 11, 24).unwrap(),
+                    opening_balance: 11743.91,
+                    interest: 411.04,
+                    principal: 164.46,
+                    iof: 5.54,
+                    tac: 0.0,
+                    closing_balance: 11579.45,
                 },
                 Invoice {
                     accumulated_days: 426,
                     factor: 0.61475315619947,
                     accumulated_factor: 10.891618627387482,
-                    main_iof_tac: 173.02155372614823,
-                    debit_service: 402.4784462738518,
+                    main_iof_tac: 173.0215537261482,
+                    debit_service: 402.4784462738517,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 12, 23).unwrap(),
+                    opening_balance: 11579.45,
+                    interest: 405.28,
+                    principal: 170.22,
+                    iof: 5.74,
+                    tac: 0.0,
+                    closing_balance: 11409.23,
                 },
                 Invoice {
                     accumulated_days: 457,
@@ -515,15 +908,29 @@ mod test {
                     accumulated_factor: 11.485583029503896,
                     main_iof_tac: 179.0773081065634,
                     debit_service: 396.4226918934366,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 1, 23).unwrap(),
+                    opening_balance: 11409.23,
+                    interest: 399.32,
+                    principal: 176.18,
+                    iof: 5.94,
+                    tac: 0.0,
+                    closing_balance: 11233.05,
                 },
                 Invoice {
                     accumulated_days: 488,
                     factor: 0.575761946586711,
                     accumulated_factor: 12.061344976090608,
-                    main_iof_tac: 185.3450138902931,
+                    main_iof_tac: 185.34501389029313,
                     debit_service: 390.1549861097069,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 2, 23).unwrap(),
+                    opening_balance: 11233.05,
+                    interest: 355.13,
+                    principal: 220.37,
+                    iof: 7.44,
+                    tac: 0.0,
+                    closing_balance: 11012.68,
                 },
                 Invoice {
                     accumulated_days: 516,
@@ -531,23 +938,44 @@ mod test {
                     accumulated_factor: 12.618548755386799,
                     main_iof_tac: 191.83208937645338,
                     debit_service: 383.6679106235466,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 3, 23).unwrap(),
+                    opening_balance: 11012.68,
+                    interest: 366.79,
+                    principal: 208.71,
+                    iof: 7.04,
+                    tac: 0.0,
+                    closing_balance: 10803.97,
                 },
                 Invoice {
                     accumulated_days: 547,
                     factor: 0.538361139408745,
                     accumulated_factor: 13.156909894795543,
-                    main_iof_tac: 198.5462125046293,
-                    debit_service: 376.9537874953707,
+                    main_iof_tac: 198.5462125046292,
+                    debit_service: 376.95378749537076,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 23).unwrap(),
+                    opening_balance: 10803.97,
+                    interest: 378.14,
+                    principal: 197.36,
+                    iof: 6.66,
+                    tac: 0.0,
+                    closing_balance: 10606.61,
                 },
                 Invoice {
                     accumulated_days: 579,
                     factor: 0.520155690242396,
                     accumulated_factor: 13.677065585037939,
-                    main_iof_tac: 205.49532994229128,
+                    main_iof_tac: 205.4953299422913,
                     debit_service: 370.0046700577087,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 25).unwrap(),
+                    opening_balance: 10606.61,
+                    interest: 371.23,
+                    principal: 204.27,
+                    iof: 6.89,
+                    tac: 0.0,
+                    closing_balance: 10402.34,
                 },
                 Invoice {
                     accumulated_days: 608,
@@ -555,23 +983,44 @@ mod test {
                     accumulated_factor: 14.180455428954678,
                     main_iof_tac: 212.68766649027145,
                     debit_service: 362.81233350972855,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 6, 23).unwrap(),
+                    opening_balance: 10402.34,
+                    interest: 346.46,
+                    principal: 229.04,
+                    iof: 7.73,
+                    tac: 0.0,
+                    closing_balance: 10173.3,
                 },
                 Invoice {
                     accumulated_days: 638,
                     factor: 0.485570902683561,
                     accumulated_factor: 14.666026331638239,
-                    main_iof_tac: 220.13173481743098,
+                    main_iof_tac: 220.13173481743092,
                     debit_service: 355.368265182569,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 7, 23).unwrap(),
+                    opening_balance: 10173.3,
+                    interest: 373.33,
+                    principal: 202.17,
+                    iof: 6.82,
+                    tac: 0.0,
+                    closing_balance: 9971.13,
                 },
                 Invoice {
                     accumulated_days: 670,
                     factor: 0.468382714475119,
                     accumulated_factor: 15.134409046113358,
-                    main_iof_tac: 227.83634553604105,
+                    main_iof_tac: 227.83634553604102,
                     debit_service: 347.66365446395895,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 8, 24).unwrap(),
+                    opening_balance: 9971.13,
+                    interest: 365.91,
+                    principal: 209.59,
+                    iof: 7.07,
+                    tac: 0.0,
+                    closing_balance: 9761.54,
                 },
                 Invoice {
                     accumulated_days: 700,
@@ -579,207 +1028,389 @@ mod test {
                     accumulated_factor: 15.586952731589955,
                     main_iof_tac: 235.8106176298025,
                     debit_service: 339.6893823701975,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 9, 23).unwrap(),
+                    opening_balance: 9761.54,
+                    interest: 341.65,
+                    principal: 233.85,
+                    iof: 7.89,
+                    tac: 0.0,
+                    closing_balance: 9527.69,
                 },
                 Invoice {
                     accumulated_days: 730,
                     factor: 0.43724027581641,
                     accumulated_factor: 16.024193007406364,
-                    main_iof_tac: 244.0639892468456,
-                    debit_service: 331.4360107531544,
+                    main_iof_tac: 244.06398924684558,
+                    debit_service: 331.43601075315445,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 10, 23).unwrap(),
+                    opening_balance: 9527.69,
+                    interest: 333.47,
+                    principal: 242.03,
+                    iof: 8.16,
+                    tac: 0.0,
+                    closing_balance: 9285.66,
                 },
                 Invoice {
                     accumulated_days: 761,
                     factor: 0.423840741016035,
                     accumulated_factor: 16.4480337484224,
-                    main_iof_tac: 252.6062288704852,
-                    debit_service: 322.8937711295148,
+                    main_iof_tac: 252.60622887048515,
+                    debit_service: 322.89377112951485,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 11, 23).unwrap(),
+                    opening_balance: 9285.66,
+                    interest: 293.56,
+                    principal: 281.94,
+                    iof: 9.51,
+                    tac: 0.0,
+                    closing_balance: 9003.72,
                 },
                 Invoice {
                     accumulated_days: 791,
                     factor: 0.408837670636966,
                     accumulated_factor: 16.856871419059367,
-                    main_iof_tac: 261.4474468809522,
-                    debit_service: 314.0525531190478,
+                    main_iof_tac: 261.44744688095216,
+                    debit_service: 314.05255311904784,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 12, 23).unwrap(),
+                    opening_balance: 9003.72,
+                    interest: 330.41,
+                    principal: 245.09,
+                    iof: 8.27,
+                    tac: 0.0,
+                    closing_balance: 8758.63,
                 },
                 Invoice {
                     accumulated_days: 824,
                     factor: 0.395012242155549,
                     accumulated_factor: 17.251883661214915,
-                    main_iof_tac: 270.59810752178555,
-                    debit_service: 304.90189247821445,
+                    main_iof_tac: 270.5981075217855,
+                    debit_service: 304.9018924782145,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 1, 25).unwrap(),
+                    opening_balance: 8758.63,
+                    interest: 306.55,
+                    principal: 268.95,
+                    iof: 9.07,
+                    tac: 0.0,
+                    closing_balance: 8489.68,
                 },
                 Invoice {
                     accumulated_days: 853,
                     factor: 0.382906815052672,
                     accumulated_factor: 17.634790476267586,
-                    main_iof_tac: 280.06904128504806,
-                    debit_service: 295.43095871495194,
+                    main_iof_tac: 280.06904128504794,
+                    debit_service: 295.43095871495206,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 2, 23).unwrap(),
+                    opening_balance: 8489.68,
+                    interest: 268.4,
+                    principal: 307.1,
+                    iof: 10.36,
+                    tac: 0.0,
+                    closing_balance: 8182.58,
                 },
                 Invoice {
                     accumulated_days: 881,
                     factor: 0.370564824109098,
                     accumulated_factor: 18.005355300376685,
-                    main_iof_tac: 289.87145773002476,
-                    debit_service: 285.62854226997524,
+                    main_iof_tac: 289.87145773002464,
+                    debit_service: 285.62854226997536,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 3, 23).unwrap(),
+                    opening_balance: 8182.58,
+                    interest: 272.53,
+                    principal: 302.97,
+                    iof: 10.22,
+                    tac: 0.0,
+                    closing_balance: 7879.61,
                 },
                 Invoice {
                     accumulated_days: 912,
                     factor: 0.35803364647699,
                     accumulated_factor: 18.363388946853675,
-                    main_iof_tac: 300.0169587505756,
-                    debit_service: 275.4830412494244,
+                    main_iof_tac: 300.0169587505755,
+                    debit_service: 275.4830412494245,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 4, 23).unwrap(),
+                    opening_balance: 7879.61,
+                    interest: 275.79,
+                    principal: 299.71,
+                    iof: 10.11,
+                    tac: 0.0,
+                    closing_balance: 7579.9,
                 },
                 Invoice {
                     accumulated_days: 943,
                     factor: 0.345926228475129,
                     accumulated_factor: 18.709315175328804,
-                    main_iof_tac: 310.51755230684574,
-                    debit_service: 264.98244769315426,
+                    main_iof_tac: 310.51755230684563,
+                    debit_service: 264.98244769315437,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 5, 24).unwrap(),
+                    opening_balance: 7579.9,
+                    interest: 265.3,
+                    principal: 310.2,
+                    iof: 10.46,
+                    tac: 0.0,
+                    closing_balance: 7269.7,
                 },
                 Invoice {
                     accumulated_days: 973,
                     factor: 0.334228240067706,
                     accumulated_factor: 19.04354341539651,
-                    main_iof_tac: 321.38566663758536,
-                    debit_service: 254.11433336241467,
+                    main_iof_tac: 321.38566663758525,
+                    debit_service: 254.11433336241475,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 6, 23).unwrap(),
+                    opening_balance: 7269.7,
+                    interest: 254.44,
+                    principal: 321.06,
+                    iof: 10.83,
+                    tac: 0.0,
+                    closing_balance: 6948.64,
                 },
                 Invoice {
                     accumulated_days: 1003,
                     factor: 0.322397263658059,
                     accumulated_factor: 19.365940679054567,
-                    main_iof_tac: 332.6341649699008,
-                    debit_service: 242.8658350300992,
+                    main_iof_tac: 332.6341649699007,
+                    debit_service: 242.86583503009925,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 7, 23).unwrap(),
+                    opening_balance: 6948.64,
+                    interest: 254.99,
+                    principal: 320.51,
+                    iof: 10.81,
+                    tac: 0.0,
+                    closing_balance: 6628.13,
                 },
                 Invoice {
                     accumulated_days: 1034,
                     factor: 0.311494940727874,
                     accumulated_factor: 19.677435619782443,
-                    main_iof_tac: 344.27636074384736,
-                    debit_service: 231.22363925615267,
+                    main_iof_tac: 344.27636074384725,
+                    debit_service: 231.22363925615275,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 8, 23).unwrap(),
+                    opening_balance: 6628.13,
+                    interest: 231.98,
+                    principal: 343.52,
+                    iof: 11.59,
+                    tac: 0.0,
+                    closing_balance: 6284.61,
                 },
                 Invoice {
                     accumulated_days: 1065,
                     factor: 0.300468675279062,
                     accumulated_factor: 19.977904295061506,
-                    main_iof_tac: 356.326033369882,
-                    debit_service: 219.173966630118,
+                    main_iof_tac: 356.3260333698819,
+                    debit_service: 219.17396663011812,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 9, 23).unwrap(),
+                    opening_balance: 6284.61,
+                    interest: 230.63,
+                    principal: 344.87,
+                    iof: 11.63,
+                    tac: 0.0,
+                    closing_balance: 5939.74,
                 },
                 Invoice {
                     accumulated_days: 1097,
                     factor: 0.290307898816108,
                     accumulated_factor: 20.268212193877613,
-                    main_iof_tac: 368.79744453782786,
-                    debit_service: 206.70255546217214,
+                    main_iof_tac: 368.7974445378278,
+                    debit_service: 206.7025554621722,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 10, 25).unwrap(),
+                    opening_balance: 5939.74,
+                    interest: 207.89,
+                    principal: 367.61,
+                    iof: 12.4,
+                    tac: 0.0,
+                    closing_balance: 5572.13,
                 },
                 Invoice {
                     accumulated_days: 1126,
                     factor: 0.281411209722801,
                     accumulated_factor: 20.549623403600414,
-                    main_iof_tac: 381.70535509665183,
-                    debit_service: 193.79464490334817,
+                    main_iof_tac: 381.7053550966518,
+                    debit_service: 193.79464490334826,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 11, 23).unwrap(),
+                    opening_balance: 5572.13,
+                    interest: 176.16,
+                    principal: 399.34,
+                    iof: 13.47,
+                    tac: 0.0,
+                    closing_balance: 5172.79,
                 },
                 Invoice {
                     accumulated_days: 1156,
                     factor: 0.27144984504887,
                     accumulated_factor: 20.821073248649284,
-                    main_iof_tac: 395.06504252503464,
-                    debit_service: 180.43495747496536,
+                    main_iof_tac: 395.0650425250346,
+                    debit_service: 180.43495747496544,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 12, 23).unwrap(),
+                    opening_balance: 5172.79,
+                    interest: 189.83,
+                    principal: 385.67,
+                    iof: 13.01,
+                    tac: 0.0,
+                    closing_balance: 4787.12,
                 },
                 Invoice {
                     accumulated_days: 1188,
                     factor: 0.261841091723523,
                     accumulated_factor: 21.082914340372806,
-                    main_iof_tac: 408.8923190134109,
-                    debit_service: 166.60768098658912,
+                    main_iof_tac: 408.8923190134108,
+                    debit_service: 166.60768098658923,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 1, 24).unwrap(),
+                    opening_balance: 4787.12,
+                    interest: 175.67,
+                    principal: 399.83,
+                    iof: 13.49,
+                    tac: 0.0,
+                    closing_balance: 4387.29,
                 },
                 Invoice {
                     accumulated_days: 1218,
                     factor: 0.252572468046991,
                     accumulated_factor: 21.335486808419798,
-                    main_iof_tac: 423.2035501788803,
-                    debit_service: 152.29644982111975,
+                    main_iof_tac: 423.20355017888016,
+                    debit_service: 152.29644982111986,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 2, 23).unwrap(),
+                    opening_balance: 4387.29,
+                    interest: 161.0,
+                    principal: 414.5,
+                    iof: 13.99,
+                    tac: 0.0,
+                    closing_balance: 3972.79,
                 },
                 Invoice {
                     accumulated_days: 1247,
                     factor: 0.244832207685813,
                     accumulated_factor: 21.580319016105612,
-                    main_iof_tac: 438.0156744351411,
-                    debit_service: 137.48432556485892,
+                    main_iof_tac: 438.01567443514097,
+                    debit_service: 137.48432556485903,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 3, 23).unwrap(),
+                    opening_balance: 3972.79,
+                    interest: 125.6,
+                    principal: 449.9,
+                    iof: 15.18,
+                    tac: 0.0,
+                    closing_balance: 3522.89,
                 },
                 Invoice {
                     accumulated_days: 1279,
                     factor: 0.236940687422488,
                     accumulated_factor: 21.8172597035281,
-                    main_iof_tac: 453.346223040371,
-                    debit_service: 122.15377695962898,
+                    main_iof_tac: 453.34622304037083,
+                    debit_service: 122.15377695962911,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 4, 24).unwrap(),
+                    opening_balance: 3522.89,
+                    interest: 117.33,
+                    principal: 458.17,
+                    iof: 15.45,
+                    tac: 0.0,
+                    closing_balance: 3064.72,
                 },
                 Invoice {
                     accumulated_days: 1308,
                     factor: 0.229303529494311,
                     accumulated_factor: 22.04656323302241,
-                    main_iof_tac: 469.21334084678404,
-                    debit_service: 106.286659153216,
+                    main_iof_tac: 469.21334084678386,
+                    debit_service: 106.28665915321612,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 5, 23).unwrap(),
+                    opening_balance: 3064.72,
+                    interest: 102.07,
+                    principal: 473.43,
+                    iof: 15.97,
+                    tac: 0.0,
+                    closing_balance: 2591.29,
                 },
                 Invoice {
                     accumulated_days: 1339,
                     factor: 0.221186667054601,
                     accumulated_factor: 22.26774990007701,
-                    main_iof_tac: 485.6358077764214,
-                    debit_service: 89.86419222357856,
+                    main_iof_tac: 485.6358077764213,
+                    debit_service: 89.8641922235787,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 6, 23).unwrap(),
+                    opening_balance: 2591.29,
+                    interest: 95.09,
+                    principal: 480.41,
+                    iof: 16.21,
+                    tac: 0.0,
+                    closing_balance: 2110.88,
                 },
                 Invoice {
                     accumulated_days: 1370,
                     factor: 0.213706924687313,
                     accumulated_factor: 22.481456824764322,
-                    main_iof_tac: 502.63306104859623,
-                    debit_service: 72.8669389514038,
+                    main_iof_tac: 502.6330610485961,
+                    debit_service: 72.86693895140395,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 7, 24).unwrap(),
+                    opening_balance: 2110.88,
+                    interest: 73.88,
+                    principal: 501.62,
+                    iof: 16.92,
+                    tac: 0.0,
+                    closing_balance: 1609.26,
                 },
                 Invoice {
                     accumulated_days: 1400,
                     factor: 0.20614214923913,
                     accumulated_factor: 22.687598974003453,
-                    main_iof_tac: 520.2252181852971,
-                    debit_service: 55.274781814702926,
+                    main_iof_tac: 520.2252181852969,
+                    debit_service: 55.27478181470309,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 8, 23).unwrap(),
+                    opening_balance: 1609.26,
+                    interest: 59.05,
+                    principal: 516.45,
+                    iof: 17.42,
+                    tac: 0.0,
+                    closing_balance: 1092.81,
                 },
                 Invoice {
                     accumulated_days: 1433,
                     factor: 0.198845150923883,
                     accumulated_factor: 22.886444124927337,
-                    main_iof_tac: 538.4331008217824,
-                    debit_service: 37.066899178217525,
+                    main_iof_tac: 538.4331008217823,
+                    debit_service: 37.0668991782177,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 9, 25).unwrap(),
+                    opening_balance: 1092.81,
+                    interest: 40.1,
+                    principal: 535.4,
+                    iof: 18.05,
+                    tac: 0.0,
+                    closing_balance: 557.41,
                 },
                 Invoice {
                     accumulated_days: 1461,
                     factor: 0.19275140186402,
                     accumulated_factor: 23.079195526791356,
-                    main_iof_tac: 557.2782593505449,
-                    debit_service: 18.22174064945514,
+                    main_iof_tac: 557.2782593505447,
+                    debit_service: 18.22174064945532,
+                    amount: 575.5,
                     due_date: chrono::NaiveDate::from_ymd_opt(2028, 10, 23).unwrap(),
+                    opening_balance: 557.41,
+                    interest: 18.09,
+                    principal: 557.41,
+                    iof: 18.79,
+                    tac: 0.0,
+                    closing_balance: 0.0,
                 },
             ],
         };
@@ -807,6 +1438,7 @@ mod test {
             iof_overall: 0.0038,      // %0.38
             iof_percentage: 0.000082, // 0.0082%
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let iterative = Iterative;
@@ -836,6 +1468,7 @@ mod test {
             iof_overall: 0.0038,      // %0.38
             iof_percentage: 0.000082, // 0.0082%
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let iterative = Iterative;
@@ -868,6 +1501,7 @@ mod test {
             iof_overall: 0.0038,      // %0.38
             iof_percentage: 0.000082, // 0.0082%
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let iterative = Iterative;
@@ -896,17 +1530,19 @@ mod test {
             customer_amount: 106.59,
             calculation_basis_for_effective_interest_rate: 105.815,
             merchant_debit_service_amount: 0.0,
-            merchant_total_amount: 10.021500000000001,
+            merchant_total_amount: 10.0215,
             settled_to_merchant: 190.4085,
-            mdr_amount: 10.021500000000001,
+            mdr_amount: 10.0215,
             effective_interest_rate: 0.0356,
             total_effective_cost: 0.0408,
             eir_yearly: 0.521921,
             tec_yearly: 0.616492,
             eir_monthly: 0.0356,
             tec_monthly: 0.0408,
+            eir_periodic: 0.0356,
+            tec_periodic: 0.0408,
             total_iof: 1.55,
-            contract_amount: 201.98000000000002,
+            contract_amount: 201.98,
             contract_amount_without_tac: 0.0,
             tac_amount: 0.0,
             iof_percentage: 8.2e-5,
@@ -914,22 +1550,40 @@ mod test {
             pre_disbursement_amount: 200.44,
             paid_total_iof: 1.56,
             paid_contract_amount: 201.99,
+            due_day_policy: DueDayPolicy::Stepped,
+            rounding_residual: 0.0,
+            iof_iterations: 2,
+            iof_residual: 0.01,
             invoices: vec![
                 Invoice {
                     accumulated_days: 33,
                     factor: 0.96302322215506,
                     accumulated_factor: 0.96302322215506,
                     main_iof_tac: 99.5207,
-                    debit_service: 7.069300000000001,
+                    debit_service: 7.0693,
+                    amount: 106.59,
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 11, 25).unwrap(),
+                    opening_balance: 201.98,
+                    interest: 7.76,
+                    principal: 98.83,
+                    iof: 0.65,
+                    tac: 0.0,
+                    closing_balance: 103.15,
                 },
                 Invoice {
                     accumulated_days: 61,
                     factor: 0.931982709374806,
                     accumulated_factor: 1.895005931529866,
                     main_iof_tac: 103.0039245,
-                    debit_service: 3.5860755000000006,
+                    debit_service: 3.5860755,
+                    amount: 106.59,
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 12, 23).unwrap(),
+                    opening_balance: 103.15,
+                    interest: 3.44,
+                    principal: 103.15,
+                    iof: 0.9,
+                    tac: 0.0,
+                    closing_balance: 0.0,
                 },
             ],
         };
@@ -957,6 +1611,7 @@ mod test {
             iof_overall: 0.0038,      // %0.38
             iof_percentage: 0.000082, // 0.0082%
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let iterative = Iterative;
@@ -980,8 +1635,8 @@ mod test {
             installment_amount: 296.26,
             installment_amount_without_tac: 0.0,
             total_amount: 2370.08,
-            debit_service: 338.04999999999984,
-            customer_debit_service_amount: 338.04999999999984,
+            debit_service: 338.05,
+            customer_debit_service_amount: 338.05,
             customer_amount: 296.26,
             calculation_basis_for_effective_interest_rate: 292.31,
             merchant_debit_service_amount: 0.0,
@@ -994,6 +1649,8 @@ mod test {
             tec_yearly: 0.58491,
             eir_monthly: 0.0354,
             tec_monthly: 0.0391,
+            eir_periodic: 0.0354,
+            tec_periodic: 0.0391,
             total_iof: 31.6,
             contract_amount: 2032.03,
             contract_amount_without_tac: 0.0,
@@ -1003,22 +1660,40 @@ mod test {
             pre_disbursement_amount: 2000.45,
             paid_total_iof: 31.62,
             paid_contract_amount: 2032.05,
+            due_day_policy: DueDayPolicy::Stepped,
+            rounding_residual: 0.0,
+            iof_iterations: 3,
+            iof_residual: 0.0,
             invoices: vec![
                 Invoice {
                     accumulated_days: 33,
                     factor: 0.96302322215506,
                     accumulated_factor: 0.96302322215506,
-                    main_iof_tac: 225.13894999999997,
-                    debit_service: 71.12105000000001,
+                    main_iof_tac: 225.13895,
+                    debit_service: 71.12105,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 11, 25).unwrap(),
+                    opening_balance: 2032.03,
+                    interest: 78.02,
+                    principal: 218.24,
+                    iof: 1.42,
+                    tac: 0.0,
+                    closing_balance: 1813.79,
                 },
                 Invoice {
                     accumulated_days: 61,
                     factor: 0.931982709374806,
                     accumulated_factor: 1.895005931529866,
                     main_iof_tac: 233.01881325,
-                    debit_service: 63.241186750000004,
+                    debit_service: 63.24118675,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2024, 12, 23).unwrap(),
+                    opening_balance: 1813.79,
+                    interest: 60.41,
+                    principal: 235.85,
+                    iof: 2.08,
+                    tac: 0.0,
+                    closing_balance: 1577.94,
                 },
                 Invoice {
                     accumulated_days: 92,
@@ -1026,15 +1701,29 @@ mod test {
                     accumulated_factor: 2.792526923304795,
                     main_iof_tac: 241.17447171375,
                     debit_service: 55.08552828625,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 1, 23).unwrap(),
+                    opening_balance: 1577.94,
+                    interest: 60.59,
+                    principal: 235.67,
+                    iof: 2.68,
+                    tac: 0.0,
+                    closing_balance: 1342.27,
                 },
                 Invoice {
                     accumulated_days: 124,
                     factor: 0.865750637245039,
                     accumulated_factor: 3.658277560549834,
-                    main_iof_tac: 249.61557822373123,
-                    debit_service: 46.64442177626876,
+                    main_iof_tac: 249.6155782237313,
+                    debit_service: 46.64442177626875,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 2, 24).unwrap(),
+                    opening_balance: 1342.27,
+                    interest: 49.26,
+                    principal: 247.0,
+                    iof: 3.45,
+                    tac: 0.0,
+                    closing_balance: 1095.27,
                 },
                 Invoice {
                     accumulated_days: 152,
@@ -1042,7 +1731,14 @@ mod test {
                     accumulated_factor: 4.498872567042319,
                     main_iof_tac: 258.35212346156186,
                     debit_service: 37.90787653843816,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 3, 24).unwrap(),
+                    opening_balance: 1095.27,
+                    interest: 32.78,
+                    principal: 263.48,
+                    iof: 4.28,
+                    tac: 0.0,
+                    closing_balance: 831.79,
                 },
                 Invoice {
                     accumulated_days: 182,
@@ -1050,23 +1746,44 @@ mod test {
                     accumulated_factor: 5.312373211278764,
                     main_iof_tac: 267.3944477827165,
                     debit_service: 28.865552217283494,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 4, 23).unwrap(),
+                    opening_balance: 831.79,
+                    interest: 27.7,
+                    principal: 268.56,
+                    iof: 5.03,
+                    tac: 0.0,
+                    closing_balance: 563.23,
                 },
                 Invoice {
                     accumulated_days: 212,
                     factor: 0.78599096060344,
                     accumulated_factor: 6.098364171882205,
                     main_iof_tac: 276.7532534551116,
-                    debit_service: 19.50674654488842,
+                    debit_service: 19.506746544888415,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 5, 23).unwrap(),
+                    opening_balance: 563.23,
+                    interest: 19.71,
+                    principal: 276.55,
+                    iof: 5.86,
+                    tac: 0.0,
+                    closing_balance: 286.68,
                 },
                 Invoice {
                     accumulated_days: 243,
                     factor: 0.760656615702412,
                     accumulated_factor: 6.859020787584616,
                     main_iof_tac: 286.4396173260405,
-                    debit_service: 9.82038267395951,
+                    debit_service: 9.820382673959509,
+                    amount: 296.26,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 6, 23).unwrap(),
+                    opening_balance: 286.68,
+                    interest: 9.58,
+                    principal: 286.68,
+                    iof: 6.8,
+                    tac: 0.0,
+                    closing_balance: 0.0,
                 },
             ],
         };
@@ -1094,6 +1811,7 @@ mod test {
             iof_overall: 0.0038,      // %0.38
             iof_percentage: 0.000082, // 0.0082%
             interest_rate: 0.0449,
+            ..Default::default()
         };
 
         let iterative = Iterative;
@@ -1131,6 +1849,8 @@ mod test {
             tec_yearly: 0.74792,
             eir_monthly: 0.0446,
             tec_monthly: 0.0476,
+            eir_periodic: 0.0446,
+            tec_periodic: 0.0476,
             total_iof: 116.04,
             contract_amount: 3999.52,
             contract_amount_without_tac: 0.0,
@@ -1140,14 +1860,25 @@ mod test {
             pre_disbursement_amount: 3883.43,
             paid_total_iof: 115.99,
             paid_contract_amount: 3999.47,
+            due_day_policy: DueDayPolicy::Stepped,
+            rounding_residual: 0.0,
+            iof_iterations: 4,
+            iof_residual: 0.0,
             invoices: vec![
                 Invoice {
                     accumulated_days: 28,
                     factor: 0.959033087711361,
                     accumulated_factor: 0.959033087711361,
-                    main_iof_tac: 94.43155199999998,
+                    main_iof_tac: 94.431552,
                     debit_service: 179.578448,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+                    opening_balance: 3999.52,
+                    interest: 170.85,
+                    principal: 103.16,
+                    iof: 0.63,
+                    tac: 0.0,
+                    closing_balance: 3896.36,
                 },
                 Invoice {
                     accumulated_days: 60,
@@ -1155,23 +1886,44 @@ mod test {
                     accumulated_factor: 1.874938322606282,
                     main_iof_tac: 98.6715286848,
                     debit_service: 175.3384713152,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 10, 20).unwrap(),
+                    opening_balance: 3896.36,
+                    interest: 183.47,
+                    principal: 90.54,
+                    iof: 0.79,
+                    tac: 0.0,
+                    closing_balance: 3805.82,
                 },
                 Invoice {
                     accumulated_days: 89,
                     factor: 0.876548219430817,
                     accumulated_factor: 2.7514865420370986,
-                    main_iof_tac: 103.10188032274749,
-                    debit_service: 170.9081196772525,
+                    main_iof_tac: 103.10188032274752,
+                    debit_service: 170.90811967725247,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 11, 18).unwrap(),
+                    opening_balance: 3805.82,
+                    interest: 170.88,
+                    principal: 103.13,
+                    iof: 1.14,
+                    tac: 0.0,
+                    closing_balance: 3702.69,
                 },
                 Invoice {
                     accumulated_days: 119,
                     factor: 0.83888239930792,
                     accumulated_factor: 3.5903689413450186,
-                    main_iof_tac: 107.73115474923887,
+                    main_iof_tac: 107.73115474923888,
                     debit_service: 166.27884525076112,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap(),
+                    opening_balance: 3702.69,
+                    interest: 166.25,
+                    principal: 107.76,
+                    iof: 1.46,
+                    tac: 0.0,
+                    closing_balance: 3594.93,
                 },
                 Invoice {
                     accumulated_days: 151,
@@ -1179,7 +1931,14 @@ mod test {
                     accumulated_factor: 4.394884918980009,
                     main_iof_tac: 112.5682835974797,
                     debit_service: 161.44171640252029,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 1, 19).unwrap(),
+                    opening_balance: 3594.93,
+                    interest: 153.56,
+                    principal: 120.45,
+                    iof: 1.95,
+                    tac: 0.0,
+                    closing_balance: 3474.48,
                 },
                 Invoice {
                     accumulated_days: 181,
@@ -1187,15 +1946,29 @@ mod test {
                     accumulated_factor: 5.1664423611244175,
                     main_iof_tac: 117.62259953100653,
                     debit_service: 156.38740046899346,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 2, 18).unwrap(),
+                    opening_balance: 3474.48,
+                    interest: 148.42,
+                    principal: 125.59,
+                    iof: 2.34,
+                    tac: 0.0,
+                    closing_balance: 3348.89,
                 },
                 Invoice {
                     accumulated_days: 209,
                     factor: 0.739949116086432,
                     accumulated_factor: 5.90639147721085,
-                    main_iof_tac: 122.90385424994872,
+                    main_iof_tac: 122.90385424994875,
                     debit_service: 151.10614575005127,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 3, 18).unwrap(),
+                    opening_balance: 3348.89,
+                    interest: 143.05,
+                    principal: 130.96,
+                    iof: 2.74,
+                    tac: 0.0,
+                    closing_balance: 3217.93,
                 },
                 Invoice {
                     accumulated_days: 242,
@@ -1203,7 +1976,14 @@ mod test {
                     accumulated_factor: 6.613064977493049,
                     main_iof_tac: 128.42223730577143,
                     debit_service: 145.58776269422856,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 4, 20).unwrap(),
+                    opening_balance: 3217.93,
+                    interest: 151.52,
+                    principal: 122.49,
+                    iof: 2.9,
+                    tac: 0.0,
+                    closing_balance: 3095.44,
                 },
                 Invoice {
                     accumulated_days: 270,
@@ -1211,7 +1991,14 @@ mod test {
                     accumulated_factor: 7.293629080155911,
                     main_iof_tac: 134.18839576080057,
                     debit_service: 139.82160423919942,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 5, 18).unwrap(),
+                    opening_balance: 3095.44,
+                    interest: 118.75,
+                    principal: 155.26,
+                    iof: 4.03,
+                    tac: 0.0,
+                    closing_balance: 2940.18,
                 },
                 Invoice {
                     accumulated_days: 301,
@@ -1219,7 +2006,14 @@ mod test {
                     accumulated_factor: 7.943588119419094,
                     main_iof_tac: 140.2134547304605,
                     debit_service: 133.79654526953948,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 6, 18).unwrap(),
+                    opening_balance: 2940.18,
+                    interest: 138.45,
+                    principal: 135.56,
+                    iof: 3.87,
+                    tac: 0.0,
+                    closing_balance: 2804.62,
                 },
                 Invoice {
                     accumulated_days: 333,
@@ -1227,115 +2021,989 @@ mod test {
                     accumulated_factor: 8.564318409287456,
                     main_iof_tac: 146.5090388478582,
                     debit_service: 127.5009611521418,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 7, 20).unwrap(),
+                    opening_balance: 2804.62,
+                    interest: 132.06,
+                    principal: 141.95,
+                    iof: 4.42,
+                    tac: 0.0,
+                    closing_balance: 2662.67,
                 },
                 Invoice {
                     accumulated_days: 362,
                     factor: 0.594057124690754,
                     accumulated_factor: 9.158375533978209,
                     main_iof_tac: 153.08729469212705,
-                    debit_service: 120.92270530787296,
+                    debit_service: 120.92270530787295,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 8, 18).unwrap(),
+                    opening_balance: 2662.67,
+                    interest: 119.55,
+                    principal: 154.46,
+                    iof: 5.17,
+                    tac: 0.0,
+                    closing_balance: 2508.21,
                 },
                 Invoice {
                     accumulated_days: 393,
                     factor: 0.567342292255346,
                     accumulated_factor: 9.725717826233554,
                     main_iof_tac: 159.96091422380354,
-                    debit_service: 114.04908577619645,
+                    debit_service: 114.04908577619646,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 9, 18).unwrap(),
+                    opening_balance: 2508.21,
+                    interest: 118.11,
+                    principal: 155.9,
+                    iof: 5.26,
+                    tac: 0.0,
+                    closing_balance: 2352.31,
                 },
                 Invoice {
                     accumulated_days: 424,
                     factor: 0.544100030330886,
                     accumulated_factor: 10.26981785656444,
-                    main_iof_tac: 167.14315927245235,
-                    debit_service: 106.86684072754765,
+                    main_iof_tac: 167.14315927245232,
+                    debit_service: 106.86684072754768,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+                    opening_balance: 2352.31,
+                    interest: 100.48,
+                    principal: 173.53,
+                    iof: 5.85,
+                    tac: 0.0,
+                    closing_balance: 2178.78,
                 },
                 Invoice {
                     accumulated_days: 454,
                     factor: 0.520719714887871,
                     accumulated_factor: 10.790537571452312,
                     main_iof_tac: 174.64788712378544,
-                    debit_service: 99.36211287621454,
+                    debit_service: 99.36211287621457,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 11, 18).unwrap(),
+                    opening_balance: 2178.78,
+                    interest: 97.83,
+                    principal: 176.18,
+                    iof: 5.94,
+                    tac: 0.0,
+                    closing_balance: 2002.6,
                 },
                 Invoice {
                     accumulated_days: 484,
                     factor: 0.498344066086544,
                     accumulated_factor: 11.288881637538855,
-                    main_iof_tac: 182.48957725564344,
-                    debit_service: 91.52042274435657,
+                    main_iof_tac: 182.48957725564338,
+                    debit_service: 91.52042274435661,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2026, 12, 18).unwrap(),
+                    opening_balance: 2002.6,
+                    interest: 89.92,
+                    principal: 184.09,
+                    iof: 6.21,
+                    tac: 0.0,
+                    closing_balance: 1818.51,
                 },
                 Invoice {
                     accumulated_days: 515,
                     factor: 0.47892907471239,
                     accumulated_factor: 11.767810712251245,
-                    main_iof_tac: 190.6833592744218,
-                    debit_service: 83.32664072557819,
+                    main_iof_tac: 190.68335927442178,
+                    debit_service: 83.32664072557822,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 1, 18).unwrap(),
+                    opening_balance: 1818.51,
+                    interest: 73.72,
+                    principal: 200.29,
+                    iof: 6.75,
+                    tac: 0.0,
+                    closing_balance: 1618.22,
                 },
                 Invoice {
                     accumulated_days: 546,
                     factor: 0.45834919561406,
                     accumulated_factor: 12.226159907865306,
                     main_iof_tac: 199.24504210584334,
-                    debit_service: 74.76495789415665,
+                    debit_service: 74.76495789415668,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 2, 18).unwrap(),
+                    opening_balance: 1618.22,
+                    interest: 72.66,
+                    principal: 201.35,
+                    iof: 6.8,
+                    tac: 0.0,
+                    closing_balance: 1416.87,
                 },
                 Invoice {
                     accumulated_days: 574,
                     factor: 0.439572044319771,
                     accumulated_factor: 12.665731952185077,
-                    main_iof_tac: 208.1911444963957,
-                    debit_service: 65.81885550360428,
+                    main_iof_tac: 208.19114449639568,
+                    debit_service: 65.81885550360431,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 3, 18).unwrap(),
+                    opening_balance: 1416.87,
+                    interest: 60.52,
+                    principal: 213.49,
+                    iof: 7.2,
+                    tac: 0.0,
+                    closing_balance: 1203.38,
                 },
                 Invoice {
                     accumulated_days: 606,
                     factor: 0.420683361204136,
                     accumulated_factor: 13.086415313389212,
-                    main_iof_tac: 217.53892688428388,
-                    debit_service: 56.47107311571611,
+                    main_iof_tac: 217.53892688428385,
+                    debit_service: 56.47107311571615,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 4, 19).unwrap(),
+                    opening_balance: 1203.38,
+                    interest: 54.03,
+                    principal: 219.98,
+                    iof: 7.42,
+                    tac: 0.0,
+                    closing_balance: 983.4,
                 },
                 Invoice {
                     accumulated_days: 635,
                     factor: 0.403449262844397,
                     accumulated_factor: 13.48986457623361,
                     main_iof_tac: 227.3064247013882,
-                    debit_service: 46.70357529861177,
+                    debit_service: 46.7035752986118,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 5, 18).unwrap(),
+                    opening_balance: 983.4,
+                    interest: 42.01,
+                    principal: 232.0,
+                    iof: 7.82,
+                    tac: 0.0,
+                    closing_balance: 751.4,
                 },
                 Invoice {
                     accumulated_days: 666,
                     factor: 0.385306092760059,
                     accumulated_factor: 13.875170668993668,
-                    main_iof_tac: 237.51248317048055,
-                    debit_service: 36.497516829519434,
+                    main_iof_tac: 237.51248317048052,
+                    debit_service: 36.497516829519476,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 6, 18).unwrap(),
+                    opening_balance: 751.4,
+                    interest: 35.38,
+                    principal: 238.63,
+                    iof: 8.05,
+                    tac: 0.0,
+                    closing_balance: 512.77,
                 },
                 Invoice {
                     accumulated_days: 697,
                     factor: 0.368749251207657,
                     accumulated_factor: 14.243919920201325,
-                    main_iof_tac: 248.17679366483515,
-                    debit_service: 25.833206335164856,
+                    main_iof_tac: 248.17679366483512,
+                    debit_service: 25.8332063351649,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 7, 19).unwrap(),
+                    opening_balance: 512.77,
+                    interest: 23.02,
+                    principal: 250.99,
+                    iof: 8.46,
+                    tac: 0.0,
+                    closing_balance: 261.78,
                 },
                 Invoice {
                     accumulated_days: 727,
                     factor: 0.352166545526241,
                     accumulated_factor: 14.596086465727566,
-                    main_iof_tac: 259.31993170038623,
-                    debit_service: 14.690068299613758,
+                    main_iof_tac: 259.3199317003862,
+                    debit_service: 14.690068299613802,
+                    amount: 274.01,
                     due_date: chrono::NaiveDate::from_ymd_opt(2027, 8, 18).unwrap(),
+                    opening_balance: 261.78,
+                    interest: 12.23,
+                    principal: 261.78,
+                    iof: 8.84,
+                    tac: 0.0,
+                    closing_balance: 0.0,
                 },
             ],
         };
 
         assert_eq!(resp, expected);
     }
+
+    #[test]
+    fn test_iterative_sac() {
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap();
+
+        let first_payment_date = chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap();
+
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date,
+            disbursement_date,
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            amortization_system: AmortizationSystem::Sac,
+            ..Default::default()
+        };
+
+        let iterative = Iterative;
+
+        let mut resp = iterative.calculate_payment_plan(params).unwrap();
+
+        assert_eq!(resp.len(), 12);
+
+        let resp = resp.pop().unwrap();
+
+        let amounts: Vec<f64> = resp.invoices.iter().map(|i| i.amount).collect();
+
+        // The interest follows the business days of each period, so the installments only decline
+        // broadly as the balance is amortized
+        let expected_amounts = vec![
+            499.41, 501.18, 478.41, 463.58, 442.99, 428.89, 414.79, 407.9, 380.83, 376.8, 361.26,
+            344.99,
+        ];
+        assert_eq!(amounts, expected_amounts);
+        assert_eq!(resp.installment_amount, 499.41);
+        assert_eq!(resp.total_amount, 5101.03);
+        assert_eq!(resp.total_iof, 78.49);
+        assert_eq!(resp.eir_monthly, 0.0448);
+        assert_eq!(resp.tec_monthly, 0.0487);
+
+        // The principal amortization is constant
+        let plan = iterative
+            .calculate_decimal_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let amortization = plan.contract_amount / Decimal::from(12);
+        for invoice in &plan.invoices {
+            assert_eq!(invoice.main_iof_tac, amortization);
+            assert_eq!(invoice.main_iof_tac + invoice.debit_service, invoice.amount);
+        }
+    }
+
+    #[test]
+    fn test_iterative_rounding_policy() {
+        let mut params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            rounding_policy: RoundingPolicy {
+                mode: RoundingMode::Truncate,
+                residual_allocation: ResidualAllocation::LastInstallment,
+            },
+            ..Default::default()
+        };
+
+        let iterative = Iterative;
+
+        let resp = iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        // The truncated installments lose a fraction of a cent each, which adds up on the last one
+        assert_eq!(resp.installment_amount, 433.17);
+        assert_eq!(resp.rounding_residual, 0.02);
+        let last = resp.invoices.len() - 1;
+        for invoice in &resp.invoices[..last] {
+            assert_eq!(invoice.amount, resp.installment_amount);
+        }
+        assert_eq!(resp.invoices[last].amount, 433.19);
+        assert_eq!(resp.total_iof, 83.67);
+        assert_eq!(resp.tec_monthly, 0.0485);
+
+        // Without an allocation the residual is dropped
+        params.rounding_policy.residual_allocation = ResidualAllocation::None;
+        let resp = iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        assert_eq!(resp.rounding_residual, 0.0);
+        assert_eq!(resp.invoices[last].amount, resp.installment_amount);
+
+        // Half even on the first installment
+        params.rounding_policy = RoundingPolicy {
+            mode: RoundingMode::HalfEven,
+            residual_allocation: ResidualAllocation::FirstInstallment,
+        };
+        let resp = iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        assert_eq!(resp.installment_amount, 433.19);
+        assert_eq!(resp.customer_amount, 433.19);
+        assert_eq!(resp.rounding_residual, 0.01);
+        assert_eq!(resp.invoices[0].amount, resp.installment_amount);
+        assert_eq!(resp.invoices[1].amount, 433.18);
+    }
+
+    #[test]
+    fn test_iterative_decimal_payment_plan() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 30.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let iterative = Iterative;
+
+        let plans = iterative.calculate_decimal_payment_plan(params).unwrap();
+        let responses = iterative.calculate_payment_plan(params).unwrap();
+
+        assert_eq!(plans.len(), 12);
+        for (plan, response) in plans.into_iter().zip(responses) {
+            // The invoices reconcile with the totals to the cent
+            let amounts: Decimal = plan.invoices.iter().map(|i| i.amount).sum();
+            let principal: Decimal = plan.invoices.iter().map(|i| i.principal).sum();
+            let iof: Decimal = plan.invoices.iter().map(|i| i.iof).sum();
+            let tac: Decimal = plan.invoices.iter().map(|i| i.tac).sum();
+            assert_eq!(amounts, plan.total_amount);
+            assert_eq!(principal, plan.contract_amount);
+            assert_eq!(iof, plan.total_iof);
+            assert_eq!(tac, Decimal::from(30));
+
+            // The float response is the decimal plan converted
+            assert_eq!(Response::from(plan), response);
+        }
+    }
+
+    #[test]
+    fn test_iterative_interest_only_grace() {
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap();
+
+        let first_payment_date = chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap();
+
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date,
+            disbursement_date,
+            installments: 6,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            grace_period: GracePeriod::InterestOnly,
+            ..Default::default()
+        };
+
+        let iterative = Iterative;
+
+        let mut resp = iterative.calculate_payment_plan(params).unwrap();
+
+        assert_eq!(resp.len(), 6);
+
+        let resp = resp.pop().unwrap();
+
+        // Three grace periods between the disbursement and the first installment, each with an
+        // interest-only invoice on the installments due day
+        assert_eq!(resp.invoices.len(), 9);
+        let grace_invoices = &resp.invoices[..3];
+        let grace_due_dates: Vec<_> = grace_invoices.iter().map(|i| i.due_date).collect();
+        let expected_grace_due_dates = vec![
+            chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 10, 20).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 11, 18).unwrap(),
+        ];
+        assert_eq!(grace_due_dates, expected_grace_due_dates);
+
+        let grace_amounts: Vec<_> = grace_invoices.iter().map(|i| i.amount).collect();
+        assert_eq!(grace_amounts, vec![169.3, 186.62, 177.95]);
+        for invoice in grace_invoices {
+            assert_eq!(invoice.main_iof_tac, 0.0);
+            assert_eq!(invoice.debit_service, invoice.amount);
+        }
+
+        // The installments start from the full balance and still count the days since the disbursement
+        let first_installment = resp.invoices[3];
+        assert_eq!(first_installment.due_date, first_payment_date);
+        assert_eq!(first_installment.accumulated_days, 119);
+        assert_eq!(first_installment.amount, 764.83);
+
+        assert_eq!(resp.installment, 6);
+        assert_eq!(resp.installment_amount, 764.83);
+        assert_eq!(resp.total_amount, 5122.85);
+        assert_eq!(resp.total_iof, 79.78);
+        assert_eq!(resp.contract_amount, 3963.26);
+        assert_eq!(resp.eir_monthly, 0.0448);
+        assert_eq!(resp.tec_monthly, 0.0485);
+    }
+
+    #[test]
+    fn test_iterative_capitalized_grace() {
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap();
+
+        let first_payment_date = chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap();
+
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date,
+            disbursement_date,
+            installments: 6,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let iterative = Iterative;
+
+        let mut resp = iterative.calculate_payment_plan(params).unwrap();
+
+        let resp = resp.pop().unwrap();
+
+        // No interest-only invoices, the grace interest is paid with the installments
+        assert_eq!(resp.invoices.len(), 6);
+        assert_eq!(resp.invoices[0].due_date, first_payment_date);
+        assert!(resp.installment_amount > 764.84);
+    }
+
+    fn custom_schedule_params() -> Params {
+        Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_iterative_balloon() {
+        let mut params = custom_schedule_params();
+        params.balloon_percentage = 0.3;
+
+        let resp = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let amounts: Vec<f64> = resp.invoices.iter().map(|i| i.amount).collect();
+        // 30% of the principal is paid with the last installment
+        assert_eq!(amounts[..11], vec![357.33; 11][..]);
+        assert_eq!(amounts[11], 1552.02);
+        assert_eq!(resp.installment_amount, 357.33);
+        assert_eq!(resp.total_iof, 98.82);
+        assert_eq!(resp.tec_monthly, 0.0486);
+
+        let last = resp.invoices.last().unwrap();
+        assert_eq!(last.closing_balance, 0.0);
+    }
+
+    #[test]
+    fn test_iterative_custom_schedule_monthly() {
+        // The same due dates the plan would step monthly give the same plan
+        let params = custom_schedule_params();
+        let due_dates = (0..12)
+            .map(|i| crate::util::add_months(params.first_payment_date, i))
+            .collect();
+
+        let custom = Iterative
+            .calculate_custom_payment_plan(&CustomScheduleParams {
+                params,
+                due_dates,
+                weights: Vec::new(),
+            })
+            .unwrap();
+
+        let expected = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        assert_eq!(custom, expected);
+    }
+
+    #[test]
+    fn test_iterative_custom_schedule_weights() {
+        let params = custom_schedule_params();
+
+        // Quarterly, with a double installment on the second one
+        let due_dates = vec![
+            chrono::NaiveDate::from_ymd_opt(2025, 11, 19).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 19).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 5, 19).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 8, 19).unwrap(),
+        ];
+
+        let resp = Iterative
+            .calculate_custom_payment_plan(&CustomScheduleParams {
+                params,
+                due_dates: due_dates.clone(),
+                weights: vec![1.0, 2.0, 1.0, 1.0],
+            })
+            .unwrap();
+
+        assert_eq!(resp.installment, 4);
+        assert_eq!(resp.due_date, due_dates[3]);
+
+        let invoice_due_dates: Vec<_> = resp.invoices.iter().map(|i| i.due_date).collect();
+        assert_eq!(invoice_due_dates, due_dates);
+
+        let amounts: Vec<f64> = resp.invoices.iter().map(|i| i.amount).collect();
+        assert_eq!(amounts, vec![1078.01, 2156.02, 1078.01, 1078.01]);
+        assert_eq!(resp.total_iof, 90.67);
+        assert_eq!(resp.tec_monthly, 0.0484);
+        assert_eq!(resp.invoices[3].closing_balance, 0.0);
+    }
+
+    #[test]
+    fn test_iterative_custom_schedule_invalid() {
+        let params = custom_schedule_params();
+        let due_date = chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap();
+        let before_disbursement = chrono::NaiveDate::from_ymd_opt(2025, 8, 1).unwrap();
+
+        let custom = |due_dates: Vec<chrono::NaiveDate>, weights: Vec<f64>| {
+            Iterative.calculate_custom_payment_plan(&CustomScheduleParams {
+                params,
+                due_dates,
+                weights,
+            })
+        };
+
+        assert_eq!(
+            custom(Vec::new(), Vec::new()),
+            Err(PaymentPlanError::InvalidNumberOfInstallments)
+        );
+        assert_eq!(
+            custom(vec![before_disbursement], Vec::new()),
+            Err(PaymentPlanError::InvalidDate(DateRangeError {
+                date: before_disbursement,
+                min_date: params.disbursement_date.succ_opt(),
+                max_date: None,
+            }))
+        );
+        assert_eq!(
+            custom(vec![due_date, due_date], Vec::new()),
+            Err(PaymentPlanError::InvalidDate(DateRangeError {
+                date: due_date,
+                min_date: due_date.succ_opt(),
+                max_date: None,
+            }))
+        );
+        assert_eq!(
+            custom(vec![due_date], vec![1.0, 1.0]),
+            Err(PaymentPlanError::InvalidInstallmentWeights)
+        );
+        assert_eq!(
+            custom(vec![due_date], vec![0.0]),
+            Err(PaymentPlanError::InvalidInstallmentWeights)
+        );
+
+        let mut params = params;
+        params.balloon_percentage = 1.0;
+        assert_eq!(
+            Iterative.calculate_payment_plan(params),
+            Err(PaymentPlanError::InvalidBalloonPercentage)
+        );
+    }
+
+    #[test]
+    fn test_iterative_weekly() {
+        let mut params = custom_schedule_params();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2025, 8, 28).unwrap();
+        params.installments = 8;
+        params.min_installment_amount = 0.0;
+        params.periodicity = Periodicity::Weekly;
+
+        let mut resp = Iterative.calculate_payment_plan(params).unwrap();
+
+        assert_eq!(resp.len(), 8);
+
+        let resp = resp.pop().unwrap();
+
+        let due_dates: Vec<_> = resp.invoices.iter().map(|i| i.due_date).collect();
+        let expected_due_dates = vec![
+            chrono::NaiveDate::from_ymd_opt(2025, 8, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 9, 4).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 9, 11).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 9, 25).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 10, 2).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 10, 9).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 10, 16).unwrap(),
+        ];
+        assert_eq!(due_dates, expected_due_dates);
+
+        assert_eq!(resp.installment_amount, 511.97);
+        assert_eq!(resp.total_iof, 25.1);
+        assert_eq!(resp.eir_monthly, 0.0465);
+        assert_eq!(resp.tec_monthly, 0.0531);
+        assert_eq!(resp.eir_periodic, 0.0105);
+        assert_eq!(resp.tec_periodic, 0.012);
+    }
+
+    #[test]
+    fn test_iterative_biweekly() {
+        let mut params = custom_schedule_params();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2025, 9, 4).unwrap();
+        params.installments = 6;
+        params.min_installment_amount = 0.0;
+        params.periodicity = Periodicity::Biweekly;
+
+        let resp = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        assert_eq!(
+            resp.due_date,
+            chrono::NaiveDate::from_ymd_opt(2025, 11, 13).unwrap()
+        );
+        assert_eq!(resp.installment_amount, 701.49);
+        assert_eq!(resp.eir_periodic, 0.0211);
+        assert_eq!(resp.tec_periodic, 0.0235);
+    }
+
+    fn due_dates(params: Params) -> Vec<chrono::NaiveDate> {
+        let resp = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+        resp.invoices.iter().map(|i| i.due_date).collect()
+    }
+
+    #[test]
+    fn test_iterative_due_day_stepped() {
+        let mut params = custom_schedule_params();
+        params.disbursement_only_on_business_days = false;
+        params.disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        params.installments = 4;
+        params.min_installment_amount = 0.0;
+
+        // The default keeps the day lost on February, as the legacy plans
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 28).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+    }
+
+    #[test]
+    fn test_iterative_due_day_anchored() {
+        let mut params = custom_schedule_params();
+        params.disbursement_only_on_business_days = false;
+        params.disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        params.installments = 4;
+        params.min_installment_amount = 0.0;
+        params.due_day_policy = DueDayPolicy::Anchored;
+
+        // Back to the 31st after February
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+
+        // The business days variant moves the same due dates to the next business day
+        params.disbursement_only_on_business_days = true;
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+    }
+
+    #[test]
+    fn test_iterative_due_day_end_of_month() {
+        let mut params = custom_schedule_params();
+        params.disbursement_only_on_business_days = false;
+        params.disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 12).unwrap();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 2, 10).unwrap();
+        params.installments = 3;
+        params.min_installment_amount = 0.0;
+        params.due_day_policy = DueDayPolicy::EndOfMonth;
+
+        let resp = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let due_dates: Vec<_> = resp.invoices.iter().map(|i| i.due_date).collect();
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+        ];
+        assert_eq!(due_dates, expected);
+        assert_eq!(resp.due_day_policy, DueDayPolicy::EndOfMonth);
+    }
+
+    #[test]
+    fn test_iterative_due_day_fixed_day() {
+        let mut params = custom_schedule_params();
+        params.disbursement_only_on_business_days = false;
+        params.disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 12).unwrap();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 2, 15).unwrap();
+        params.installments = 3;
+        params.min_installment_amount = 0.0;
+        params.due_day_policy = DueDayPolicy::FixedDay(10);
+
+        // The 10th of February is before the first payment date
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 10).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 5, 10).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+
+        params.due_day_policy = DueDayPolicy::FixedDay(0);
+        assert_eq!(
+            Iterative.calculate_payment_plan(params),
+            Err(PaymentPlanError::InvalidDueDay)
+        );
+    }
+
+    #[test]
+    fn test_iterative_holiday_calendar() {
+        let mut params = custom_schedule_params();
+        params.disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        params.installments = 4;
+        params.min_installment_amount = 0.0;
+        params.due_day_policy = DueDayPolicy::Anchored;
+
+        let plans = Iterative
+            .calculate_payment_plan_with_calendar(params, &BrazilianBankHolidays)
+            .unwrap();
+        assert_eq!(plans, Iterative.calculate_payment_plan(params).unwrap());
+
+        // Bank closures on two of the due dates of the anchored test
+        let closures = HolidayList::from_text("2026-03-02\n2026-03-31").unwrap();
+        let calendar = closures.on_top_of(BrazilianBankHolidays);
+
+        let resp = Iterative
+            .calculate_payment_plan_with_calendar(params, &calendar)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let due_dates: Vec<chrono::NaiveDate> = resp.invoices.iter().map(|i| i.due_date).collect();
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 3).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+        ];
+        assert_eq!(due_dates, expected);
+        assert_ne!(resp.installment_amount, plans[3].installment_amount);
+
+        // The explicit due dates are moved on the same calendar
+        let custom = Iterative
+            .calculate_custom_payment_plan_with_calendar(
+                &CustomScheduleParams {
+                    params,
+                    due_dates: vec![
+                        chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+                        chrono::NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(),
+                        chrono::NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+                        chrono::NaiveDate::from_ymd_opt(2026, 4, 30).unwrap(),
+                    ],
+                    weights: Vec::new(),
+                },
+                &calendar,
+            )
+            .unwrap();
+        assert_eq!(custom, resp);
+    }
+
+    #[test]
+    fn test_iterative_business_day_convention() {
+        let mut params = custom_schedule_params();
+        params.disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 3).unwrap(); // Saturday
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(); // Saturday
+        params.installments = 2;
+        params.min_installment_amount = 0.0;
+
+        // Feb 2 and Mar 2 are on the following month of the due dates
+        params.business_day_convention = BusinessDayConvention::ModifiedFollowing;
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 30).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 27).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+
+        // Feb 15 and Mar 15 are Sundays, and Feb 16 and 17 are Carnival
+        params.first_payment_date = chrono::NaiveDate::from_ymd_opt(2026, 2, 15).unwrap();
+        params.business_day_convention = BusinessDayConvention::Following;
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 18).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 16).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+
+        params.business_day_convention = BusinessDayConvention::Preceding;
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 13).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+        ];
+        assert_eq!(due_dates(params), expected);
+
+        // The disbursement is moved back too, and the days count from the adjusted dates
+        let resp = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        assert_eq!(resp.disbursement_date, disbursement_date);
+        for invoice in resp.invoices {
+            let days = invoice
+                .due_date
+                .signed_duration_since(disbursement_date)
+                .num_days();
+            assert_eq!(invoice.accumulated_days, days);
+        }
+    }
+
+    #[test]
+    fn test_iterative_iof_convergence() {
+        let mut params = custom_schedule_params();
+
+        let plans = Iterative.calculate_payment_plan(params).unwrap();
+        for plan in &plans {
+            assert!(plan.iof_iterations >= 2);
+            assert!(plan.iof_iterations <= params.iof_convergence.max_iterations);
+            assert!(plan.iof_residual <= params.iof_convergence.tolerance);
+        }
+
+        // An exact IOF can not be reached in the first comparison
+        params.iof_convergence = IofConvergence {
+            tolerance: 0.0,
+            max_iterations: 2,
+        };
+        let resp = Iterative.calculate_payment_plan(params).unwrap_err();
+        match resp {
+            PaymentPlanError::IofConvergenceError {
+                iterations,
+                residual,
+            } => {
+                assert_eq!(iterations, 2);
+                assert!(residual > 0.0);
+            }
+            _ => panic!("expected the IOF convergence error"),
+        }
+        assert_eq!(resp.code(), 302);
+    }
+
+    #[test]
+    fn test_iterative_selected_payment_plans() {
+        let mut params = custom_schedule_params();
+        params.installments = 48;
+        params.min_installment_amount = 300.0;
+
+        let plans = Iterative.calculate_payment_plan(params).unwrap();
+        let last = plans.len() as u32;
+        assert!(last > 3 && last < 48);
+
+        // The same plans as on the whole ladder, and the ones out of it with the limit they are out of
+        let resp = Iterative
+            .calculate_selected_payment_plans(params, &[48, last + 1, 3, last, 3])
+            .unwrap();
+        assert_eq!(
+            resp.plans,
+            vec![plans[2].clone(), plans[last as usize - 1].clone()]
+        );
+        assert_eq!(
+            resp.excluded,
+            vec![
+                ExcludedInstallment {
+                    installment: last + 1,
+                    reason: ExclusionReason::MinInstallmentAmount,
+                },
+                ExcludedInstallment {
+                    installment: 48,
+                    reason: ExclusionReason::MinInstallmentAmount,
+                },
+            ]
+        );
+
+        params.max_total_amount = plans[2].total_amount;
+        let resp = Iterative
+            .calculate_selected_payment_plans(params, &[3, 4])
+            .unwrap();
+        assert_eq!(resp.plans, vec![plans[2].clone()]);
+        assert_eq!(
+            resp.excluded,
+            vec![ExcludedInstallment {
+                installment: 4,
+                reason: ExclusionReason::MaxTotalAmount,
+            }]
+        );
+
+        for installments in [&[][..], &[0, 3][..]] {
+            assert_eq!(
+                Iterative.calculate_selected_payment_plans(params, installments),
+                Err(PaymentPlanError::InvalidNumberOfInstallments)
+            );
+        }
+    }
 }
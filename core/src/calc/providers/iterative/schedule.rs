@@ -0,0 +1,172 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    util::{round_decimal_with, to_decimal},
+    DecimalInvoice, RoundingMode,
+};
+
+/*
+    The amortization schedule of the invoices, starting from the balance financed:
+        - The interest is accrued on the opening balance with the daily rate for the (business) days since the previous due date.
+        - The principal is what is left of the invoice amount, and lowers the balance.
+        - The last invoice amortizes the whole balance left, so the closing balance is exactly 0
+            and its interest is what is left of the amount.
+    Every value is rounded to cents with the rounding mode.
+*/
+pub fn insert_schedule_on_invoices(
+    invoices: &mut [DecimalInvoice],
+    balance: Decimal,
+    business_diffs: &[i64],
+    daily_interest_rate: f64,
+    mode: RoundingMode,
+) {
+    let last = invoices.len().saturating_sub(1);
+    let mut balance = round_decimal_with(balance, 2, mode);
+
+    for (i, (invoice, diff)) in invoices.iter_mut().zip(business_diffs).enumerate() {
+        let interest = balance * to_decimal((1.0 + daily_interest_rate).powf(*diff as f64) - 1.0);
+        let interest = round_decimal_with(interest, 2, mode);
+
+        let (interest, principal) = if i == last {
+            let interest = round_decimal_with(invoice.amount - balance, 2, mode);
+            (interest, balance)
+        } else {
+            (
+                interest,
+                round_decimal_with(invoice.amount - interest, 2, mode),
+            )
+        };
+
+        invoice.opening_balance = balance;
+        invoice.interest = interest;
+        invoice.principal = principal;
+
+        balance = round_decimal_with(balance - principal, 2, mode);
+        invoice.closing_balance = balance;
+    }
+}
+
+/*
+    Splits the IOF and the TAC financed over the invoices.
+    The IOF of each installment is the one calculated on its principal (see `iof::calc_installments`),
+    the TAC is split in proportion to the principal of the invoices.
+    The interest-only grace invoices don't amortize anything, so they have no share,
+    and the last invoice takes the rounding left so the shares add up to the totals.
+*/
+pub fn insert_iof_and_tac_on_invoices(
+    invoices: &mut [DecimalInvoice],
+    iofs: &[Decimal],
+    total_iof: Decimal,
+    tac_amount: Decimal,
+    grace_periods: usize,
+    mode: RoundingMode,
+) {
+    let total_principal: Decimal = invoices.iter().map(|invoice| invoice.principal).sum();
+    let last = invoices.len().saturating_sub(1);
+
+    let mut iof_left = total_iof;
+    let mut tac_left = tac_amount;
+
+    for (i, invoice) in invoices.iter_mut().enumerate().skip(grace_periods) {
+        if i == last {
+            invoice.iof = round_decimal_with(iof_left, 2, mode);
+            invoice.tac = round_decimal_with(tac_left, 2, mode);
+            break;
+        }
+
+        let iof = round_decimal_with(iofs[i - grace_periods], 2, mode);
+        let tac = if total_principal > Decimal::ZERO {
+            round_decimal_with(tac_amount * invoice.principal / total_principal, 2, mode)
+        } else {
+            Decimal::ZERO
+        };
+
+        invoice.iof = iof;
+        invoice.tac = tac;
+        iof_left -= iof;
+        tac_left -= tac;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    use crate::{DecimalInvoice, RoundingMode};
+
+    use super::{insert_iof_and_tac_on_invoices, insert_schedule_on_invoices};
+
+    fn invoices(amounts: &[Decimal]) -> Vec<DecimalInvoice> {
+        amounts
+            .iter()
+            .map(|amount| DecimalInvoice {
+                amount: *amount,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_schedule_on_invoices() {
+        // 1000.0 at 1% a.m. (1 period of 30 days), Price table of 3 installments
+        let daily_interest_rate = 1.01_f64.powf(1.0 / 30.0) - 1.0;
+        let mut invoices = invoices(&[dec!(340.02), dec!(340.02), dec!(340.02)]);
+
+        insert_schedule_on_invoices(
+            &mut invoices,
+            dec!(1000.0),
+            &[30, 30, 30],
+            daily_interest_rate,
+            RoundingMode::HalfUp,
+        );
+
+        let schedule: Vec<(Decimal, Decimal, Decimal, Decimal)> = invoices
+            .iter()
+            .map(|i| {
+                (
+                    i.opening_balance,
+                    i.interest,
+                    i.principal,
+                    i.closing_balance,
+                )
+            })
+            .collect();
+
+        let expected = vec![
+            (dec!(1000.0), dec!(10.0), dec!(330.02), dec!(669.98)),
+            (dec!(669.98), dec!(6.7), dec!(333.32), dec!(336.66)),
+            (dec!(336.66), dec!(3.36), dec!(336.66), dec!(0.0)),
+        ];
+
+        assert_eq!(schedule, expected);
+    }
+
+    #[test]
+    fn test_insert_iof_and_tac_on_invoices() {
+        let mut invoices = invoices(&[dec!(0.0), dec!(0.0), dec!(0.0)]);
+        invoices[0].principal = dec!(0.0); // grace
+        invoices[1].principal = dec!(500.0);
+        invoices[2].principal = dec!(500.0);
+
+        insert_iof_and_tac_on_invoices(
+            &mut invoices,
+            &[dec!(3.333), dec!(3.333)],
+            dec!(6.67),
+            dec!(10.01),
+            1,
+            RoundingMode::HalfUp,
+        );
+
+        let shares: Vec<(Decimal, Decimal)> = invoices.iter().map(|i| (i.iof, i.tac)).collect();
+
+        assert_eq!(
+            shares,
+            vec![
+                (dec!(0.0), dec!(0.0)),
+                (dec!(3.33), dec!(5.01)),
+                (dec!(3.34), dec!(5.0))
+            ]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::NaiveDate;
+
+use crate::{
+    util::{
+        add_due_periods, adjust_to_business_day, diff_in_business_days, round_decimal_cases,
+        sub_due_periods,
+    },
+    GracePeriod,
+};
+
+use super::InnerParams;
+
+/*
+    The due dates of the longest plan with their days and discount factors, calculated once and shared by
+    the plans of fewer installments: the due dates of a plan are the first ones of any longer plan,
+    so a plan of `n` installments takes the first `n` entries.
+    The interest-only grace entries come before the installments, which count their days (and factors)
+    from the last grace due date, as if the contract was disbursed on it.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timeline {
+    pub(super) grace_due_dates: Vec<NaiveDate>,
+    pub(super) grace_diffs: Vec<i64>,
+    pub(super) grace_business_diffs: Vec<i64>,
+    pub(super) due_dates: Vec<NaiveDate>,
+    pub(super) diffs: Vec<i64>,
+    pub(super) business_diffs: Vec<i64>,
+    pub(super) accumulated_days: Vec<i64>,
+    pub(super) accumulated_business_days: Vec<i64>,
+    pub(super) factors: Vec<f64>,
+    pub(super) accumulated_factors: Vec<f64>,
+}
+
+impl Timeline {
+    /// The timeline of the installments of `inner_params`, the most a plan of them can take.
+    pub fn new(inner_params: &InnerParams) -> Self {
+        let params = inner_params.params;
+        let on_business_days = params.disbursement_only_on_business_days;
+        let calendar = inner_params.calendar;
+
+        let grace_due_dates = grace_due_dates(inner_params);
+        let mut grace_diffs = Vec::with_capacity(grace_due_dates.len());
+        let mut grace_business_diffs = Vec::with_capacity(grace_due_dates.len());
+
+        let mut last_due_date = params.disbursement_date;
+        for due_date in &grace_due_dates {
+            let diff = due_date.signed_duration_since(last_due_date).num_days();
+            let b_diff = if on_business_days {
+                diff_in_business_days(last_due_date, *due_date, calendar)
+            } else {
+                diff
+            };
+            grace_diffs.push(diff);
+            grace_business_diffs.push(b_diff);
+            last_due_date = *due_date;
+        }
+
+        let installments = params.installments as usize;
+        let mut timeline = Timeline {
+            grace_due_dates,
+            grace_diffs,
+            grace_business_diffs,
+            due_dates: Vec::with_capacity(installments),
+            diffs: Vec::with_capacity(installments),
+            business_diffs: Vec::with_capacity(installments),
+            accumulated_days: Vec::with_capacity(installments),
+            accumulated_business_days: Vec::with_capacity(installments),
+            factors: Vec::with_capacity(installments),
+            accumulated_factors: Vec::with_capacity(installments),
+        };
+
+        let base_factor = 1.0 / (1.0 + inner_params.daily_interest_rate);
+        let mut accumulated_days = 0;
+        let mut accumulated_business_days = 0;
+        let mut accumulated_factor = 0.0;
+
+        for i in 0..params.installments {
+            let due_date = due_date(inner_params, i);
+            let diff = due_date.signed_duration_since(last_due_date).num_days();
+            let b_diff = if on_business_days {
+                diff_in_business_days(last_due_date, due_date, calendar)
+            } else {
+                diff
+            };
+
+            accumulated_days += diff;
+            accumulated_business_days += b_diff;
+
+            let factor = base_factor.powf(accumulated_business_days as f64);
+            let factor = round_decimal_cases(factor, 15);
+            accumulated_factor += factor;
+
+            timeline.due_dates.push(due_date);
+            timeline.diffs.push(diff);
+            timeline.business_diffs.push(b_diff);
+            timeline.accumulated_days.push(accumulated_days);
+            timeline
+                .accumulated_business_days
+                .push(accumulated_business_days);
+            timeline.factors.push(factor);
+            timeline.accumulated_factors.push(accumulated_factor);
+
+            last_due_date = due_date;
+        }
+
+        timeline
+    }
+
+    /// The due dates of the installments, after the due day policy and the business days.
+    pub fn due_dates(&self) -> &[NaiveDate] {
+        &self.due_dates
+    }
+
+    /// The due dates of the interest-only grace invoices, before the installments.
+    pub fn grace_due_dates(&self) -> &[NaiveDate] {
+        &self.grace_due_dates
+    }
+}
+
+/*
+    The explicit due date of the installment, or the periods after the first payment date.
+    On business days the periods are added to the base date (the first payment date before it was
+    moved to a business day), and the due date is then moved with the business day convention.
+*/
+fn due_date(inner_params: &InnerParams, i: u32) -> NaiveDate {
+    let params = inner_params.params;
+    if !params.disbursement_only_on_business_days {
+        if let Some(custom_due_date) = inner_params.due_dates.get(i as usize) {
+            return *custom_due_date;
+        }
+        if i == 0 {
+            return params.first_payment_date;
+        }
+        return add_due_periods(
+            params.first_payment_date,
+            i,
+            params.periodicity,
+            params.due_day_policy,
+        );
+    }
+
+    let due_date = match inner_params.due_dates.get(i as usize) {
+        Some(custom_due_date) => *custom_due_date,
+        None => add_due_periods(
+            inner_params.base_date,
+            i,
+            params.periodicity,
+            params.due_day_policy,
+        ),
+    };
+    adjust_to_business_day(
+        due_date,
+        params.business_day_convention,
+        inner_params.calendar,
+    )
+}
+
+/*
+    The grace window goes from the disbursement to the first installment, and every whole period
+    (rounded to its days, 30 for a month) beyond the first one is a grace period.
+    With interest-only grace, each period has an invoice due a whole number of periods before the
+    first installment, so they share its due day.
+*/
+fn grace_due_dates(inner_params: &InnerParams) -> Vec<NaiveDate> {
+    let params = inner_params.params;
+    if params.grace_period != GracePeriod::InterestOnly {
+        return Vec::new();
+    }
+
+    let days = params
+        .first_payment_date
+        .signed_duration_since(params.disbursement_date)
+        .num_days();
+    let period_days = params.periodicity.days();
+    let periods = (days + period_days / 2) / period_days;
+    if periods <= 1 {
+        return Vec::new();
+    }
+
+    let grace_periods = (periods - 1) as u32;
+    let mut due_dates = Vec::with_capacity(grace_periods as usize);
+    for i in (1..=grace_periods).rev() {
+        let mut due_date = sub_due_periods(
+            inner_params.base_date,
+            i,
+            params.periodicity,
+            params.due_day_policy,
+        );
+        if params.disbursement_only_on_business_days {
+            due_date = adjust_to_business_day(
+                due_date,
+                params.business_day_convention,
+                inner_params.calendar,
+            );
+        }
+        due_dates.push(due_date);
+    }
+    due_dates
+}
+
+#[cfg(test)]
+mod test {
+    use rust_decimal_macros::dec;
+
+    use crate::{
+        calc::providers::iterative::{installment, InnerParams},
+        calendar::BrazilianBankHolidays,
+        GracePeriod, Params,
+    };
+
+    use super::Timeline;
+
+    #[test]
+    fn test_timeline_shared_by_shorter_plans() {
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap();
+        let first_payment_date = chrono::NaiveDate::from_ymd_opt(2025, 12, 18).unwrap();
+
+        let mut params = InnerParams {
+            params: Params {
+                disbursement_only_on_business_days: true,
+                requested_amount: 3883.48,
+                first_payment_date,
+                disbursement_date,
+                installments: 24,
+                interest_rate: 0.0449,
+                grace_period: GracePeriod::InterestOnly,
+                ..Default::default()
+            },
+            main_value: dec!(3883.48),
+            daily_interest_rate: 0.0014,
+            base_date: first_payment_date,
+            due_dates: &[],
+            weights: &[],
+            calendar: &BrazilianBankHolidays,
+        };
+        let timeline = Timeline::new(&params);
+        assert_eq!(timeline.grace_due_dates.len(), 3);
+        assert_eq!(timeline.due_dates.len(), 24);
+
+        // A shorter plan is the same on the longest timeline as on its own
+        for installments in [1, 6, 13, 24] {
+            params.params.installments = installments;
+            assert_eq!(
+                installment::calc(&params, &timeline),
+                installment::calc(&params, &Timeline::new(&params))
+            );
+        }
+    }
+}
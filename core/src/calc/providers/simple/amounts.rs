@@ -1,4 +1,6 @@
-use crate::Params;
+use crate::{util::round_decimal_cases, Params, ResidualAllocation, RoundingPolicy};
+
+use super::round_with_policy;
 
 #[derive(Debug)]
 pub struct AmountsResponse {
@@ -15,8 +17,14 @@ pub struct AmountsResponse {
     pub merchant_debit_service_amount: f64,
     pub merchant_total_amount: f64,
     pub settled_to_merchant: f64,
+    pub rounding_residual: f64,
 }
 
+/*
+    The amounts of the plan from its accumulated factor and total IOF.
+    Rounded to cents by a non-default rounding policy, with the residual of the rounded total added to
+    the installment of its residual allocation (the first one is the `installment_amount`).
+*/
 pub fn calculate_amounts(
     params: Params,
     accumulated_days_index: f64,
@@ -29,34 +37,56 @@ pub fn calculate_amounts(
     let requested_amount = params.requested_amount;
     let tac_amount = params.tac_percentage;
 
-    let contract_amount = requested_amount + tac_amount + total_iof;
-    let contract_amount_without_tac = requested_amount + total_iof;
+    let round = |value: f64| round_with_policy(&params, value, 2);
+    // Sums of rounded amounts are only cleared of the binary representation errors
+    let clear = |value: f64| match params.rounding_policy == RoundingPolicy::default() {
+        true => value,
+        false => round_decimal_cases(value, 2),
+    };
+
+    let contract_amount = round(requested_amount + tac_amount + total_iof);
+    let contract_amount_without_tac = round(requested_amount + total_iof);
 
-    let installment_amount = contract_amount * (1.0 / accumulated_days_index);
+    let exact_installment_amount = contract_amount * (1.0 / accumulated_days_index);
+    let installment_amount = round(exact_installment_amount);
     let installment_amount_without_tac =
-        contract_amount_without_tac * (1.0 / accumulated_days_index);
+        round(contract_amount_without_tac * (1.0 / accumulated_days_index));
+
+    let rounding_residual = match params.rounding_policy.residual_allocation {
+        ResidualAllocation::None => 0.0,
+        _ => clear(
+            round(exact_installment_amount * installments) - installment_amount * installments,
+        ),
+    };
+    let total_amount = clear(installment_amount * installments + rounding_residual);
+    let installment_amount = match params.rounding_policy.residual_allocation {
+        ResidualAllocation::FirstInstallment => clear(installment_amount + rounding_residual),
+        _ => installment_amount,
+    };
 
-    let total_amount = installment_amount * installments;
-    let debit_service = total_amount - requested_amount - tac_amount - total_iof;
-    let customer_debit_service_amount = debit_service * customer_debit_service_proportion;
+    let debit_service = clear(total_amount - requested_amount - tac_amount - total_iof);
+    let customer_debit_service_amount = round(debit_service * customer_debit_service_proportion);
 
     // CALCULATION BASIS FOR totalEffectiveCost
-    let customer_amount = (requested_amount
-        + (debit_service + tac_amount) * customer_debit_service_proportion
-        + total_iof)
-        / installments;
+    let customer_amount = round(
+        (requested_amount
+            + (debit_service + tac_amount) * customer_debit_service_proportion
+            + total_iof)
+            / installments,
+    );
 
-    let calculation_basis_for_effective_interest_rate =
-        (requested_amount + debit_service * customer_debit_service_proportion) / installments;
+    let calculation_basis_for_effective_interest_rate = round(
+        (requested_amount + debit_service * customer_debit_service_proportion) / installments,
+    );
 
-    let mdr_amount = requested_amount * params.mdr;
+    let mdr_amount = round(requested_amount * params.mdr);
 
     let merchant_debit_service_amount =
-        (debit_service + tac_amount) * debit_service_percentage as f64;
+        round((debit_service + tac_amount) * debit_service_percentage as f64);
 
-    let merchant_total_amount = merchant_debit_service_amount + mdr_amount;
+    let merchant_total_amount = clear(merchant_debit_service_amount + mdr_amount);
 
-    let settled_to_merchant = requested_amount - merchant_total_amount;
+    let settled_to_merchant = clear(requested_amount - merchant_total_amount);
 
     return AmountsResponse {
         contract_amount,
@@ -72,6 +102,7 @@ pub fn calculate_amounts(
         merchant_debit_service_amount,
         merchant_total_amount,
         settled_to_merchant,
+        rounding_residual,
     };
 }
 
@@ -95,6 +126,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let accumulated_days_index = 0.9650762734315015;
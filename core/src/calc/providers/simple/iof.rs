@@ -1,5 +1,9 @@
-use crate::Params;
+use crate::{util::round_decimal_cases_with, Params};
 
+/*
+    The total IOF of a plan with `installments` installments due `accumulated_days` after the disbursement:
+    the overall IOF of the contract plus the daily IOF (limited to 365 days) of each installment without interest.
+*/
 pub fn calculate_iof(params: Params, accumulated_days: Vec<i64>, installments: f64) -> f64 {
     let requested_amount = params.requested_amount;
     let tac_amount = params.tac_percentage;
@@ -8,8 +12,11 @@ pub fn calculate_iof(params: Params, accumulated_days: Vec<i64>, installments: f
 
     let installment_amount_without_interest = (requested_amount + tac_amount) / installments;
     let installment_amount_without_interest = installment_amount_without_interest + f64::EPSILON;
-    let installment_amount_without_interest =
-        (installment_amount_without_interest * 100.0).round() / 100.0;
+    let installment_amount_without_interest = round_decimal_cases_with(
+        installment_amount_without_interest,
+        2,
+        params.rounding_policy.mode,
+    );
 
     let contract_iof = installment_amount_without_interest * installments * iof_overall;
 
@@ -51,6 +58,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.025,
+            ..Default::default()
         };
 
         let aux_accumulated_days = vec![31];
@@ -118,6 +126,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let aux_accumulated_days = vec![31];
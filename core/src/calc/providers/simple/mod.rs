@@ -4,18 +4,23 @@ use prepare::{prepare_calculation, PreparedCalculation};
 
 use crate::{
     calc::{
-        inner_xirr::{eir::calculate_eir_monthly, prepare_xirr_params, tec::calculate_tec_monthly},
+        inner_xirr::{
+            eir::calculate_eir_monthly, periodic_rate, prepare_xirr_params,
+            tec::calculate_tec_monthly,
+        },
+        selection::pick_selected_payment_plans,
         PaymentPlan,
     },
     err::PaymentPlanError,
-    Params, Response,
+    util::round_decimal_cases_with,
+    Params, Response, RoundingPolicy, SelectedPlans,
 };
 
-mod amounts;
-mod iof;
-mod prepare;
+pub(crate) mod amounts;
+pub(crate) mod iof;
+pub(crate) mod prepare;
 
-const CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE: f64 = 0.0821917808219178;
+pub const CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE: f64 = 0.0821917808219178;
 
 /**
  * This is a simpler implementation of the payment plan calculation.
@@ -23,17 +28,33 @@ const CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE: f64 = 0.0821917808219178;
  * This results in less precise results, because the real iof value comes from a iterative calculation that uses the result of the previous iteration to calculate the next one.
  * But it is much faster and simpler to understand.
  * And as is cannot calculate the plan on business days only
+ * It also ignores the amortization system, the grace period and the balloon, always using Price with equal installments and the grace interest capitalized
+ * Nor does it fill the amortization schedule of the invoices (opening balance, interest, principal, IOF, TAC and closing balance)
+ * With the default rounding policy its amounts and rates are not rounded (only the IOF base is, half up), as on the legacy plans
+ * Any other rounding policy rounds the IOF and the amounts to cents and the rates as the Iterative provider, with the mode of the policy
  * Right now it is here for legacy reasons, but it is not recommended to use it in new code.
  */
 pub struct Simple;
 
 impl PaymentPlan for Simple {
     fn calculate_payment_plan(&self, params: Params) -> Result<Vec<Response>, PaymentPlanError> {
+        if !params.due_day_policy.is_valid() {
+            return Err(PaymentPlanError::InvalidDueDay);
+        }
         let prepared_calculations = prepare_calculation(params);
         let calculated = calculate(params, prepared_calculations);
 
         return calculated;
     }
+
+    // The plan of a single installment is not bound by the minimum installment amount, as on the plans up to it
+    fn calculate_selected_payment_plans(
+        &self,
+        params: Params,
+        installments: &[u32],
+    ) -> Result<SelectedPlans, PaymentPlanError> {
+        pick_selected_payment_plans(self, params, installments, 2)
+    }
 }
 
 fn calculate(
@@ -70,6 +91,7 @@ fn calculate(
             aux_accumulated_days_index,
             prepared_calculation.installment as f64,
         );
+        let total_iof = round_with_policy(&params, total_iof, 2);
 
         let amounts = calculate_amounts(
             params,
@@ -99,6 +121,7 @@ fn calculate(
         )?;
 
         let eir_yearly = (1.0 + eir_monthly).powf(12.0) - 1.0;
+        let eir_periodic = periodic_rate(eir_monthly, params.periodicity);
 
         let tec_monthly = calculate_tec_monthly(
             params,
@@ -107,6 +130,15 @@ fn calculate(
         )?;
 
         let tec_yearly = (1.0 + tec_monthly).powf(12.0) - 1.0;
+        let tec_periodic = periodic_rate(tec_monthly, params.periodicity);
+
+        let eir_monthly = round_with_policy(&params, eir_monthly, 4);
+        let tec_monthly = round_with_policy(&params, tec_monthly, 4);
+        let eir_periodic = round_with_policy(&params, eir_periodic, 4);
+        let tec_periodic = round_with_policy(&params, tec_periodic, 4);
+
+        let eir_yearly = round_with_policy(&params, eir_yearly, 6);
+        let tec_yearly = round_with_policy(&params, tec_yearly, 6);
 
         let installment_amount = amounts.installment_amount;
 
@@ -143,6 +175,8 @@ fn calculate(
             tec_yearly,
             eir_monthly,
             tec_monthly,
+            eir_periodic,
+            tec_periodic,
             total_iof,
             contract_amount: amounts.contract_amount,
             contract_amount_without_tac: amounts.contract_amount_without_tac,
@@ -153,6 +187,10 @@ fn calculate(
             pre_disbursement_amount: amounts.total_amount,
             paid_total_iof: total_iof,
             paid_contract_amount: amounts.contract_amount,
+            due_day_policy: params.due_day_policy,
+            rounding_residual: amounts.rounding_residual,
+            iof_iterations: 0, // The IOF is estimated, not iterated
+            iof_residual: 0.0,
             invoices: vec![prepared_calculation.invoice],
         };
 
@@ -162,6 +200,15 @@ fn calculate(
     return Ok(responses);
 }
 
+// Rounds the value with the mode of the rounding policy, the default policy keeps the legacy unrounded value
+pub(crate) fn round_with_policy(params: &Params, value: f64, round: i32) -> f64 {
+    if params.rounding_policy == RoundingPolicy::default() {
+        return value;
+    }
+
+    round_decimal_cases_with(value, round, params.rounding_policy.mode)
+}
+
 #[cfg(test)]
 mod test {
     //Test 0 - (8800 / 24) = (11980.77027564256 / 499.1987614851067)
@@ -182,7 +229,10 @@ mod test {
     //Test 15 - (1000 / 24) = (1275.5756523433513 / 106.29797102861261) max installment amount 100
     //Test 16 - (44 / 48) = (46.05063251213531 / 46.05063251213531) min installment amount 80
 
-    use crate::{calc::PaymentPlan, Params};
+    use crate::{
+        calc::PaymentPlan, ExcludedInstallment, ExclusionReason, Params, ResidualAllocation,
+        RoundingMode, RoundingPolicy,
+    };
 
     const SIMPLE: super::Simple = super::Simple {};
 
@@ -222,6 +272,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0235,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -302,6 +353,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.025,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -382,6 +434,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0235,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -462,6 +515,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.024,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -542,6 +596,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0235,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -622,6 +677,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0349,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -702,6 +758,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.025,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -782,6 +839,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -862,6 +920,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.028999999999999998,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -942,6 +1001,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0349,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1022,6 +1082,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.029,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1102,6 +1163,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0215,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1182,6 +1244,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.025,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1262,6 +1325,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0215,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1342,6 +1406,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0355,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1422,6 +1487,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0355,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1502,6 +1568,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0355,
+            ..Default::default()
         };
 
         let result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1582,6 +1649,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0355,
+            ..Default::default()
         };
 
         let mut result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1626,6 +1694,88 @@ mod test {
         assert_eq!(response.total_iof, expected_total_iof);
     }
 
+    #[test]
+    fn test_calculate_payment_plan_rounding_policy() {
+        let params = Params {
+            requested_amount: 8800.0,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2022, 4, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2022, 3, 18).unwrap(),
+            installments: 24,
+            mdr: 0.05,
+            iof_overall: 0.0038,
+            iof_percentage: 0.03,
+            interest_rate: 0.0235,
+            max_total_amount: f64::MAX,
+            rounding_policy: RoundingPolicy {
+                mode: RoundingMode::Truncate,
+                residual_allocation: ResidualAllocation::None,
+            },
+            ..Default::default()
+        };
+
+        // The IOF and the amounts are truncated to cents and the rates as on the Iterative provider
+        let resp = SIMPLE.calculate_payment_plan(params).unwrap();
+        let response = &resp[23];
+        assert_eq!(response.total_iof, 237.31);
+        assert_eq!(response.installment_amount, 499.19);
+        assert_eq!(response.total_amount, 11980.56);
+        assert_eq!(response.debit_service, 2943.25);
+        assert_eq!(response.customer_amount, 499.19);
+        assert_eq!(response.rounding_residual, 0.0);
+        assert_eq!(response.eir_monthly, 0.024);
+        assert_eq!(response.eir_yearly, 0.330524);
+        assert_eq!(response.tec_monthly, 0.0258);
+
+        // The residual of the truncated total is added to the first installment
+        let params = Params {
+            rounding_policy: RoundingPolicy {
+                mode: RoundingMode::Truncate,
+                residual_allocation: ResidualAllocation::FirstInstallment,
+            },
+            ..params
+        };
+        let resp = SIMPLE.calculate_payment_plan(params).unwrap();
+        let response = &resp[23];
+        assert_eq!(response.installment_amount, 499.38);
+        assert_eq!(response.total_amount, 11980.75);
+        assert_eq!(response.rounding_residual, 0.19);
+    }
+
+    #[test]
+    fn test_calculate_selected_payment_plans() {
+        let params = Params {
+            disbursement_only_on_business_days: false,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 80.0,
+            requested_amount: 44.14,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2022, 6, 20).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2022, 5, 20).unwrap(),
+            installments: 48,
+            debit_service_percentage: 0,
+            mdr: 0.01,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,
+            iof_percentage: 0.03,
+            interest_rate: 0.0355,
+            ..Default::default()
+        };
+
+        let plans = SIMPLE.calculate_payment_plan(params).unwrap();
+        let resp = SIMPLE
+            .calculate_selected_payment_plans(params, &[2, 1, 2])
+            .unwrap();
+
+        // The single installment is under the minimum too, but it is not bound by it
+        assert_eq!(resp.plans, plans);
+        assert_eq!(
+            resp.excluded,
+            vec![ExcludedInstallment {
+                installment: 2,
+                reason: ExclusionReason::MinInstallmentAmount,
+            }]
+        );
+    }
+
     #[test]
     fn test_go_case() {
         let expected_contract_amount = 2781.4664277614843;
@@ -1670,6 +1820,7 @@ mod test {
             iof_overall: 0.003800000064074993,
             iof_percentage: 0.029999999329447746,
             interest_rate: 0.029999999329447746,
+            ..Default::default()
         };
 
         let result = SIMPLE.calculate_payment_plan(params).unwrap();
@@ -1717,7 +1868,11 @@ mod test {
 
 #[cfg(test)]
 mod down_payment_test {
-    use crate::{calc::PaymentPlan, DownPaymentParams, Params};
+    use crate::{
+        calc::PaymentPlan, AmortizationSystem, BusinessDayConvention, DownPaymentParams,
+        DueDayPolicy, GracePeriod, IofConvergence, Params, Periodicity, ResidualAllocation,
+        RoundingMode, RoundingPolicy,
+    };
 
     const SIMPLE: super::Simple = super::Simple {};
 
@@ -1736,8 +1891,55 @@ mod down_payment_test {
         iof_overall: 0.0038,
         iof_percentage: 0.03,
         interest_rate: 0.0355,
+        amortization_system: AmortizationSystem::Price,
+        grace_period: GracePeriod::Capitalized,
+        balloon_percentage: 0.0,
+        periodicity: Periodicity::Monthly,
+        due_day_policy: DueDayPolicy::Stepped,
+        rounding_policy: RoundingPolicy {
+            mode: RoundingMode::HalfUp,
+            residual_allocation: ResidualAllocation::None,
+        },
+        business_day_convention: BusinessDayConvention::Following,
+        iof_convergence: IofConvergence {
+            tolerance: 0.02,
+            max_iterations: 10,
+        },
     };
 
+    #[test]
+    fn test_31st_first_payment_date() {
+        let params = DownPaymentParams {
+            params: Params {
+                installments: 3,
+                ..PLAN_PARAM
+            },
+            requested_amount: 200.0,
+            min_installment_amount: 100.0,
+            installments: 2,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2021, 12, 31).unwrap(),
+        };
+
+        let result = SIMPLE.calculate_down_payment_plan(params).unwrap();
+
+        // The plans keep the day lost on February, as the legacy plans
+        let due_dates: Vec<_> = result[0].plans.iter().map(|plan| plan.due_date).collect();
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 3, 28).unwrap(),
+        ];
+        assert_eq!(due_dates, expected);
+
+        let due_dates: Vec<_> = result[1].plans.iter().map(|plan| plan.due_date).collect();
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 3, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 4, 28).unwrap(),
+        ];
+        assert_eq!(due_dates, expected);
+    }
+
     #[test]
     fn test_1_installment() {
         let down_payment = 65.0;
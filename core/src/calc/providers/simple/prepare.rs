@@ -1,5 +1,9 @@
-use crate::{util::add_months, Invoice, Params};
+use crate::{
+    util::{add_due_periods, first_due_date},
+    Invoice, Params,
+};
 
+/// The due date and the discount factors of an installment, see `prepare_calculation`.
 #[derive(Debug, Clone, Copy)]
 pub struct PreparedCalculation {
     pub installment: u32,
@@ -10,10 +14,19 @@ pub struct PreparedCalculation {
     pub invoice: Invoice,
 }
 
+/*
+    The installment factors of the plans with 1 to `params.installments` installments:
+    the factor of each due date is 1 / (1 + interest_rate) ^ (days / 30) from the disbursement date,
+    and the accumulated factor divides the contract amount into the installment amount of the plan.
+*/
 pub fn prepare_calculation(params: Params) -> Vec<PreparedCalculation> {
     let disbursement_date = params.disbursement_date;
     let mut prepared_calculations: Vec<PreparedCalculation> = Vec::new();
-    let first_payment_date = params.first_payment_date;
+    let first_payment_date = first_due_date(
+        params.first_payment_date,
+        params.periodicity,
+        params.due_day_policy,
+    );
     let installments = params.installments;
     let interest_rate = params.interest_rate;
 
@@ -23,7 +36,12 @@ pub fn prepare_calculation(params: Params) -> Vec<PreparedCalculation> {
 
     for i in 0..installments {
         if i != 0 {
-            due_date = add_months(due_date, 1);
+            due_date = add_due_periods(
+                first_payment_date,
+                i,
+                params.periodicity,
+                params.due_day_policy,
+            );
         }
 
         let accumulated_days = due_date.signed_duration_since(disbursement_date).num_days();
@@ -40,7 +58,9 @@ pub fn prepare_calculation(params: Params) -> Vec<PreparedCalculation> {
             accumulated_factor: accumulated_days_index,
             debit_service: 0.0,
             main_iof_tac: 0.0,
+            amount: 0.0,
             due_date,
+            ..Default::default()
         };
 
         prepared_calculations.push(PreparedCalculation {
@@ -70,7 +90,7 @@ mod test {
     Test 7 - (2900 / 6) = (3314.5935321072 / 552.4322553512001)
      */
 
-    use crate::{calc::providers::simple::prepare::prepare_calculation, Params};
+    use crate::{calc::providers::simple::prepare::prepare_calculation, DueDayPolicy, Params};
 
     #[test]
     fn test_prepare_calculus_test_0() {
@@ -88,6 +108,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0235,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -141,6 +162,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.025,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -194,6 +216,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0235,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -247,6 +270,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.024,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -300,6 +324,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0235,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -353,6 +378,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.0349,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -406,6 +432,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.025,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -460,6 +487,7 @@ mod test {
             iof_overall: 0.0038,
             iof_percentage: 0.03,
             interest_rate: 0.035,
+            ..Default::default()
         };
 
         let prepared_calculations = prepare_calculation(params);
@@ -496,4 +524,48 @@ mod test {
         assert!(prepared_calculations[5].days_index == 0.8097777779226664); // 0.8097777779226665 is the expected value,see later
         assert!(prepared_calculations[5].accumulated_days_index == 5.315698992965537);
     }
+
+    #[test]
+    fn test_prepare_calculus_due_day_anchored() {
+        let params = Params {
+            disbursement_only_on_business_days: false,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 0.0,
+            requested_amount: 2900.0,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2022, 1, 3).unwrap(),
+            installments: 3,
+            debit_service_percentage: 0,
+            mdr: 0.029900000000000003,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,
+            iof_percentage: 0.03,
+            interest_rate: 0.035,
+            ..Default::default()
+        };
+
+        // The default keeps the day lost on February, as the legacy plans
+        let prepared_calculations = prepare_calculation(params);
+        let due_dates: Vec<_> = prepared_calculations.iter().map(|c| c.due_date).collect();
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 3, 28).unwrap(),
+        ];
+        assert!(due_dates == expected);
+
+        // Back to the 31st after February, as the Iterative provider
+        let params = Params {
+            due_day_policy: DueDayPolicy::Anchored,
+            ..params
+        };
+        let prepared_calculations = prepare_calculation(params);
+        let due_dates: Vec<_> = prepared_calculations.iter().map(|c| c.due_date).collect();
+        let expected = vec![
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(),
+        ];
+        assert!(due_dates == expected);
+    }
 }
@@ -0,0 +1,70 @@
+use crate::{err::PaymentPlanError, ExcludedInstallment, ExclusionReason, Params, SelectedPlans};
+
+use super::PaymentPlan;
+
+// The selected installments sorted and without duplicates, at least one and none of them 0
+pub(crate) fn selected_installments(installments: &[u32]) -> Result<Vec<u32>, PaymentPlanError> {
+    if installments.is_empty() || installments.contains(&0) {
+        return Err(PaymentPlanError::InvalidNumberOfInstallments);
+    }
+
+    let mut installments = installments.to_vec();
+    installments.sort_unstable();
+    installments.dedup();
+    Ok(installments)
+}
+
+// The limit of `params` a plan is out of, the minimum installment amount is checked on its smallest installment
+pub(crate) fn exclusion_reason(
+    params: &Params,
+    smallest_installment_amount: f64,
+    total_amount: f64,
+) -> Option<ExclusionReason> {
+    if smallest_installment_amount < params.min_installment_amount {
+        return Some(ExclusionReason::MinInstallmentAmount);
+    }
+    if total_amount > params.max_total_amount {
+        return Some(ExclusionReason::MaxTotalAmount);
+    }
+    None
+}
+
+/*
+    Picks the plans of the selected installments from the plans of `provider` up to the largest one,
+    calculated without the limits of `params`, which are then checked on each plan.
+    The minimum installment amount is checked on the plans from `bound_from` installments on.
+*/
+pub(crate) fn pick_selected_payment_plans<P: PaymentPlan + ?Sized>(
+    provider: &P,
+    mut params: Params,
+    installments: &[u32],
+    bound_from: u32,
+) -> Result<SelectedPlans, PaymentPlanError> {
+    let installments = selected_installments(installments)?;
+    let limits = params;
+
+    params.installments = installments[installments.len() - 1];
+    params.min_installment_amount = 0.0;
+    params.max_total_amount = f64::MAX;
+    let plans = provider.calculate_payment_plan(params)?;
+
+    let mut selected = SelectedPlans::default();
+    for plan in plans
+        .into_iter()
+        .filter(|plan| installments.contains(&plan.installment))
+    {
+        let smallest_installment_amount = if plan.installment >= bound_from {
+            plan.installment_amount
+        } else {
+            f64::INFINITY
+        };
+        match exclusion_reason(&limits, smallest_installment_amount, plan.total_amount) {
+            Some(reason) => selected.excluded.push(ExcludedInstallment {
+                installment: plan.installment,
+                reason,
+            }),
+            None => selected.plans.push(plan),
+        }
+    }
+    Ok(selected)
+}
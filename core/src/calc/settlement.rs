@@ -0,0 +1,222 @@
+use chrono::NaiveDate;
+
+use crate::{
+    calendar::HolidayCalendar,
+    err::PaymentPlanError,
+    util::{diff_in_business_days, round_decimal_cases},
+    EarlySettlementInvoice, EarlySettlementParams, EarlySettlementResponse,
+};
+
+/*
+    Early settlement (quitação antecipada) of the open invoices of a contract.
+
+    Following the CDC (art. 52, §2º) and the CMN Resolution 3.516, the customer is entitled to a
+    proportional reduction of the interest when paying in advance, so every invoice due after the
+    settlement date is discounted at the contract rate for the days left until its due date.
+    Invoices already due on the settlement date are charged at face value.
+
+    The discount uses the same basis as the plan: the monthly rate is compounded to a yearly one
+    and spread over 252 days, counting the business days of `calendar` when the plan was calculated on business days.
+*/
+pub fn calculate_early_settlement_with_calendar(
+    params: &EarlySettlementParams,
+    calendar: &dyn HolidayCalendar,
+) -> Result<EarlySettlementResponse, PaymentPlanError> {
+    if params.interest_rate < 0.0 {
+        return Err(PaymentPlanError::InvalidInterestRate);
+    }
+
+    let settlement_date = params.settlement_date;
+
+    let mut invoices = Vec::with_capacity(params.invoices.len());
+    let mut total_amount = 0.0;
+    let mut settlement_amount = 0.0;
+
+    for invoice in &params.invoices {
+        let due_date = invoice.due_date;
+        let amount = invoice.amount;
+
+        let days = days_until(
+            settlement_date,
+            due_date,
+            params.disbursement_only_on_business_days,
+            calendar,
+        );
+
+        let factor = discount_factor(params.interest_rate, days);
+        let present_value = round_decimal_cases(amount * factor, 2);
+        let discount = round_decimal_cases(amount - present_value, 2);
+
+        total_amount += amount;
+        settlement_amount += present_value;
+
+        invoices.push(EarlySettlementInvoice {
+            due_date,
+            amount,
+            days,
+            present_value,
+            discount,
+        });
+    }
+
+    let total_amount = round_decimal_cases(total_amount, 2);
+    let settlement_amount = round_decimal_cases(settlement_amount, 2);
+    let discount = round_decimal_cases(total_amount - settlement_amount, 2);
+
+    Ok(EarlySettlementResponse {
+        settlement_date,
+        total_amount,
+        settlement_amount,
+        discount,
+        invoices,
+    })
+}
+
+/// Days from `date` until `due_date` (business days of `calendar`), 0 when the due date is not after it.
+pub(crate) fn days_until(
+    date: NaiveDate,
+    due_date: NaiveDate,
+    disbursement_only_on_business_days: bool,
+    calendar: &dyn HolidayCalendar,
+) -> i64 {
+    if due_date <= date {
+        0
+    } else if disbursement_only_on_business_days {
+        diff_in_business_days(date, due_date, calendar)
+    } else {
+        due_date.signed_duration_since(date).num_days()
+    }
+}
+
+/// The factor that brings an amount `days` ahead to its present value at the monthly `interest_rate`.
+pub(crate) fn discount_factor(interest_rate: f64, days: i64) -> f64 {
+    let annual_interest_rate = (1.0 + interest_rate).powf(12.0);
+    1.0 / annual_interest_rate.powf(days as f64 / 252.0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        calc::{
+            providers::{iterative::Iterative, simple::Simple},
+            PaymentPlan,
+        },
+        calendar::{BrazilianBankHolidays, HolidayList},
+        err::PaymentPlanError,
+        EarlySettlementParams, Invoice, Params,
+    };
+
+    use super::calculate_early_settlement_with_calendar;
+
+    fn invoice(due_date: chrono::NaiveDate, amount: f64) -> Invoice {
+        Invoice {
+            amount,
+            due_date,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calculate_early_settlement() {
+        let params = EarlySettlementParams {
+            invoices: vec![
+                invoice(chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(), 500.0),
+                invoice(
+                    chrono::NaiveDate::from_ymd_opt(2025, 10, 20).unwrap(),
+                    500.0,
+                ),
+                invoice(
+                    chrono::NaiveDate::from_ymd_opt(2025, 11, 18).unwrap(),
+                    500.0,
+                ),
+            ],
+            interest_rate: 0.0449,
+            settlement_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_only_on_business_days: true,
+        };
+
+        let resp =
+            calculate_early_settlement_with_calendar(&params, &BrazilianBankHolidays).unwrap();
+
+        // The first invoice is due on the settlement date, so it is not discounted
+        assert_eq!(resp.invoices[0].days, 0);
+        assert_eq!(resp.invoices[0].present_value, 500.0);
+        assert_eq!(resp.invoices[0].discount, 0.0);
+
+        assert_eq!(resp.invoices[1].days, 22);
+        assert_eq!(resp.invoices[1].present_value, 477.51);
+        assert_eq!(resp.invoices[2].days, 43);
+        assert_eq!(resp.invoices[2].present_value, 457.0);
+
+        assert_eq!(resp.total_amount, 1500.0);
+        assert_eq!(resp.settlement_amount, 1434.51);
+        assert_eq!(resp.discount, 65.49);
+
+        // A bank closure of the partner calendar is not counted
+        let calendar = HolidayList::from_text("2025-10-01")
+            .unwrap()
+            .on_top_of(BrazilianBankHolidays);
+        let resp = calculate_early_settlement_with_calendar(&params, &calendar).unwrap();
+        assert_eq!(resp.invoices[1].days, 21);
+        assert_eq!(resp.invoices[2].days, 42);
+    }
+
+    #[test]
+    fn test_calculate_early_settlement_on_disbursement_date() {
+        let disbursement_date = chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap();
+
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date,
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let plan = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let settlement = EarlySettlementParams::from_plan(&plan, disbursement_date, true).unwrap();
+        let resp =
+            calculate_early_settlement_with_calendar(&settlement, &BrazilianBankHolidays).unwrap();
+
+        // Paying everything on the disbursement date costs the financed amount
+        assert!((resp.settlement_amount - plan.contract_amount).abs() < 0.1);
+        assert_eq!(resp.total_amount, plan.total_amount);
+
+        // The Simple plans have no invoice amounts to discount
+        let plan = Simple
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(
+            EarlySettlementParams::from_plan(&plan, disbursement_date, true).unwrap_err(),
+            PaymentPlanError::InvalidPlanInvoices
+        );
+    }
+
+    #[test]
+    fn test_calculate_early_settlement_invalid_interest_rate() {
+        let params = EarlySettlementParams {
+            interest_rate: -0.01,
+            ..Default::default()
+        };
+
+        let resp = calculate_early_settlement_with_calendar(&params, &BrazilianBankHolidays);
+
+        assert_eq!(resp, Err(PaymentPlanError::InvalidInterestRate));
+    }
+}
@@ -0,0 +1,413 @@
+use xirr::Payment;
+
+use crate::{
+    calc::{
+        inner_xirr::tec::calculate_tec_monthly,
+        providers::iterative::{Iterative, CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE},
+        PaymentPlan,
+    },
+    err::PaymentPlanError,
+    util::round_decimal_cases,
+    InterestRateTarget, Params, RequestedAmountResponse, Response,
+};
+
+const MAX_ITERATIONS: u32 = 20;
+const TOLERANCE: f64 = 0.01; // One cent
+
+const INTEREST_RATE_STEP: f64 = 0.000001; // 0.0001%
+const MAX_INTEREST_RATE_STEPS: u32 = 1_000_000; // 100% a.m.
+
+/*
+    Inverse of the payment plan: finds the requested amount whose plan with `params.installments`
+    invoices has the target installment amount.
+
+    Every value of the plan (interest, IOF, TAC and debit service) grows in proportion to the requested amount,
+    so the installment amount is close to linear on it and the requested amount is corrected by the ratio
+    between the target and the installment found until they are within a cent.
+    The requested amount is kept in cents, so when the correction stops changing it the best amount was found.
+
+    It always uses the Iterative provider, the plan returned with the requested amount found is the same `calculate_payment_plan` would return
+    for that requested amount. The minimum installment and maximum total amounts of the params are not considered.
+*/
+pub fn calculate_requested_amount(
+    mut params: Params,
+    installment_amount: f64,
+) -> Result<RequestedAmountResponse, PaymentPlanError> {
+    if installment_amount <= 0.0 {
+        return Err(PaymentPlanError::InvalidInstallmentAmount);
+    }
+    if params.installments == 0 {
+        return Err(PaymentPlanError::InvalidNumberOfInstallments);
+    }
+
+    params.min_installment_amount = 0.0;
+    params.max_total_amount = f64::MAX;
+
+    // First guess: the sum of the installments, which is always above the amount financed
+    params.requested_amount =
+        round_decimal_cases(installment_amount * params.installments as f64, 2);
+
+    for _ in 0..MAX_ITERATIONS {
+        let plan = calculate_plan(params)?;
+
+        let diff = plan.installment_amount - installment_amount;
+        if diff.abs() <= TOLERANCE {
+            return Ok(RequestedAmountResponse {
+                requested_amount: params.requested_amount,
+                plan,
+            });
+        }
+
+        let ratio = installment_amount / plan.installment_amount;
+        let requested_amount = round_decimal_cases(params.requested_amount * ratio, 2);
+        if requested_amount == params.requested_amount {
+            break;
+        }
+        params.requested_amount = requested_amount;
+    }
+
+    Err(PaymentPlanError::UnreachableInstallmentAmount)
+}
+
+/*
+    Inverse of the payment plan on the interest rate: finds the monthly interest rate of the plan with
+    `params.requested_amount` and `params.installments` invoices that reaches the target:
+        - InstallmentAmount: the rate whose installment amount is the closest to the target, within a cent.
+        - MaxTecMonthly/MaxTecYearly: the highest rate whose TEC does not go over the cap.
+
+    The installment amount and the TEC only grow with the interest rate, so the rate is found with a bisection
+    between 0% and 100% a.m., in steps of 0.0001%.
+    The TEC is the one from `inner_xirr::tec` over the invoices of the plan, before it is rounded on the response.
+
+    The target is unreachable when it is out of the values of this range, for example when the IOF and the TAC alone
+    already put the TEC over the cap, or when the installment amount without interest is already above the target.
+*/
+pub fn calculate_interest_rate(
+    mut params: Params,
+    target: InterestRateTarget,
+) -> Result<Response, PaymentPlanError> {
+    if params.requested_amount <= 0.0 {
+        return Err(PaymentPlanError::InvalidRequestedAmount);
+    }
+    if params.installments == 0 {
+        return Err(PaymentPlanError::InvalidNumberOfInstallments);
+    }
+
+    params.min_installment_amount = 0.0;
+    params.max_total_amount = f64::MAX;
+
+    match target {
+        InterestRateTarget::InstallmentAmount(installment_amount) => {
+            if installment_amount <= 0.0 {
+                return Err(PaymentPlanError::InvalidInstallmentAmount);
+            }
+            solve_installment_amount(params, installment_amount)
+        }
+        InterestRateTarget::MaxTecMonthly(tec_monthly) => solve_max_tec(params, tec_monthly),
+        InterestRateTarget::MaxTecYearly(tec_yearly) => {
+            let tec_monthly =
+                (1.0 + tec_yearly).powf(CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE) - 1.0;
+            solve_max_tec(params, tec_monthly)
+        }
+    }
+}
+
+fn solve_installment_amount(
+    params: Params,
+    installment_amount: f64,
+) -> Result<Response, PaymentPlanError> {
+    // The first step whose installment amount is not below the target
+    let plan_at = |step: u32| calculate_plan(with_interest_rate(params, step));
+    let step = bisect(|step| Ok(plan_at(step)?.installment_amount >= installment_amount))?;
+
+    let Some(step) = step else {
+        return Err(PaymentPlanError::UnreachableInstallmentAmount);
+    };
+
+    let plan = plan_at(step)?;
+    let plan = if step > 0 {
+        let previous = plan_at(step - 1)?;
+        let diff = plan.installment_amount - installment_amount;
+        let previous_diff = installment_amount - previous.installment_amount;
+        if previous_diff < diff {
+            previous
+        } else {
+            plan
+        }
+    } else {
+        plan
+    };
+
+    if (plan.installment_amount - installment_amount).abs() > TOLERANCE {
+        return Err(PaymentPlanError::UnreachableInstallmentAmount);
+    }
+
+    Ok(plan)
+}
+
+fn solve_max_tec(params: Params, max_tec_monthly: f64) -> Result<Response, PaymentPlanError> {
+    // The first step whose TEC goes over the cap
+    let step = bisect(|step| {
+        let params = with_interest_rate(params, step);
+        let plan = calculate_plan(params)?;
+        Ok(tec_monthly(params, &plan)? > max_tec_monthly)
+    })?;
+
+    let step = match step {
+        Some(0) => return Err(PaymentPlanError::UnreachableTec),
+        Some(step) => step - 1,
+        None => MAX_INTEREST_RATE_STEPS,
+    };
+
+    calculate_plan(with_interest_rate(params, step))
+}
+
+/*
+    Binary search for the first step of the interest rate where `predicate` holds,
+    `predicate` must be false up to some step and true from it on.
+    Returns None when it does not hold up to the max interest rate.
+*/
+fn bisect<F>(mut predicate: F) -> Result<Option<u32>, PaymentPlanError>
+where
+    F: FnMut(u32) -> Result<bool, PaymentPlanError>,
+{
+    if !predicate(MAX_INTEREST_RATE_STEPS)? {
+        return Ok(None);
+    }
+
+    let mut low = 0;
+    let mut high = MAX_INTEREST_RATE_STEPS;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if predicate(mid)? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    Ok(Some(low))
+}
+
+fn with_interest_rate(mut params: Params, step: u32) -> Params {
+    params.interest_rate = round_decimal_cases(step as f64 * INTEREST_RATE_STEP, 6);
+    params
+}
+
+fn tec_monthly(mut params: Params, plan: &Response) -> Result<f64, PaymentPlanError> {
+    params.disbursement_date = plan.disbursement_date;
+
+    let tec_params = plan
+        .invoices
+        .iter()
+        .map(|invoice| Payment {
+            amount: -invoice.amount,
+            date: invoice.due_date,
+        })
+        .collect();
+
+    calculate_tec_monthly(
+        params,
+        tec_params,
+        CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+    )
+}
+
+// Only the plan of `params.installments` is calculated, the installment excluded by the limits has no plan
+fn calculate_plan(params: Params) -> Result<Response, PaymentPlanError> {
+    Iterative
+        .calculate_selected_payment_plans(params, &[params.installments])?
+        .plans
+        .pop()
+        .ok_or(PaymentPlanError::InvalidNumberOfInstallments)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{err::PaymentPlanError, InterestRateTarget, Params};
+
+    use super::{calculate_interest_rate, calculate_requested_amount};
+
+    #[test]
+    fn test_calculate_requested_amount() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 0.0,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let resp = calculate_requested_amount(params, 350.0).unwrap();
+
+        assert_eq!(resp.plan.installment, 12);
+        assert_eq!(resp.plan.installment_amount, 350.0);
+        assert_eq!(resp.requested_amount, 3137.81);
+    }
+
+    #[test]
+    fn test_calculate_requested_amount_round_trip() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+        let plan = super::calculate_plan(params).unwrap();
+
+        let resp = calculate_requested_amount(params, plan.installment_amount).unwrap();
+
+        assert_eq!(resp.plan.installment_amount, plan.installment_amount);
+        assert!((resp.requested_amount - 3883.48).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_calculate_requested_amount_invalid_installment_amount() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 0.0,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let resp = calculate_requested_amount(params, 0.0);
+
+        assert_eq!(resp, Err(PaymentPlanError::InvalidInstallmentAmount));
+    }
+
+    #[test]
+    fn test_calculate_interest_rate_installment_amount() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let resp =
+            calculate_interest_rate(params, InterestRateTarget::InstallmentAmount(433.18)).unwrap();
+
+        assert_eq!(resp.installment_amount, 433.18);
+        assert!((resp.interest_rate - 0.0449).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_calculate_interest_rate_max_tec() {
+        let mut params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        let resp =
+            calculate_interest_rate(params, InterestRateTarget::MaxTecMonthly(0.05)).unwrap();
+
+        assert_eq!(resp.interest_rate, 0.046281);
+        assert!(resp.tec_monthly <= 0.05);
+
+        // A step above the rate found goes over the cap
+        params.interest_rate = 0.046282;
+        let plan = super::calculate_plan(params).unwrap();
+        assert!(plan.tec_monthly >= 0.05);
+    }
+
+    #[test]
+    fn test_calculate_interest_rate_unreachable_tec() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        // The IOF alone costs more than 0.1% a.m.
+        let resp = calculate_interest_rate(params, InterestRateTarget::MaxTecMonthly(0.001));
+
+        assert_eq!(resp, Err(PaymentPlanError::UnreachableTec));
+    }
+
+    #[test]
+    fn test_calculate_interest_rate_unreachable_installment_amount() {
+        let params = Params {
+            disbursement_only_on_business_days: true,
+            max_total_amount: f64::MAX,
+            min_installment_amount: 100.0,
+            requested_amount: 3883.48,
+            first_payment_date: chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap(),
+            disbursement_date: chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap(),
+            installments: 12,
+            debit_service_percentage: 0,
+            mdr: 0.05,
+            tac_percentage: 0.0,
+            iof_overall: 0.0038,      // %0.38
+            iof_percentage: 0.000082, // 0.0082%
+            interest_rate: 0.0449,
+            ..Default::default()
+        };
+
+        // Less than the requested amount split over the installments
+        let resp = calculate_interest_rate(params, InterestRateTarget::InstallmentAmount(300.0));
+
+        assert_eq!(resp, Err(PaymentPlanError::UnreachableInstallmentAmount));
+    }
+}
@@ -0,0 +1,244 @@
+use chrono::Days;
+
+use crate::util;
+
+use super::HolidayCalendar;
+
+/*
+    The business days of a calendar from `from` to `until` (both inclusive), precomputed once so the
+    business day arithmetic on the dates it covers is constant or logarithmic time:
+        - `cumulative[i]` is the number of business days from `from` to the i-th day after it (inclusive),
+            so the business days between two dates is a subtraction.
+        - The non business days (weekends and holidays) are kept sorted to be listed by a binary search.
+    The dates out of the range return None, and the callers fall back to walking the calendar day by day.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessDayIndex {
+    from: chrono::NaiveDate,
+    cumulative: Vec<u32>,
+    non_business_days: Vec<chrono::NaiveDate>,
+}
+
+impl BusinessDayIndex {
+    pub fn new(
+        calendar: &dyn HolidayCalendar,
+        from: chrono::NaiveDate,
+        until: chrono::NaiveDate,
+    ) -> Self {
+        let days = until.signed_duration_since(from).num_days().max(-1) + 1;
+        let mut cumulative = Vec::with_capacity(days as usize);
+        let mut non_business_days = Vec::new();
+
+        let mut business_days = 0;
+        for date in from.iter_days().take(days as usize) {
+            if util::is_business_day(date, calendar) {
+                business_days += 1;
+            } else {
+                non_business_days.push(date);
+            }
+            cumulative.push(business_days);
+        }
+
+        BusinessDayIndex {
+            from,
+            cumulative,
+            non_business_days,
+        }
+    }
+
+    pub fn from(&self) -> chrono::NaiveDate {
+        self.from
+    }
+
+    pub fn until(&self) -> chrono::NaiveDate {
+        self.from + Days::new(self.cumulative.len() as u64) - Days::new(1)
+    }
+
+    fn position(&self, date: chrono::NaiveDate) -> Option<usize> {
+        let days = date.signed_duration_since(self.from).num_days();
+        if days < 0 || days as usize >= self.cumulative.len() {
+            return None;
+        }
+        Some(days as usize)
+    }
+
+    pub fn is_business_day(&self, date: chrono::NaiveDate) -> Option<bool> {
+        let i = self.position(date)?;
+        let before = if i == 0 { 0 } else { self.cumulative[i - 1] };
+        Some(self.cumulative[i] > before)
+    }
+
+    /// The business days after `start_date` up to `end_date` (inclusive), negative when `end_date` is before it.
+    pub fn business_days_between(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Option<i64> {
+        let start = self.cumulative[self.position(start_date)?] as i64;
+        let end = self.cumulative[self.position(end_date)?] as i64;
+        Some(end - start)
+    }
+
+    /// The `days`-th business day after `date` (before it when negative), `date` itself for 0.
+    pub fn add_business_days(
+        &self,
+        date: chrono::NaiveDate,
+        days: i64,
+    ) -> Option<chrono::NaiveDate> {
+        if days == 0 {
+            return Some(date);
+        }
+        let i = self.position(date)?;
+        let target = if days > 0 {
+            self.cumulative[i] as i64 + days
+        } else {
+            // Counted back from the day before, as `date` is not one of them
+            let before = if i == 0 { 0 } else { self.cumulative[i - 1] };
+            before as i64 + days + 1
+        };
+        if target <= 0 {
+            return None;
+        }
+
+        // The first day that reaches the count is the business day that made it
+        let i = self
+            .cumulative
+            .partition_point(|count| (*count as i64) < target);
+        if i == self.cumulative.len() {
+            return None;
+        }
+        Some(self.from + Days::new(i as u64))
+    }
+
+    /// The non business days from `start_date` to `end_date` (inclusive).
+    pub fn non_business_days_between(
+        &self,
+        start_date: chrono::NaiveDate,
+        end_date: chrono::NaiveDate,
+    ) -> Option<&[chrono::NaiveDate]> {
+        self.position(start_date)?;
+        self.position(end_date)?;
+        if end_date < start_date {
+            return Some(&[]);
+        }
+
+        let start = self
+            .non_business_days
+            .partition_point(|date| *date < start_date);
+        let end = self
+            .non_business_days
+            .partition_point(|date| *date <= end_date);
+        Some(&self.non_business_days[start..end])
+    }
+}
+
+/*
+    A calendar with the business days indexed from `from` to `until`, for the custom calendars
+    used on many calculations (see `BusinessDayIndex`).
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedCalendar<C> {
+    calendar: C,
+    index: BusinessDayIndex,
+}
+
+impl<C: HolidayCalendar> IndexedCalendar<C> {
+    pub fn new(calendar: C, from: chrono::NaiveDate, until: chrono::NaiveDate) -> Self {
+        let index = BusinessDayIndex::new(&calendar, from, until);
+        IndexedCalendar { calendar, index }
+    }
+
+    pub fn calendar(&self) -> &C {
+        &self.calendar
+    }
+}
+
+impl<C: HolidayCalendar> HolidayCalendar for IndexedCalendar<C> {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        self.calendar.is_holiday(date)
+    }
+
+    fn business_day_index(&self) -> Option<&BusinessDayIndex> {
+        Some(&self.index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        calendar::{BrazilianBankHolidays, HolidayCalendar, HolidayList},
+        util,
+    };
+
+    use super::{BusinessDayIndex, IndexedCalendar};
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_business_day_index() {
+        let index =
+            BusinessDayIndex::new(&BrazilianBankHolidays, date(2026, 1, 1), date(2026, 12, 31));
+
+        assert_eq!(index.until(), date(2026, 12, 31));
+        assert_eq!(index.is_business_day(date(2026, 2, 16)), Some(false)); // Carnival
+        assert_eq!(index.is_business_day(date(2026, 2, 18)), Some(true));
+        assert_eq!(index.is_business_day(date(2027, 1, 4)), None);
+
+        // Friday to the Wednesday after Carnival
+        let friday = date(2026, 2, 13);
+        let wednesday = date(2026, 2, 18);
+        assert_eq!(index.business_days_between(friday, wednesday), Some(1));
+        assert_eq!(index.business_days_between(wednesday, friday), Some(-1));
+        assert_eq!(index.add_business_days(friday, 1), Some(wednesday));
+        assert_eq!(index.add_business_days(wednesday, -1), Some(friday));
+        assert_eq!(
+            index.add_business_days(date(2026, 2, 14), 0),
+            Some(date(2026, 2, 14))
+        );
+        assert_eq!(index.add_business_days(date(2026, 2, 14), -1), Some(friday));
+        assert_eq!(index.add_business_days(date(2026, 12, 30), 5), None);
+
+        assert_eq!(
+            index.non_business_days_between(friday, wednesday),
+            Some(
+                &[
+                    date(2026, 2, 14),
+                    date(2026, 2, 15),
+                    date(2026, 2, 16),
+                    date(2026, 2, 17),
+                ][..]
+            )
+        );
+    }
+
+    #[test]
+    fn test_business_day_index_matches_the_walk() {
+        let calendar = IndexedCalendar::new(
+            HolidayList::new(vec![date(2026, 7, 9)]).on_top_of(BrazilianBankHolidays),
+            date(2026, 1, 1),
+            date(2027, 12, 31),
+        );
+        let index = calendar.business_day_index().unwrap();
+
+        let start_date = date(2026, 1, 1);
+        for end_date in start_date.iter_days().take(500) {
+            let mut walked = 0;
+            let mut day = start_date;
+            while day < end_date {
+                day = day.succ_opt().unwrap();
+                if util::is_business_day(day, &calendar) {
+                    walked += 1;
+                }
+            }
+            assert_eq!(
+                index.business_days_between(start_date, end_date),
+                Some(walked)
+            );
+            if walked > 0 && util::is_business_day(end_date, &calendar) {
+                assert_eq!(index.add_business_days(start_date, walked), Some(end_date));
+            }
+        }
+    }
+}
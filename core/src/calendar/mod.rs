@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{err::PaymentPlanError, util};
+
+mod index;
+mod rules;
+
+pub use index::{BusinessDayIndex, IndexedCalendar};
+pub use rules::{HolidayDate, HolidayRule, RuleCalendar};
+
+/*
+    The holidays used to find the business days (the weekends are never business days).
+    The plan calculation, the disbursement dates and the business day counts take a calendar,
+    and `BrazilianBankHolidays` is the one used when none is given.
+    A calendar with a `BusinessDayIndex` has the business day arithmetic of the dates it covers
+    in constant time, instead of walking one day at a time.
+*/
+pub trait HolidayCalendar {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool;
+
+    fn business_day_index(&self) -> Option<&BusinessDayIndex> {
+        None
+    }
+}
+
+/// The Brazilian national and bank holidays, from the table and calculated by `RuleCalendar::brazilian` after it.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BrazilianBankHolidays;
+
+impl HolidayCalendar for BrazilianBankHolidays {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        util::is_holiday(date)
+    }
+
+    fn business_day_index(&self) -> Option<&BusinessDayIndex> {
+        Some(util::brazilian_business_day_index())
+    }
+}
+
+/*
+    A list of holiday dates, for municipal holidays, bank strikes or any other closure.
+    It can be loaded from a text with one date (YYYY-MM-DD) per line or from a JSON list of dates,
+    and combined with another calendar with `on_top_of`.
+*/
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "Vec<chrono::NaiveDate>", into = "Vec<chrono::NaiveDate>")]
+pub struct HolidayList {
+    dates: Vec<chrono::NaiveDate>, // Sorted and without duplicates
+}
+
+impl HolidayList {
+    pub fn new(mut dates: Vec<chrono::NaiveDate>) -> Self {
+        dates.sort_unstable();
+        dates.dedup();
+        HolidayList { dates }
+    }
+
+    /*
+        One date per line, the blank lines and the comments (from `#` to the end of the line) are skipped:
+            # São Paulo
+            2025-01-25
+            2025-07-09
+    */
+    pub fn from_text(text: &str) -> Result<Self, PaymentPlanError> {
+        let mut dates = Vec::new();
+        for line in text.lines() {
+            let line = match line.split_once('#') {
+                Some((date, _)) => date,
+                None => line,
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let date = parse_date(line)?;
+            dates.push(date);
+        }
+        Ok(HolidayList::new(dates))
+    }
+
+    /// A JSON list of dates (YYYY-MM-DD): `["2025-01-25", "2025-07-09"]`
+    pub fn from_json(json: &str) -> Result<Self, PaymentPlanError> {
+        let dates: Vec<String> = serde_json::from_str(json)
+            .map_err(|error| PaymentPlanError::InvalidHolidayCalendar(error.to_string()))?;
+        let dates = dates
+            .iter()
+            .map(|date| parse_date(date))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HolidayList::new(dates))
+    }
+
+    pub fn dates(&self) -> &[chrono::NaiveDate] {
+        &self.dates
+    }
+
+    /// The holidays of this list and of `calendar`.
+    pub fn on_top_of<C: HolidayCalendar>(self, calendar: C) -> CombinedCalendar<Self, C> {
+        CombinedCalendar(self, calendar)
+    }
+}
+
+impl From<Vec<chrono::NaiveDate>> for HolidayList {
+    fn from(dates: Vec<chrono::NaiveDate>) -> Self {
+        HolidayList::new(dates)
+    }
+}
+
+impl From<HolidayList> for Vec<chrono::NaiveDate> {
+    fn from(list: HolidayList) -> Self {
+        list.dates
+    }
+}
+
+impl HolidayCalendar for HolidayList {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        self.dates.binary_search(&date).is_ok()
+    }
+}
+
+/// A date is a holiday when it is a holiday on any of the two calendars.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CombinedCalendar<A, B>(pub A, pub B);
+
+impl<A: HolidayCalendar, B: HolidayCalendar> HolidayCalendar for CombinedCalendar<A, B> {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        self.0.is_holiday(date) || self.1.is_holiday(date)
+    }
+}
+
+impl<C: HolidayCalendar + ?Sized> HolidayCalendar for &C {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        (**self).is_holiday(date)
+    }
+
+    fn business_day_index(&self) -> Option<&BusinessDayIndex> {
+        (**self).business_day_index()
+    }
+}
+
+fn parse_date(date: &str) -> Result<chrono::NaiveDate, PaymentPlanError> {
+    chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| PaymentPlanError::InvalidHolidayCalendar(date.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{err::PaymentPlanError, util::is_business_day};
+
+    use super::{BrazilianBankHolidays, HolidayCalendar, HolidayList};
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_holiday_list_from_text() {
+        let text = "
+            # São Paulo
+            2025-07-09
+            2025-01-25 # Anniversary of the city
+
+            2025-07-09
+        ";
+
+        let list = HolidayList::from_text(text).unwrap();
+
+        assert_eq!(list.dates(), &[date(2025, 1, 25), date(2025, 7, 9)]);
+        assert!(list.is_holiday(date(2025, 7, 9)));
+        assert!(!list.is_holiday(date(2025, 7, 10)));
+    }
+
+    #[test]
+    fn test_holiday_list_from_json() {
+        let list = HolidayList::from_json(r#"["2025-07-09", "2025-01-25"]"#).unwrap();
+
+        assert_eq!(list.dates(), &[date(2025, 1, 25), date(2025, 7, 9)]);
+    }
+
+    #[test]
+    fn test_holiday_list_invalid() {
+        let resp = HolidayList::from_text("2025-02-30");
+        assert_eq!(
+            resp,
+            Err(PaymentPlanError::InvalidHolidayCalendar(
+                "2025-02-30".to_string()
+            ))
+        );
+
+        let resp = HolidayList::from_json(r#"{"date": "2025-07-09"}"#);
+        assert!(matches!(
+            resp,
+            Err(PaymentPlanError::InvalidHolidayCalendar(_))
+        ));
+    }
+
+    #[test]
+    fn test_holiday_list_on_top_of() {
+        // Wednesday, a business day on the national calendar
+        let strike = date(2025, 7, 9);
+        assert!(is_business_day(strike, &BrazilianBankHolidays));
+
+        let calendar = HolidayList::new(vec![strike]).on_top_of(BrazilianBankHolidays);
+
+        assert!(!is_business_day(strike, &calendar));
+        assert!(!is_business_day(date(2025, 12, 25), &calendar));
+        assert!(is_business_day(date(2025, 7, 10), &calendar));
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::Datelike;
+
+use super::HolidayCalendar;
+
+/// When a holiday falls on each year.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HolidayDate {
+    Fixed { month: u32, day: u32 }, // The same day every year
+    Easter(i64),                    // Days from Easter Sunday (-48 is the Carnival Monday)
+}
+
+/*
+    A holiday and the years it is observed, both inclusive, so a holiday created or
+    abolished by a law is observed from or until the year of the law.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct HolidayRule {
+    pub name: String,
+    pub date: HolidayDate,
+    pub from_year: Option<i32>,  // None when it was always observed
+    pub until_year: Option<i32>, // None when it is still observed
+}
+
+impl HolidayRule {
+    pub fn fixed(name: &str, month: u32, day: u32) -> Self {
+        HolidayRule {
+            name: name.to_string(),
+            date: HolidayDate::Fixed { month, day },
+            from_year: None,
+            until_year: None,
+        }
+    }
+
+    pub fn easter(name: &str, days: i64) -> Self {
+        HolidayRule {
+            name: name.to_string(),
+            date: HolidayDate::Easter(days),
+            from_year: None,
+            until_year: None,
+        }
+    }
+
+    pub fn from_year(mut self, year: i32) -> Self {
+        self.from_year = Some(year);
+        self
+    }
+
+    pub fn until_year(mut self, year: i32) -> Self {
+        self.until_year = Some(year);
+        self
+    }
+
+    /// The date of the holiday on `year`, None when it is not observed on that year.
+    pub fn date_on(&self, year: i32) -> Option<chrono::NaiveDate> {
+        if self.from_year.is_some_and(|from| year < from)
+            || self.until_year.is_some_and(|until| year > until)
+        {
+            return None;
+        }
+
+        match self.date {
+            HolidayDate::Fixed { month, day } => chrono::NaiveDate::from_ymd_opt(year, month, day),
+            HolidayDate::Easter(days) => easter_sunday(year)
+                .and_then(|easter| easter.checked_add_signed(chrono::Duration::days(days))),
+        }
+    }
+}
+
+/*
+    Holidays calculated from rules for any year, instead of a list of dates.
+    `brazilian` has the national and bank holidays of the `BrazilianBankHolidays` table:
+        - Fixed: New Year, Tiradentes, Labour Day, Independence, Our Lady Aparecida, All Souls,
+            Proclamation of the Republic, Black Consciousness (since 2024) and Christmas.
+        - Easter: Carnival Monday and Tuesday, Good Friday and Corpus Christi.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleCalendar {
+    rules: Vec<HolidayRule>,
+}
+
+impl RuleCalendar {
+    pub fn new(rules: Vec<HolidayRule>) -> Self {
+        RuleCalendar { rules }
+    }
+
+    pub fn brazilian() -> Self {
+        RuleCalendar::new(vec![
+            HolidayRule::fixed("Confraternização Universal", 1, 1),
+            HolidayRule::easter("Carnaval (segunda-feira)", -48),
+            HolidayRule::easter("Carnaval (terça-feira)", -47),
+            HolidayRule::easter("Sexta-feira Santa", -2),
+            HolidayRule::fixed("Tiradentes", 4, 21),
+            HolidayRule::fixed("Dia do Trabalho", 5, 1),
+            HolidayRule::easter("Corpus Christi", 60),
+            HolidayRule::fixed("Independência do Brasil", 9, 7),
+            HolidayRule::fixed("Nossa Senhora Aparecida", 10, 12),
+            HolidayRule::fixed("Finados", 11, 2),
+            HolidayRule::fixed("Proclamação da República", 11, 15),
+            HolidayRule::fixed("Dia Nacional de Zumbi e da Consciência Negra", 11, 20)
+                .from_year(2024), // Lei 14.759/2023
+            HolidayRule::fixed("Natal", 12, 25),
+        ])
+    }
+
+    pub fn rules(&self) -> &[HolidayRule] {
+        &self.rules
+    }
+
+    pub fn with_rule(mut self, rule: HolidayRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Stops observing the holidays named `name` from `year` on.
+    pub fn remove_from(mut self, name: &str, year: i32) -> Self {
+        for rule in self.rules.iter_mut().filter(|rule| rule.name == name) {
+            let until_year = rule
+                .until_year
+                .map_or(year - 1, |until| until.min(year - 1));
+            rule.until_year = Some(until_year);
+        }
+        self
+    }
+
+    /// The holidays of `year`, sorted and without duplicates.
+    pub fn holidays(&self, year: i32) -> Vec<chrono::NaiveDate> {
+        let mut dates: Vec<chrono::NaiveDate> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.date_on(year))
+            .collect();
+        dates.sort_unstable();
+        dates.dedup();
+        dates
+    }
+}
+
+impl HolidayCalendar for RuleCalendar {
+    fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.date_on(date.year()) == Some(date))
+    }
+}
+
+// The Easter Sunday of the Gregorian calendar (anonymous algorithm, Meeus/Jones/Butcher)
+fn easter_sunday(year: i32) -> Option<chrono::NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    chrono::NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Datelike;
+
+    use crate::{
+        calendar::{BrazilianBankHolidays, HolidayCalendar},
+        util::BRAZILIAN_BANK_HOLIDAYS,
+    };
+
+    use super::{easter_sunday, HolidayRule, RuleCalendar};
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+        assert_eq!(easter_sunday(2285), Some(date(2285, 3, 22)));
+    }
+
+    #[test]
+    fn test_brazilian_rules_match_the_table() {
+        let calendar = RuleCalendar::brazilian();
+        let first = BRAZILIAN_BANK_HOLIDAYS[0];
+        let last = BRAZILIAN_BANK_HOLIDAYS[BRAZILIAN_BANK_HOLIDAYS.len() - 1];
+
+        let mut holidays: Vec<chrono::NaiveDate> = (first.year()..=last.year())
+            .flat_map(|year| calendar.holidays(year))
+            .collect();
+        // The table starts after the New Year of its first year
+        holidays.retain(|holiday| *holiday >= first);
+
+        assert_eq!(holidays, BRAZILIAN_BANK_HOLIDAYS.to_vec());
+        assert!(BRAZILIAN_BANK_HOLIDAYS
+            .iter()
+            .all(|holiday| calendar.is_holiday(*holiday)));
+    }
+
+    #[test]
+    fn test_rule_calendar_after_the_table() {
+        let calendar = RuleCalendar::brazilian();
+
+        assert_eq!(
+            calendar.holidays(2100),
+            vec![
+                date(2100, 1, 1),
+                date(2100, 2, 8),
+                date(2100, 2, 9),
+                date(2100, 3, 26),
+                date(2100, 4, 21),
+                date(2100, 5, 1),
+                date(2100, 5, 27),
+                date(2100, 9, 7),
+                date(2100, 10, 12),
+                date(2100, 11, 2),
+                date(2100, 11, 15),
+                date(2100, 11, 20),
+                date(2100, 12, 25),
+            ]
+        );
+
+        // The default calendar uses the rules when the table runs out
+        assert!(BrazilianBankHolidays.is_holiday(date(2100, 2, 8)));
+        assert!(!BrazilianBankHolidays.is_holiday(date(2100, 2, 10)));
+    }
+
+    #[test]
+    fn test_rule_calendar_add_and_remove() {
+        let calendar = RuleCalendar::brazilian()
+            .with_rule(
+                HolidayRule::fixed("Dia da Revolução Constitucionalista", 7, 9).from_year(2030),
+            )
+            .remove_from("Finados", 2040);
+
+        assert!(!calendar.is_holiday(date(2029, 7, 9)));
+        assert!(calendar.is_holiday(date(2030, 7, 9)));
+        assert!(calendar.is_holiday(date(2039, 11, 2)));
+        assert!(!calendar.is_holiday(date(2040, 11, 2)));
+
+        // Before the law of the Black Consciousness day
+        assert!(!calendar.is_holiday(date(2023, 11, 20)));
+        assert!(calendar.is_holiday(date(2024, 11, 20)));
+    }
+}
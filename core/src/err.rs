@@ -1,55 +1,338 @@
 use std::fmt::Display;
 
-use xirr::InvalidPaymentsError;
+use serde::Serialize;
 
-use crate::Params;
+/// A field of the params out of its domain, see `Params::validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub value: String,
+    pub constraint: &'static str,
+}
+
+impl FieldError {
+    pub(crate) fn new(field: &'static str, value: impl Display, constraint: &'static str) -> Self {
+        FieldError {
+            field,
+            value: value.to_string(),
+            constraint,
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}) {}", self.field, self.value, self.constraint)
+    }
+}
+
+/// A date out of the range (both inclusive) it must be in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateRangeError {
+    pub date: chrono::NaiveDate,
+    pub min_date: Option<chrono::NaiveDate>, // None when there is no lower limit
+    pub max_date: Option<chrono::NaiveDate>, // None when there is no upper limit
+}
+
+impl Display for DateRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.date)?;
+        match (self.min_date, self.max_date) {
+            (Some(min), Some(max)) => write!(f, " must be between {} and {}", min, max),
+            (Some(min), None) => write!(f, " must be on or after {}", min),
+            (None, Some(max)) => write!(f, " must be on or before {}", max),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// The rate calculated by the XIRR of the cash flows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum XirrRate {
+    Eir,
+    Tec,
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CashFlow {
+    pub amount: f64,
+    pub date: chrono::NaiveDate,
+}
+
+/// The cash flows whose XIRR could not be calculated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceError {
+    pub rate: XirrRate,
+    pub cash_flows: Vec<CashFlow>,
+}
+
+impl ConvergenceError {
+    pub(crate) fn new(rate: XirrRate, payments: &[xirr::Payment]) -> Self {
+        ConvergenceError {
+            rate,
+            cash_flows: payments
+                .iter()
+                .map(|payment| CashFlow {
+                    amount: payment.amount,
+                    date: payment.date,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Display for ConvergenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} of {} cash flows", self.rate, self.cash_flows.len())
+    }
+}
+
+/// The group of an error, the hundreds of its code.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum ErrorKind {
+    Validation,  // 1xx: a param out of its domain
+    DateRange,   // 2xx: a date out of its range
+    Convergence, // 3xx: the rates of the cash flows could not be calculated
+    Unreachable, // 4xx: the target of the solver can not be reached
+}
+
+/*
+    The codes are stable: a variant keeps its code, and new variants take the next code of their kind.
+    They are the `PaymentPlanResult` of the C bindings, the `code` of the uniffi and wasm errors,
+    and the CLI exits with the kind (see `ErrorKind`).
+*/
+#[derive(Debug, Clone, PartialEq)]
 pub enum PaymentPlanError {
-    CalculationError(InvalidPaymentsError),
+    InvalidParams(Vec<FieldError>),
     InvalidNumberOfInstallments,
     InvalidRequestedAmount,
-    InvalidDate(chrono::NaiveDate),
-    XirCalculationError(Params),
-}
-
-impl PartialEq for PaymentPlanError {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (PaymentPlanError::CalculationError(_), PaymentPlanError::CalculationError(_)) => true,
-            (
-                PaymentPlanError::InvalidNumberOfInstallments,
-                PaymentPlanError::InvalidNumberOfInstallments,
-            ) => true,
-            (
-                PaymentPlanError::InvalidRequestedAmount,
-                PaymentPlanError::InvalidRequestedAmount,
-            ) => true,
-            _ => false,
+    InvalidInterestRate,
+    InvalidPrepaymentAmount,
+    InvalidInstallmentAmount,
+    InvalidInstallmentWeights,
+    InvalidBalloonPercentage,
+    InvalidDueDay,
+    InvalidHolidayCalendar(String),
+    InvalidPlanInvoices, // The plan has no invoice amounts (ex: a plan of the Simple provider)
+    UnsupportedHolidayCalendar, // The provider does not move the dates to the business days of a calendar
+    InvalidDate(DateRangeError),
+    CalculationError(ConvergenceError), // The XIRR has no solution for the cash flows
+    XirCalculationError(ConvergenceError), // The XIRR of the cash flows is not a number
+    IofConvergenceError { iterations: u32, residual: f64 }, // The IOF still changed by the residual after the iterations
+    UnreachableInstallmentAmount,
+    UnreachableTec,
+}
+
+impl PaymentPlanError {
+    pub fn code(&self) -> u16 {
+        match self {
+            PaymentPlanError::InvalidParams(_) => 100,
+            PaymentPlanError::InvalidNumberOfInstallments => 101,
+            PaymentPlanError::InvalidRequestedAmount => 102,
+            PaymentPlanError::InvalidInterestRate => 103,
+            PaymentPlanError::InvalidPrepaymentAmount => 104,
+            PaymentPlanError::InvalidInstallmentAmount => 105,
+            PaymentPlanError::InvalidInstallmentWeights => 106,
+            PaymentPlanError::InvalidBalloonPercentage => 107,
+            PaymentPlanError::InvalidDueDay => 108,
+            PaymentPlanError::InvalidHolidayCalendar(_) => 109,
+            PaymentPlanError::InvalidPlanInvoices => 110,
+            PaymentPlanError::UnsupportedHolidayCalendar => 111,
+            PaymentPlanError::InvalidDate(_) => 200,
+            PaymentPlanError::CalculationError(_) => 300,
+            PaymentPlanError::XirCalculationError(_) => 301,
+            PaymentPlanError::IofConvergenceError { .. } => 302,
+            PaymentPlanError::UnreachableInstallmentAmount => 400,
+            PaymentPlanError::UnreachableTec => 401,
         }
     }
-}
 
-impl From<InvalidPaymentsError> for PaymentPlanError {
-    fn from(error: InvalidPaymentsError) -> Self {
-        PaymentPlanError::CalculationError(error)
+    pub fn kind(&self) -> ErrorKind {
+        match self.code() / 100 {
+            1 => ErrorKind::Validation,
+            2 => ErrorKind::DateRange,
+            3 => ErrorKind::Convergence,
+            _ => ErrorKind::Unreachable,
+        }
+    }
+
+    /// The field errors of the validation errors, the fields checked by the providers have no value.
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        let field = |field, constraint| FieldError {
+            field,
+            value: String::new(),
+            constraint,
+        };
+        match self {
+            PaymentPlanError::InvalidParams(errors) => errors.clone(),
+            PaymentPlanError::InvalidNumberOfInstallments => {
+                vec![field("installments", "must be greater than 0")]
+            }
+            PaymentPlanError::InvalidRequestedAmount => {
+                vec![field("requested_amount", "must be greater than 0")]
+            }
+            PaymentPlanError::InvalidInterestRate => {
+                vec![field("interest_rate", "must not be negative")]
+            }
+            PaymentPlanError::InvalidPrepaymentAmount => vec![field(
+                "amount",
+                "must be greater than 0 and less than the outstanding balance",
+            )],
+            PaymentPlanError::InvalidInstallmentAmount => {
+                vec![field("installment_amount", "must be greater than 0")]
+            }
+            PaymentPlanError::InvalidInstallmentWeights => vec![field(
+                "weights",
+                "must be greater than 0, one for each due date",
+            )],
+            PaymentPlanError::InvalidBalloonPercentage => {
+                vec![field(
+                    "balloon_percentage",
+                    "must be at least 0 and less than 1",
+                )]
+            }
+            PaymentPlanError::InvalidDueDay => {
+                vec![field("due_day_policy", "must be between 1 and 31")]
+            }
+            PaymentPlanError::InvalidHolidayCalendar(date) => vec![FieldError {
+                field: "holidays",
+                value: date.clone(),
+                constraint: "must be dates (YYYY-MM-DD)",
+            }],
+            PaymentPlanError::InvalidPlanInvoices => vec![field(
+                "invoices",
+                "must have an amount, as calculated by the Iterative provider",
+            )],
+            PaymentPlanError::UnsupportedHolidayCalendar => vec![field(
+                "calendar",
+                "must be used with the Iterative provider",
+            )],
+            _ => Vec::new(),
+        }
     }
 }
 
+impl std::error::Error for PaymentPlanError {}
+
 impl Display for PaymentPlanError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            PaymentPlanError::CalculationError(error) => write!(f, "Calculation error: {}", error),
+            PaymentPlanError::CalculationError(error) => {
+                write!(
+                    f,
+                    "Calculation error: the XIRR has no solution for the {}",
+                    error
+                )
+            }
             PaymentPlanError::InvalidNumberOfInstallments => {
                 write!(f, "Number of installments must be greater than 0")
             }
             PaymentPlanError::InvalidRequestedAmount => {
                 write!(f, "Requested amount must be greater than 0")
             }
-            PaymentPlanError::XirCalculationError(params) => {
-                write!(f, "XIR calculation error: {}", params)
+            PaymentPlanError::InvalidInterestRate => {
+                write!(f, "Interest rate must not be negative")
+            }
+            PaymentPlanError::InvalidPrepaymentAmount => {
+                write!(
+                    f,
+                    "Prepayment amount must be greater than 0 and less than the outstanding balance"
+                )
+            }
+            PaymentPlanError::InvalidInstallmentAmount => {
+                write!(f, "Installment amount must be greater than 0")
+            }
+            PaymentPlanError::InvalidInstallmentWeights => {
+                write!(
+                    f,
+                    "Installment weights must be greater than 0, one for each due date"
+                )
+            }
+            PaymentPlanError::InvalidBalloonPercentage => {
+                write!(f, "Balloon percentage must be at least 0 and less than 1")
+            }
+            PaymentPlanError::InvalidDueDay => {
+                write!(f, "Due day must be between 1 and 31")
+            }
+            PaymentPlanError::UnreachableInstallmentAmount => {
+                write!(f, "The installment amount can not be reached")
+            }
+            PaymentPlanError::UnreachableTec => {
+                write!(f, "The TEC can not be kept under the cap")
+            }
+            PaymentPlanError::XirCalculationError(error) => {
+                write!(
+                    f,
+                    "XIR calculation error: the XIRR is not a number for the {}",
+                    error
+                )
+            }
+            PaymentPlanError::IofConvergenceError {
+                iterations,
+                residual,
+            } => {
+                write!(
+                    f,
+                    "IOF convergence error: the IOF still changed by {} after {} iterations",
+                    residual, iterations
+                )
+            }
+            PaymentPlanError::InvalidDate(error) => write!(f, "Invalid date: {}", error),
+            PaymentPlanError::InvalidHolidayCalendar(date) => {
+                write!(f, "Invalid holiday calendar date: {}", date)
+            }
+            PaymentPlanError::InvalidPlanInvoices => {
+                write!(
+                    f,
+                    "Plan invoices must have an amount, as calculated by the Iterative provider"
+                )
+            }
+            PaymentPlanError::UnsupportedHolidayCalendar => {
+                write!(
+                    f,
+                    "Holiday calendar is not supported by the provider, only by the Iterative provider"
+                )
+            }
+            PaymentPlanError::InvalidParams(errors) => {
+                let errors: Vec<String> = errors.iter().map(FieldError::to_string).collect();
+                write!(f, "Invalid params: {}", errors.join(", "))
             }
-            PaymentPlanError::InvalidDate(date) => write!(f, "Invalid date: {}", date),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{DateRangeError, ErrorKind, PaymentPlanError};
+
+    #[test]
+    fn test_error_codes() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap();
+        let error = PaymentPlanError::InvalidDate(DateRangeError {
+            date,
+            min_date: Some(date.succ_opt().unwrap()),
+            max_date: None,
+        });
+
+        assert_eq!(error.code(), 200);
+        assert_eq!(error.kind(), ErrorKind::DateRange);
+        assert_eq!(
+            error.to_string(),
+            "Invalid date: 2025-09-18 must be on or after 2025-09-19"
+        );
+
+        assert_eq!(PaymentPlanError::InvalidRequestedAmount.code(), 102);
+        assert_eq!(
+            PaymentPlanError::InvalidRequestedAmount.kind(),
+            ErrorKind::Validation
+        );
+        assert_eq!(
+            PaymentPlanError::InvalidRequestedAmount.field_errors()[0].field,
+            "requested_amount"
+        );
+        assert_eq!(
+            PaymentPlanError::UnreachableTec.kind(),
+            ErrorKind::Unreachable
+        );
+    }
+}
@@ -1,20 +1,21 @@
 use std::fmt::Display;
 
 use calc::PaymentPlan;
+use calendar::{BrazilianBankHolidays, HolidayCalendar};
 use err::PaymentPlanError;
 use serde::{Deserialize, Serialize};
+use util::{to_decimal, to_f64};
 
-#[cfg(feature = "iterative")]
-use calc::providers::iterative::Iterative;
-#[cfg(feature = "simple")]
-use calc::providers::simple::Simple;
+pub use params::ParamsBuilder;
+pub use rust_decimal::Decimal;
 
-// Default to Iterative if no feature is specified
-#[cfg(not(any(feature = "simple", feature = "iterative")))]
-use calc::providers::iterative::Iterative;
+use calc::providers::{iterative::Iterative, simple::Simple};
 
 mod calc;
+pub mod calendar;
 pub mod err;
+mod params;
+pub mod provider;
 mod util;
 
 #[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
@@ -24,7 +25,221 @@ pub struct Invoice {
     pub accumulated_factor: f64,
     pub main_iof_tac: f64,
     pub debit_service: f64,
+    pub amount: f64,
     pub due_date: chrono::NaiveDate,
+    // The amortization schedule of the invoice, the last one closes the balance at exactly 0
+    #[serde(default)]
+    pub opening_balance: f64, // The balance of the contract before the invoice
+    #[serde(default)]
+    pub interest: f64, // The interest accrued on the balance since the previous due date
+    #[serde(default)]
+    pub principal: f64, // The amount of the balance amortized (amount - interest)
+    #[serde(default)]
+    pub iof: f64, // The share of the IOF financed on the principal of the invoice
+    #[serde(default)]
+    pub tac: f64, // The share of the TAC on the principal of the invoice
+    #[serde(default)]
+    pub closing_balance: f64, // The balance of the contract after the invoice
+}
+
+/// An invoice with its monetary values as exact decimals, see `calculate_decimal_payment_plan`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct DecimalInvoice {
+    pub accumulated_days: i64,
+    pub factor: f64,
+    pub accumulated_factor: f64,
+    pub main_iof_tac: Decimal,
+    pub debit_service: Decimal,
+    pub amount: Decimal,
+    pub due_date: chrono::NaiveDate,
+    pub opening_balance: Decimal,
+    pub interest: Decimal,
+    pub principal: Decimal,
+    pub iof: Decimal,
+    pub tac: Decimal,
+    pub closing_balance: Decimal,
+}
+
+impl From<DecimalInvoice> for Invoice {
+    fn from(value: DecimalInvoice) -> Self {
+        Invoice {
+            accumulated_days: value.accumulated_days,
+            factor: value.factor,
+            accumulated_factor: value.accumulated_factor,
+            main_iof_tac: to_f64(value.main_iof_tac),
+            debit_service: to_f64(value.debit_service),
+            amount: to_f64(value.amount),
+            due_date: value.due_date,
+            opening_balance: to_f64(value.opening_balance),
+            interest: to_f64(value.interest),
+            principal: to_f64(value.principal),
+            iof: to_f64(value.iof),
+            tac: to_f64(value.tac),
+            closing_balance: to_f64(value.closing_balance),
+        }
+    }
+}
+
+impl From<Invoice> for DecimalInvoice {
+    fn from(value: Invoice) -> Self {
+        DecimalInvoice {
+            accumulated_days: value.accumulated_days,
+            factor: value.factor,
+            accumulated_factor: value.accumulated_factor,
+            main_iof_tac: to_decimal(value.main_iof_tac),
+            debit_service: to_decimal(value.debit_service),
+            amount: to_decimal(value.amount),
+            due_date: value.due_date,
+            opening_balance: to_decimal(value.opening_balance),
+            interest: to_decimal(value.interest),
+            principal: to_decimal(value.principal),
+            iof: to_decimal(value.iof),
+            tac: to_decimal(value.tac),
+            closing_balance: to_decimal(value.closing_balance),
+        }
+    }
+}
+
+/// How the principal is amortized over the installments.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum AmortizationSystem {
+    /// Tabela Price: every installment has the same amount.
+    #[default]
+    Price,
+    /// Sistema de Amortização Constante: the principal amortization is constant and the installments decline.
+    Sac,
+}
+
+/// How the interest accrued between the disbursement and the first installment is paid.
+///
+/// The grace window is inferred from `first_payment_date - disbursement_date`: every whole month
+/// (30 days) beyond the first one counts as a grace period.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum GracePeriod {
+    /// The grace interest is capitalized into the balance and paid with the installments.
+    #[default]
+    Capitalized,
+    /// The grace interest is paid on interest-only invoices, one per grace period, due monthly
+    /// before the first installment.
+    InterestOnly,
+}
+
+/// How often the installments are due.
+///
+/// The interest rate is still monthly, the factors of the installments compound it daily
+/// over the days between the disbursement and each due date.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum Periodicity {
+    /// Every month, on the day of the first payment date.
+    #[default]
+    Monthly,
+    /// Every 14 days from the first payment date.
+    Biweekly,
+    /// Every 7 days from the first payment date.
+    Weekly,
+}
+
+impl Periodicity {
+    /// The days between two installments, a month counts as 30 days.
+    pub fn days(self) -> i64 {
+        match self {
+            Periodicity::Monthly => 30,
+            Periodicity::Biweekly => 14,
+            Periodicity::Weekly => 7,
+        }
+    }
+}
+
+/// How the day of the month of the due dates is chosen on monthly plans,
+/// the other periodicities always step the same number of days from the first payment date.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum DueDayPolicy {
+    /// Every month one month after the previous due date, so the day the first payment date
+    /// loses on a shorter month is not recovered (Jan 31, Feb 28, Mar 28...).
+    #[default]
+    Stepped,
+    /// Every month on the day of the first payment date, or on the last day of the months
+    /// without it (Jan 31, Feb 28, Mar 31...).
+    Anchored,
+    /// Every month on its last day, starting on the month of the first payment date.
+    EndOfMonth,
+    /// Every month on the chosen day (1-31), or on the last day of the months without it.
+    /// The first installment is due on the first of these days on or after the first payment date.
+    FixedDay(u32),
+}
+
+impl DueDayPolicy {
+    pub(crate) fn is_valid(self) -> bool {
+        match self {
+            DueDayPolicy::FixedDay(day) => (1..=31).contains(&day),
+            _ => true,
+        }
+    }
+}
+
+/// How a date that is not a business day is moved to one, with `disbursement_only_on_business_days`.
+/// It applies to the disbursement date and to every due date.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum BusinessDayConvention {
+    /// The next business day.
+    #[default]
+    Following,
+    /// The next business day, or the previous one when the next is on the following month.
+    ModifiedFollowing,
+    /// The previous business day.
+    Preceding,
+}
+
+/// How a value is rounded to its decimal places.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum RoundingMode {
+    /// Half away from zero (0.125 -> 0.13).
+    #[default]
+    HalfUp,
+    /// Half to the even digit, the banker's rounding (0.125 -> 0.12, 0.135 -> 0.14).
+    HalfEven,
+    /// Towards zero, dropping the digits beyond the decimal places (0.129 -> 0.12).
+    Truncate,
+}
+
+/// Which installment takes the rounding residual, the difference between the exact total of the installments
+/// and the sum of the installments rounded to cents.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum ResidualAllocation {
+    /// The installments are rounded on their own and the residual is dropped.
+    #[default]
+    None,
+    FirstInstallment,
+    LastInstallment,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub struct RoundingPolicy {
+    #[serde(default)]
+    pub mode: RoundingMode,
+    #[serde(default)]
+    pub residual_allocation: ResidualAllocation,
+}
+
+/*
+    When the IOF of the Iterative provider converged. The IOF is financed, so the installments are
+    recalculated with it until the total IOF changes by at most `tolerance` between two iterations.
+    The IOF is rounded to cents and the rounding of the installments can move it a cent each way,
+    so the default tolerance is two cents.
+*/
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub struct IofConvergence {
+    pub tolerance: f64, // The largest change of the total IOF of a converged plan (ex: 0.02)
+    pub max_iterations: u32, // The iterations of the IOF before the `IofConvergenceError`
+}
+
+impl Default for IofConvergence {
+    fn default() -> Self {
+        IofConvergence {
+            tolerance: 0.02,
+            max_iterations: 10,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, Deserialize)]
@@ -42,13 +257,29 @@ pub struct Params {
     pub min_installment_amount: f64,
     pub max_total_amount: f64,
     pub disbursement_only_on_business_days: bool,
+    #[serde(default)]
+    pub amortization_system: AmortizationSystem,
+    #[serde(default)]
+    pub grace_period: GracePeriod,
+    #[serde(default)]
+    pub balloon_percentage: f64, // 0.0-1.0, share of the principal paid with the last installment
+    #[serde(default)]
+    pub periodicity: Periodicity,
+    #[serde(default)]
+    pub due_day_policy: DueDayPolicy,
+    #[serde(default)]
+    pub rounding_policy: RoundingPolicy,
+    #[serde(default)]
+    pub business_day_convention: BusinessDayConvention,
+    #[serde(default)]
+    pub iof_convergence: IofConvergence,
 }
 
 impl Display for Params {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Params {{ requested_amount: {}, first_payment_date: {}, disbursement_date: {}, installments: {}, debit_service_percentage: {}, mdr: {}, tac_percentage: {}, iof_overall: {}, iof_percentage: {}, interest_rate: {}, min_installment_amount: {}, max_total_amount: {} }}",
+            "Params {{ requested_amount: {}, first_payment_date: {}, disbursement_date: {}, installments: {}, debit_service_percentage: {}, mdr: {}, tac_percentage: {}, iof_overall: {}, iof_percentage: {}, interest_rate: {}, min_installment_amount: {}, max_total_amount: {}, amortization_system: {:?}, grace_period: {:?}, balloon_percentage: {}, periodicity: {:?}, due_day_policy: {:?}, rounding_policy: {:?}, business_day_convention: {:?}, iof_convergence: {:?} }}",
             self.requested_amount,
             self.first_payment_date,
             self.disbursement_date,
@@ -60,7 +291,15 @@ impl Display for Params {
             self.iof_percentage,
             self.interest_rate,
             self.min_installment_amount,
-            self.max_total_amount
+            self.max_total_amount,
+            self.amortization_system,
+            self.grace_period,
+            self.balloon_percentage,
+            self.periodicity,
+            self.due_day_policy,
+            self.rounding_policy,
+            self.business_day_convention,
+            self.iof_convergence
         )
     }
 }
@@ -91,6 +330,8 @@ pub struct Response {
     pub tec_yearly: f64,
     pub eir_monthly: f64,
     pub tec_monthly: f64,
+    pub eir_periodic: f64, // The EIR over the periodicity of the installments, the same as the monthly one for monthly plans
+    pub tec_periodic: f64, // The TEC over the periodicity of the installments, the same as the monthly one for monthly plans
     pub total_iof: f64,
     pub contract_amount: f64,
     pub contract_amount_without_tac: f64,
@@ -100,9 +341,111 @@ pub struct Response {
     pub pre_disbursement_amount: f64,
     pub paid_total_iof: f64,
     pub paid_contract_amount: f64,
+    pub due_day_policy: DueDayPolicy, // The policy the due dates of the invoices follow
+    pub rounding_residual: f64, // The rounding residual added to an installment by the residual allocation
+    pub iof_iterations: u32, // The iterations of the IOF until it converged, 0 when it is not iterated
+    pub iof_residual: f64,   // The change of the total IOF on the last iteration
     pub invoices: Vec<Invoice>,
 }
 
+/*
+    The payment plan with its monetary values as exact decimals, rounded to cents the same way as the `Response`
+    (the amounts that are not rounded there keep the precision of the decimal).
+    The invoices add up to the total amount exactly, and the rates are the same f64 of the `Response`.
+*/
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct DecimalResponse {
+    pub installment: u32,
+    pub due_date: chrono::NaiveDate,
+    pub disbursement_date: chrono::NaiveDate,
+    pub accumulated_days: i64,
+    pub days_index: f64,
+    pub accumulated_days_index: f64,
+    pub interest_rate: f64,
+    pub installment_amount: Decimal,
+    pub installment_amount_without_tac: Decimal,
+    pub total_amount: Decimal,
+    pub debit_service: Decimal,
+    pub customer_debit_service_amount: Decimal,
+    pub customer_amount: Decimal,
+    pub calculation_basis_for_effective_interest_rate: Decimal,
+    pub merchant_debit_service_amount: Decimal,
+    pub merchant_total_amount: Decimal,
+    pub settled_to_merchant: Decimal,
+    pub mdr_amount: Decimal,
+    pub effective_interest_rate: f64,
+    pub total_effective_cost: f64,
+    pub eir_yearly: f64,
+    pub tec_yearly: f64,
+    pub eir_monthly: f64,
+    pub tec_monthly: f64,
+    pub eir_periodic: f64,
+    pub tec_periodic: f64,
+    pub total_iof: Decimal,
+    pub contract_amount: Decimal,
+    pub contract_amount_without_tac: Decimal,
+    pub tac_amount: Decimal,
+    pub iof_percentage: f64,
+    pub overall_iof: f64,
+    pub pre_disbursement_amount: Decimal,
+    pub paid_total_iof: Decimal,
+    pub paid_contract_amount: Decimal,
+    pub due_day_policy: DueDayPolicy,
+    pub rounding_residual: Decimal,
+    pub iof_iterations: u32,
+    pub iof_residual: Decimal,
+    pub invoices: Vec<DecimalInvoice>,
+}
+
+impl From<DecimalResponse> for Response {
+    fn from(value: DecimalResponse) -> Self {
+        Response {
+            installment: value.installment,
+            due_date: value.due_date,
+            disbursement_date: value.disbursement_date,
+            accumulated_days: value.accumulated_days,
+            days_index: value.days_index,
+            accumulated_days_index: value.accumulated_days_index,
+            interest_rate: value.interest_rate,
+            installment_amount: to_f64(value.installment_amount),
+            installment_amount_without_tac: to_f64(value.installment_amount_without_tac),
+            total_amount: to_f64(value.total_amount),
+            debit_service: to_f64(value.debit_service),
+            customer_debit_service_amount: to_f64(value.customer_debit_service_amount),
+            customer_amount: to_f64(value.customer_amount),
+            calculation_basis_for_effective_interest_rate: to_f64(
+                value.calculation_basis_for_effective_interest_rate,
+            ),
+            merchant_debit_service_amount: to_f64(value.merchant_debit_service_amount),
+            merchant_total_amount: to_f64(value.merchant_total_amount),
+            settled_to_merchant: to_f64(value.settled_to_merchant),
+            mdr_amount: to_f64(value.mdr_amount),
+            effective_interest_rate: value.effective_interest_rate,
+            total_effective_cost: value.total_effective_cost,
+            eir_yearly: value.eir_yearly,
+            tec_yearly: value.tec_yearly,
+            eir_monthly: value.eir_monthly,
+            tec_monthly: value.tec_monthly,
+            eir_periodic: value.eir_periodic,
+            tec_periodic: value.tec_periodic,
+            total_iof: to_f64(value.total_iof),
+            contract_amount: to_f64(value.contract_amount),
+            contract_amount_without_tac: to_f64(value.contract_amount_without_tac),
+            tac_amount: to_f64(value.tac_amount),
+            iof_percentage: value.iof_percentage,
+            overall_iof: value.overall_iof,
+            pre_disbursement_amount: to_f64(value.pre_disbursement_amount),
+            paid_total_iof: to_f64(value.paid_total_iof),
+            paid_contract_amount: to_f64(value.paid_contract_amount),
+            due_day_policy: value.due_day_policy,
+            rounding_residual: to_f64(value.rounding_residual),
+            iof_iterations: value.iof_iterations,
+            iof_residual: to_f64(value.iof_residual),
+            invoices: value.invoices.into_iter().map(Invoice::from).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Copy)]
 pub struct DownPaymentParams {
     pub params: Params,              // The params for the actual payment plan
@@ -112,6 +455,172 @@ pub struct DownPaymentParams {
     pub installments: u32,           // The max number of installments for the down payment (ex: 12)
 }
 
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct EarlySettlementParams {
+    pub invoices: Vec<Invoice>, // The invoices still open, only the amount and due date are used
+    pub interest_rate: f64,     // The monthly interest rate of the contract (0.0-1.0)
+    pub settlement_date: chrono::NaiveDate, // The date the contract is paid off
+    pub disbursement_only_on_business_days: bool, // Same as the one used to calculate the plan
+}
+
+impl EarlySettlementParams {
+    /// Builds the settlement params from a plan calculated by the Iterative provider.
+    /// The invoices due before the settlement date are considered paid and left out.
+    /// The plans without the invoice amounts (ex: the ones of the Simple provider) can not be settled.
+    pub fn from_plan(
+        plan: &Response,
+        settlement_date: chrono::NaiveDate,
+        disbursement_only_on_business_days: bool,
+    ) -> Result<Self, PaymentPlanError> {
+        if plan.invoices.is_empty() || plan.invoices.iter().any(|invoice| invoice.amount <= 0.0) {
+            return Err(PaymentPlanError::InvalidPlanInvoices);
+        }
+
+        let invoices = plan
+            .invoices
+            .iter()
+            .filter(|invoice| invoice.due_date >= settlement_date)
+            .copied()
+            .collect();
+
+        Ok(Self {
+            invoices,
+            interest_rate: plan.interest_rate,
+            settlement_date,
+            disbursement_only_on_business_days,
+        })
+    }
+}
+
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct CustomScheduleParams {
+    pub params: Params, // The params of the plan, the installments and first payment date are taken from the due dates
+    pub due_dates: Vec<chrono::NaiveDate>, // The due date of each installment, in ascending order
+    #[serde(default)]
+    pub weights: Vec<f64>, // The relative size of each installment (ex: 2.0 is twice the others), empty for equal installments
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct EarlySettlementInvoice {
+    pub due_date: chrono::NaiveDate,
+    pub amount: f64,        // The face value of the invoice
+    pub days: i64,          // The days discounted, 0 when already due on the settlement date
+    pub present_value: f64, // The amount to be paid for the invoice on the settlement date
+    pub discount: f64,      // The interest not charged because of the early payment
+}
+
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct EarlySettlementResponse {
+    pub settlement_date: chrono::NaiveDate,
+    pub total_amount: f64, // The sum of the face values of the open invoices
+    pub settlement_amount: f64, // The amount to pay off the contract on the settlement date
+    pub discount: f64,     // The total interest not charged
+    pub invoices: Vec<EarlySettlementInvoice>,
+}
+
+/// How the invoices left are recalculated after a partial prepayment.
+#[derive(Debug, Default, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum PrepaymentStrategy {
+    /// Keep the installment amount and shorten the term.
+    #[default]
+    ReduceTerm,
+    /// Keep the term and lower the installment amount.
+    ReduceInstallment,
+}
+
+/// What the interest rate of a plan is solved for.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+pub enum InterestRateTarget {
+    /// The installment amount the plan must have (within a cent).
+    InstallmentAmount(f64),
+    /// The max monthly TEC (0.0-1.0) the plan can have.
+    MaxTecMonthly(f64),
+    /// The max yearly TEC (0.0-1.0) the plan can have.
+    MaxTecYearly(f64),
+}
+
+#[derive(Debug, Default, Deserialize, Clone, Copy)]
+pub struct PrepaymentParams {
+    pub params: Params, // The params of the plan, with the number of installments contracted
+    pub prepayment_date: chrono::NaiveDate, // The date of the extra payment
+    pub amount: f64,    // The extra payment amount
+    pub strategy: PrepaymentStrategy,
+}
+
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct PrepaymentResponse {
+    pub prepayment_date: chrono::NaiveDate,
+    pub prepayment_amount: f64,
+    pub outstanding_balance: f64, // The balance of the invoices left, right before the prepayment
+    pub remaining_balance: f64,   // The balance after the prepayment
+    pub installment: u32,         // The number of invoices left
+    pub installment_amount: f64,  // The amount of the next invoice
+    pub total_amount: f64, // Everything paid in the contract (invoices paid, prepayment and invoices left)
+    pub eir_monthly: f64,
+    pub eir_yearly: f64,
+    pub tec_monthly: f64,
+    pub tec_yearly: f64,
+    pub invoices: Vec<Invoice>, // The invoices left after the prepayment
+}
+
+pub const DEFAULT_LATE_FINE_RATE: f64 = 0.02; // 2%, the limit of the CDC (art. 52, §1º)
+pub const DEFAULT_LATE_MORA_RATE: f64 = 0.01; // 1% a.m.
+
+fn default_late_fine_rate() -> f64 {
+    DEFAULT_LATE_FINE_RATE
+}
+
+fn default_late_mora_rate() -> f64 {
+    DEFAULT_LATE_MORA_RATE
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct LateChargesParams {
+    pub invoice: Invoice, // The overdue invoice, the amount, due date, main_iof_tac and accumulated days are used
+    pub interest_rate: f64, // The monthly interest rate of the contract (0.0-1.0)
+    pub payment_date: chrono::NaiveDate, // The date the invoice is paid
+    #[serde(default = "default_late_fine_rate")]
+    pub fine_rate: f64, // The fine over the invoice amount (ex: 0.02)
+    #[serde(default = "default_late_mora_rate")]
+    pub mora_rate: f64, // The monthly moratory interest rate (ex: 0.01)
+    pub iof_percentage: f64, // The daily IOF of the contract (ex: 0.000082)
+}
+
+impl Default for LateChargesParams {
+    fn default() -> Self {
+        Self {
+            invoice: Invoice::default(),
+            interest_rate: 0.0,
+            payment_date: chrono::NaiveDate::default(),
+            fine_rate: DEFAULT_LATE_FINE_RATE,
+            mora_rate: DEFAULT_LATE_MORA_RATE,
+            iof_percentage: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq)]
+pub struct LateChargesResponse {
+    pub days_late: i64, // The calendar days after the due date, 0 when paid on time
+    pub amount: f64,    // The amount of the invoice
+    pub fine: f64,      // The fine (multa)
+    pub mora_interest: f64, // The moratory interest (juros de mora)
+    pub remunerative_interest: f64, // The contract interest for the days late (juros remuneratórios)
+    pub iof: f64,                   // The additional IOF on the overdue principal
+    pub total_amount: f64,          // The updated amount due on the payment date
+}
+
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct RequestedAmountResponse {
+    pub requested_amount: f64, // The requested amount found for the target installment amount
+    pub plan: Response,        // The plan calculated with the requested amount found
+}
+
 //This struct can't derive Copy because it contains a Vec that is not known at compile time
 #[derive(Debug, Serialize, Clone)]
 pub struct DownPaymentResponse {
@@ -122,48 +631,267 @@ pub struct DownPaymentResponse {
     pub plans: Vec<Response>,    // The payment plans available for the down payment
 }
 
-#[cfg(feature = "simple")]
-const P: Simple = Simple {};
-#[cfg(feature = "iterative")]
-const P: Iterative = Iterative {};
+/// A value of the plans of the Simple and the Iterative providers with the same installments.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct FieldComparison {
+    pub field: &'static str, // The name of the field of the `Response`
+    pub simple: f64,
+    pub iterative: f64,
+    pub absolute_delta: f64, // iterative - simple
+    pub relative_delta: f64, // The absolute delta over the simple value, infinite when it is 0
+    pub material: bool,      // The relative delta is over the tolerance
+}
+
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ProviderComparison {
+    pub installment: u32,
+    pub missing: Option<Provider>, // The provider without a plan with these installments (ex: min installment amount)
+    pub fields: Vec<FieldComparison>, // Empty when a provider is missing
+    pub material: bool,            // A field is material or a provider is missing
+}
+
+/// Why a selected number of installments has no plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExclusionReason {
+    MinInstallmentAmount, // An installment is under the minimum installment amount
+    MaxTotalAmount,       // The total amount is over the maximum total amount
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct ExcludedInstallment {
+    pub installment: u32,
+    pub reason: ExclusionReason,
+}
 
-// Default to Iterative if no feature is specified
-#[cfg(not(any(feature = "simple", feature = "iterative")))]
-const P: Iterative = Iterative {};
+//This struct can't derive Copy because it contains a Vec that is not known at compile time
+#[derive(Debug, Default, Serialize, Clone, PartialEq)]
+pub struct SelectedPlans {
+    pub plans: Vec<Response>, // The plans of the selected installments, sorted by them
+    pub excluded: Vec<ExcludedInstallment>, // The selected installments without a plan, sorted
+}
+
+/// The method used to calculate the plans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Provider {
+    Simple,    // Estimates the IOF from the overall IOF percentage, faster but less precise
+    Iterative, // Calculates the IOF iteratively, the recommended one
+}
+
+impl Default for Provider {
+    // The provider of the cargo features, Iterative if no feature is specified
+    fn default() -> Self {
+        if cfg!(all(feature = "simple", not(feature = "iterative"))) {
+            Provider::Simple
+        } else {
+            Provider::Iterative
+        }
+    }
+}
+
+impl Provider {
+    fn payment_plan(self) -> &'static dyn PaymentPlan {
+        match self {
+            Provider::Simple => &Simple,
+            Provider::Iterative => &Iterative,
+        }
+    }
+}
 
 pub fn calculate_down_payment_plan(
     params: DownPaymentParams,
 ) -> Result<Vec<DownPaymentResponse>, PaymentPlanError> {
-    return P.calculate_down_payment_plan(params);
+    calculate_down_payment_plan_with(Provider::default(), params)
+}
+
+/// The same plans as `calculate_down_payment_plan`, calculated by `provider`.
+pub fn calculate_down_payment_plan_with(
+    provider: Provider,
+    params: DownPaymentParams,
+) -> Result<Vec<DownPaymentResponse>, PaymentPlanError> {
+    provider.payment_plan().calculate_down_payment_plan(params)
 }
 
 pub fn calculate_payment_plan(params: Params) -> Result<Vec<Response>, PaymentPlanError> {
-    return P.calculate_payment_plan(params);
+    calculate_payment_plan_with(Provider::default(), params)
+}
+
+/// The same plans as `calculate_payment_plan`, calculated by `provider`.
+pub fn calculate_payment_plan_with(
+    provider: Provider,
+    params: Params,
+) -> Result<Vec<Response>, PaymentPlanError> {
+    provider.payment_plan().calculate_payment_plan(params)
+}
+
+/// The plans of the numbers of installments in `installments` only (ex: `&[3, 6, 10, 12]`, or `&[10]` for a single one),
+/// with the installments excluded by the minimum installment amount or the maximum total amount.
+pub fn calculate_selected_payment_plans(
+    params: Params,
+    installments: &[u32],
+) -> Result<SelectedPlans, PaymentPlanError> {
+    calculate_selected_payment_plans_with(Provider::default(), params, installments)
+}
+
+/// The same plans as `calculate_selected_payment_plans`, calculated by `provider`.
+pub fn calculate_selected_payment_plans_with(
+    provider: Provider,
+    params: Params,
+    installments: &[u32],
+) -> Result<SelectedPlans, PaymentPlanError> {
+    provider
+        .payment_plan()
+        .calculate_selected_payment_plans(params, installments)
+}
+
+/// The same plans as `calculate_payment_plan`, moving the dates to the business days of `calendar`.
+pub fn calculate_payment_plan_with_calendar(
+    params: Params,
+    calendar: &dyn HolidayCalendar,
+) -> Result<Vec<Response>, PaymentPlanError> {
+    calculate_payment_plan_with_calendar_with(Provider::default(), params, calendar)
+}
+
+/// The same plans as `calculate_payment_plan_with_calendar`, calculated by `provider`.
+/// Only the Iterative provider moves the dates to business days, the others return `UnsupportedHolidayCalendar`.
+pub fn calculate_payment_plan_with_calendar_with(
+    provider: Provider,
+    params: Params,
+    calendar: &dyn HolidayCalendar,
+) -> Result<Vec<Response>, PaymentPlanError> {
+    provider
+        .payment_plan()
+        .calculate_payment_plan_with_calendar(params, calendar)
+}
+
+/// The same plans as `calculate_payment_plan`, with the monetary values as exact decimals.
+/// It always uses the Iterative provider.
+pub fn calculate_decimal_payment_plan(
+    params: Params,
+) -> Result<Vec<DecimalResponse>, PaymentPlanError> {
+    calc::providers::iterative::Iterative.calculate_decimal_payment_plan(params)
+}
+
+/// Calculates the plans of `params` with the Simple and the Iterative providers and compares them field by field,
+/// for each number of installments. The differences over `tolerance` (relative, ex: 0.01 for 1%) are material.
+pub fn compare_providers(
+    params: Params,
+    tolerance: f64,
+) -> Result<Vec<ProviderComparison>, PaymentPlanError> {
+    calc::comparison::compare_providers(params, tolerance)
+}
+
+/// Finds the requested amount whose plan with `params.installments` invoices has the target
+/// installment amount (within a cent) and returns it with that plan. It always uses the Iterative provider.
+pub fn calculate_requested_amount(
+    params: Params,
+    installment_amount: f64,
+) -> Result<RequestedAmountResponse, PaymentPlanError> {
+    calc::solver::calculate_requested_amount(params, installment_amount)
+}
+
+/// Finds the monthly interest rate of the plan with `params.requested_amount` and `params.installments`
+/// invoices that reaches the target and returns that plan. It always uses the Iterative provider.
+pub fn calculate_interest_rate(
+    params: Params,
+    target: InterestRateTarget,
+) -> Result<Response, PaymentPlanError> {
+    calc::solver::calculate_interest_rate(params, target)
+}
+
+/// Calculates the plan with one installment due on each of `params.due_dates`, sized by `params.weights`
+/// and with the balloon of `params.params`. It always uses the Iterative provider and the minimum
+/// installment and maximum total amounts of the params are not considered.
+pub fn calculate_custom_payment_plan(
+    params: &CustomScheduleParams,
+) -> Result<Response, PaymentPlanError> {
+    calculate_custom_payment_plan_with_calendar(params, &BrazilianBankHolidays)
+}
+
+/// The same plan as `calculate_custom_payment_plan`, moving the due dates to the business days of `calendar`.
+pub fn calculate_custom_payment_plan_with_calendar(
+    params: &CustomScheduleParams,
+    calendar: &dyn HolidayCalendar,
+) -> Result<Response, PaymentPlanError> {
+    Iterative.calculate_custom_payment_plan_with_calendar(params, calendar)
+}
+
+pub fn calculate_early_settlement(
+    params: &EarlySettlementParams,
+) -> Result<EarlySettlementResponse, PaymentPlanError> {
+    calculate_early_settlement_with_calendar(params, &BrazilianBankHolidays)
 }
 
-pub fn next_disbursement_date(mut base_date: chrono::NaiveDate) -> chrono::NaiveDate {
+/// The same settlement as `calculate_early_settlement`, counting the business days of `calendar`.
+pub fn calculate_early_settlement_with_calendar(
+    params: &EarlySettlementParams,
+    calendar: &dyn HolidayCalendar,
+) -> Result<EarlySettlementResponse, PaymentPlanError> {
+    calc::settlement::calculate_early_settlement_with_calendar(params, calendar)
+}
+
+/// The updated amount due of an invoice paid after its due date, with the fine,
+/// the moratory and remunerative interest and the additional IOF.
+pub fn calculate_late_charges(
+    params: &LateChargesParams,
+) -> Result<LateChargesResponse, PaymentPlanError> {
+    calc::late_charges::calculate_late_charges(params)
+}
+
+/// Applies a partial prepayment to the plan calculated with `params.params`
+/// and regenerates the invoices left according to the strategy.
+/// The plan is always calculated by the Iterative provider, the only one with the invoice schedule.
+pub fn calculate_prepayment(
+    params: PrepaymentParams,
+) -> Result<PrepaymentResponse, PaymentPlanError> {
+    calculate_prepayment_with_calendar(params, &BrazilianBankHolidays)
+}
+
+/// The same prepayment as `calculate_prepayment`, with the plan and the days on the business days of `calendar`.
+pub fn calculate_prepayment_with_calendar(
+    params: PrepaymentParams,
+    calendar: &dyn HolidayCalendar,
+) -> Result<PrepaymentResponse, PaymentPlanError> {
+    let installments = params.params.installments;
+    let plan = Iterative
+        .calculate_payment_plan_with_calendar(params.params, calendar)?
+        .into_iter()
+        .find(|plan| plan.installment == installments)
+        .ok_or(PaymentPlanError::InvalidNumberOfInstallments)?;
+
+    calc::prepayment::calculate_prepayment_with_calendar(&plan, &params, calendar)
+}
+
+pub fn next_disbursement_date(base_date: chrono::NaiveDate) -> chrono::NaiveDate {
+    next_disbursement_date_with_calendar(base_date, &BrazilianBankHolidays)
+}
+
+pub fn next_disbursement_date_with_calendar(
+    mut base_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
     let today = chrono::Local::now().date_naive();
     if base_date == today {
         base_date = util::add_days(base_date, 1);
     }
 
-    return util::get_next_business_day(base_date);
+    return util::get_next_business_day(base_date, calendar);
 }
 
 pub fn disbursement_date_range(
     base_date: chrono::NaiveDate,
     days: u32,
 ) -> (chrono::NaiveDate, chrono::NaiveDate) {
-    let start_date = next_disbursement_date(base_date);
-
-    let mut end_date = start_date;
-    let mut i = 1;
-    while i < days {
-        end_date = util::add_days(end_date, 1);
-        if util::is_business_day(end_date) {
-            i += 1;
-        }
-    }
+    disbursement_date_range_with_calendar(base_date, days, &BrazilianBankHolidays)
+}
+
+pub fn disbursement_date_range_with_calendar(
+    base_date: chrono::NaiveDate,
+    days: u32,
+    calendar: &dyn HolidayCalendar,
+) -> (chrono::NaiveDate, chrono::NaiveDate) {
+    let start_date = next_disbursement_date_with_calendar(base_date, calendar);
+    let end_date = util::add_business_days(start_date, days.saturating_sub(1) as i64, calendar);
 
     return (start_date, end_date);
 }
@@ -172,7 +900,53 @@ pub fn get_non_business_days_between(
     start_date: chrono::NaiveDate,
     end_date: chrono::NaiveDate,
 ) -> Vec<chrono::NaiveDate> {
-    return util::get_non_business_days_between(start_date, end_date);
+    get_non_business_days_between_with_calendar(start_date, end_date, &BrazilianBankHolidays)
+}
+
+pub fn get_non_business_days_between_with_calendar(
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> Vec<chrono::NaiveDate> {
+    return util::get_non_business_days_between(start_date, end_date, calendar);
+}
+
+pub fn is_business_day(date: chrono::NaiveDate, calendar: &dyn HolidayCalendar) -> bool {
+    util::is_business_day(date, calendar)
+}
+
+pub fn get_next_business_day(
+    date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
+    util::get_next_business_day(date, calendar)
+}
+
+/// The business days after `start_date` up to `end_date` (inclusive).
+pub fn diff_in_business_days(
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> i64 {
+    util::diff_in_business_days(start_date, end_date, calendar)
+}
+
+/// The business days after `start_date` up to `end_date` (inclusive), negative when `end_date` is before it.
+pub fn business_days_between(
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> i64 {
+    util::business_days_between(start_date, end_date, calendar)
+}
+
+/// The `days`-th business day after `date` (before it when negative), `date` itself for 0.
+pub fn add_business_days(
+    date: chrono::NaiveDate,
+    days: i64,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
+    util::add_business_days(date, days, calendar)
 }
 
 #[cfg(test)]
@@ -212,4 +986,99 @@ mod test {
         ];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_business_day_arithmetic() {
+        let calendar = super::BrazilianBankHolidays;
+        let friday = chrono::NaiveDate::from_ymd_opt(2078, 11, 11).unwrap();
+        let wednesday = chrono::NaiveDate::from_ymd_opt(2078, 11, 16).unwrap();
+
+        assert_eq!(
+            super::business_days_between(friday, wednesday, &calendar),
+            2
+        );
+        assert_eq!(
+            super::business_days_between(wednesday, friday, &calendar),
+            -2
+        );
+        assert_eq!(super::add_business_days(friday, 2, &calendar), wednesday);
+        assert_eq!(super::add_business_days(wednesday, -2, &calendar), friday);
+
+        // After the index the calendar is walked, with the same results
+        let friday = chrono::NaiveDate::from_ymd_opt(2150, 2, 20).unwrap();
+        let after_carnival = super::add_business_days(friday, 3, &calendar);
+        assert_eq!(
+            after_carnival,
+            chrono::NaiveDate::from_ymd_opt(2150, 2, 27).unwrap()
+        );
+        assert_eq!(
+            super::business_days_between(friday, after_carnival, &calendar),
+            3
+        );
+        assert_eq!(
+            super::add_business_days(after_carnival, -3, &calendar),
+            friday
+        );
+    }
+
+    #[test]
+    fn test_next_disbursement_date_with_calendar() {
+        // The day after Carnival is a closure
+        let calendar =
+            crate::calendar::HolidayList::new(vec![
+                chrono::NaiveDate::from_ymd_opt(2078, 2, 16).unwrap()
+            ])
+            .on_top_of(crate::calendar::BrazilianBankHolidays);
+        let base_date = chrono::NaiveDate::from_ymd_opt(2078, 2, 12).unwrap();
+        let result = super::next_disbursement_date_with_calendar(base_date, &calendar);
+        let expected = chrono::NaiveDate::from_ymd_opt(2078, 2, 17).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_calculate_payment_plan_with_provider() {
+        let params = crate::Params::builder()
+            .requested_amount(3883.48)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap())
+            .installments(12)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0449)
+            .build()
+            .unwrap();
+
+        let default = super::calculate_payment_plan(params).unwrap();
+        let by_default =
+            super::calculate_payment_plan_with(super::Provider::default(), params).unwrap();
+        let iterative =
+            super::calculate_payment_plan_with(super::Provider::Iterative, params).unwrap();
+        let simple = super::calculate_payment_plan_with(super::Provider::Simple, params).unwrap();
+
+        // The default provider is the one of the cargo features
+        #[cfg(not(feature = "simple"))]
+        assert_eq!(super::Provider::default(), super::Provider::Iterative);
+        assert_eq!(default, by_default);
+        assert_eq!(iterative.len(), 12);
+        assert_eq!(simple.len(), 12);
+        assert_ne!(simple[11].total_iof, iterative[11].total_iof);
+
+        // Only the Iterative provider moves the dates to the business days of a calendar
+        let calendar = super::calendar::BrazilianBankHolidays;
+        let with_calendar = super::calculate_payment_plan_with_calendar_with(
+            super::Provider::Iterative,
+            params,
+            &calendar,
+        )
+        .unwrap();
+        assert_eq!(with_calendar, iterative);
+        let error = super::calculate_payment_plan_with_calendar_with(
+            super::Provider::Simple,
+            params,
+            &calendar,
+        )
+        .unwrap_err();
+        assert_eq!(error, super::PaymentPlanError::UnsupportedHolidayCalendar);
+        assert_eq!(error.code(), 111);
+    }
 }
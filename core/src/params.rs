@@ -0,0 +1,351 @@
+use crate::{
+    err::{FieldError, PaymentPlanError},
+    AmortizationSystem, BusinessDayConvention, DueDayPolicy, GracePeriod, IofConvergence, Params,
+    Periodicity, RoundingPolicy,
+};
+
+impl Params {
+    pub fn builder() -> ParamsBuilder {
+        ParamsBuilder::default()
+    }
+
+    /*
+        Checks the domain of every field and the relations between them, returning all the violations at once:
+            - The amounts must be numbers, the requested amount greater than 0 and the limits not negative.
+            - The rates and percentages are fractions (0.0-1.0), the debit service percentage is 0-100.
+            - The balloon is at least 0 and less than the whole principal.
+            - The fixed due day is 1-31.
+            - The first payment date is after the disbursement date.
+            - The IOF tolerance is not negative and there are at least 2 iterations to compare.
+    */
+    pub fn validate(&self) -> Result<(), PaymentPlanError> {
+        let mut errors = Vec::new();
+
+        if self.requested_amount <= 0.0 || !self.requested_amount.is_finite() {
+            errors.push(FieldError::new(
+                "requested_amount",
+                self.requested_amount,
+                "must be greater than 0",
+            ));
+        }
+        if self.installments == 0 {
+            errors.push(FieldError::new(
+                "installments",
+                self.installments,
+                "must be greater than 0",
+            ));
+        }
+        if self.debit_service_percentage > 100 {
+            errors.push(FieldError::new(
+                "debit_service_percentage",
+                self.debit_service_percentage,
+                "must be between 0 and 100",
+            ));
+        }
+
+        let fractions = [
+            ("mdr", self.mdr),
+            ("tac_percentage", self.tac_percentage),
+            ("iof_overall", self.iof_overall),
+            ("iof_percentage", self.iof_percentage),
+            ("interest_rate", self.interest_rate),
+        ];
+        for (field, value) in fractions {
+            if !(0.0..=1.0).contains(&value) {
+                errors.push(FieldError::new(field, value, "must be between 0 and 1"));
+            }
+        }
+
+        if self.min_installment_amount.is_nan() || self.min_installment_amount < 0.0 {
+            errors.push(FieldError::new(
+                "min_installment_amount",
+                self.min_installment_amount,
+                "must not be negative",
+            ));
+        }
+        if self.max_total_amount.is_nan() || self.max_total_amount <= 0.0 {
+            errors.push(FieldError::new(
+                "max_total_amount",
+                self.max_total_amount,
+                "must be greater than 0",
+            ));
+        }
+        if !(0.0..1.0).contains(&self.balloon_percentage) {
+            errors.push(FieldError::new(
+                "balloon_percentage",
+                self.balloon_percentage,
+                "must be at least 0 and less than 1",
+            ));
+        }
+        if let DueDayPolicy::FixedDay(day) = self.due_day_policy {
+            if !self.due_day_policy.is_valid() {
+                errors.push(FieldError::new(
+                    "due_day_policy",
+                    day,
+                    "must be between 1 and 31",
+                ));
+            }
+        }
+        if self.first_payment_date <= self.disbursement_date {
+            errors.push(FieldError::new(
+                "first_payment_date",
+                self.first_payment_date,
+                "must be after the disbursement_date",
+            ));
+        }
+
+        let convergence = self.iof_convergence;
+        if convergence.tolerance.is_nan() || convergence.tolerance < 0.0 {
+            errors.push(FieldError::new(
+                "iof_convergence.tolerance",
+                convergence.tolerance,
+                "must not be negative",
+            ));
+        }
+        if convergence.max_iterations < 2 {
+            errors.push(FieldError::new(
+                "iof_convergence.max_iterations",
+                convergence.max_iterations,
+                "must be at least 2",
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(PaymentPlanError::InvalidParams(errors))
+        }
+    }
+}
+
+/*
+    Builds `Params` checked by `Params::validate`.
+    The fields not set keep the default of `Params`, but the maximum total amount, which has no limit.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct ParamsBuilder {
+    params: Params,
+}
+
+impl Default for ParamsBuilder {
+    fn default() -> Self {
+        ParamsBuilder {
+            params: Params {
+                max_total_amount: f64::MAX,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ParamsBuilder {
+    pub fn requested_amount(mut self, requested_amount: f64) -> Self {
+        self.params.requested_amount = requested_amount;
+        self
+    }
+
+    pub fn first_payment_date(mut self, first_payment_date: chrono::NaiveDate) -> Self {
+        self.params.first_payment_date = first_payment_date;
+        self
+    }
+
+    pub fn disbursement_date(mut self, disbursement_date: chrono::NaiveDate) -> Self {
+        self.params.disbursement_date = disbursement_date;
+        self
+    }
+
+    pub fn installments(mut self, installments: u32) -> Self {
+        self.params.installments = installments;
+        self
+    }
+
+    pub fn debit_service_percentage(mut self, debit_service_percentage: u16) -> Self {
+        self.params.debit_service_percentage = debit_service_percentage;
+        self
+    }
+
+    pub fn mdr(mut self, mdr: f64) -> Self {
+        self.params.mdr = mdr;
+        self
+    }
+
+    pub fn tac_percentage(mut self, tac_percentage: f64) -> Self {
+        self.params.tac_percentage = tac_percentage;
+        self
+    }
+
+    pub fn iof_overall(mut self, iof_overall: f64) -> Self {
+        self.params.iof_overall = iof_overall;
+        self
+    }
+
+    pub fn iof_percentage(mut self, iof_percentage: f64) -> Self {
+        self.params.iof_percentage = iof_percentage;
+        self
+    }
+
+    pub fn interest_rate(mut self, interest_rate: f64) -> Self {
+        self.params.interest_rate = interest_rate;
+        self
+    }
+
+    pub fn min_installment_amount(mut self, min_installment_amount: f64) -> Self {
+        self.params.min_installment_amount = min_installment_amount;
+        self
+    }
+
+    pub fn max_total_amount(mut self, max_total_amount: f64) -> Self {
+        self.params.max_total_amount = max_total_amount;
+        self
+    }
+
+    pub fn disbursement_only_on_business_days(mut self, on_business_days: bool) -> Self {
+        self.params.disbursement_only_on_business_days = on_business_days;
+        self
+    }
+
+    pub fn amortization_system(mut self, amortization_system: AmortizationSystem) -> Self {
+        self.params.amortization_system = amortization_system;
+        self
+    }
+
+    pub fn grace_period(mut self, grace_period: GracePeriod) -> Self {
+        self.params.grace_period = grace_period;
+        self
+    }
+
+    pub fn balloon_percentage(mut self, balloon_percentage: f64) -> Self {
+        self.params.balloon_percentage = balloon_percentage;
+        self
+    }
+
+    pub fn periodicity(mut self, periodicity: Periodicity) -> Self {
+        self.params.periodicity = periodicity;
+        self
+    }
+
+    pub fn due_day_policy(mut self, due_day_policy: DueDayPolicy) -> Self {
+        self.params.due_day_policy = due_day_policy;
+        self
+    }
+
+    pub fn rounding_policy(mut self, rounding_policy: RoundingPolicy) -> Self {
+        self.params.rounding_policy = rounding_policy;
+        self
+    }
+
+    pub fn business_day_convention(mut self, convention: BusinessDayConvention) -> Self {
+        self.params.business_day_convention = convention;
+        self
+    }
+
+    pub fn iof_convergence(mut self, iof_convergence: IofConvergence) -> Self {
+        self.params.iof_convergence = iof_convergence;
+        self
+    }
+
+    pub fn build(self) -> Result<Params, PaymentPlanError> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        err::{FieldError, PaymentPlanError},
+        DueDayPolicy, IofConvergence, Params,
+    };
+
+    fn builder() -> super::ParamsBuilder {
+        Params::builder()
+            .requested_amount(3883.48)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2025, 8, 21).unwrap())
+            .installments(12)
+            .mdr(0.05)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0449)
+            .min_installment_amount(100.0)
+    }
+
+    #[test]
+    fn test_params_builder() {
+        let params = builder().build().unwrap();
+
+        assert_eq!(params.requested_amount, 3883.48);
+        assert_eq!(params.installments, 12);
+        assert_eq!(params.interest_rate, 0.0449);
+        assert_eq!(params.max_total_amount, f64::MAX);
+        assert!(crate::calculate_payment_plan(params).is_ok());
+    }
+
+    #[test]
+    fn test_params_validate_all_violations() {
+        let resp = builder()
+            .requested_amount(f64::NAN)
+            .debit_service_percentage(101)
+            .mdr(1.5)
+            .interest_rate(-0.01)
+            .balloon_percentage(1.0)
+            .due_day_policy(DueDayPolicy::FixedDay(32))
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2025, 9, 18).unwrap())
+            .build();
+
+        let fields = match resp {
+            Err(PaymentPlanError::InvalidParams(errors)) => errors
+                .iter()
+                .map(|error| error.field)
+                .collect::<Vec<&str>>(),
+            _ => panic!("expected the invalid params"),
+        };
+
+        assert_eq!(
+            fields,
+            vec![
+                "requested_amount",
+                "debit_service_percentage",
+                "mdr",
+                "interest_rate",
+                "balloon_percentage",
+                "due_day_policy",
+                "first_payment_date",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_params_validate_field_error() {
+        let resp = builder().installments(0).build().unwrap_err();
+
+        assert_eq!(
+            resp,
+            PaymentPlanError::InvalidParams(vec![FieldError {
+                field: "installments",
+                value: "0".to_string(),
+                constraint: "must be greater than 0",
+            }])
+        );
+        assert_eq!(
+            resp.to_string(),
+            "Invalid params: installments (0) must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_params_validate_iof_convergence() {
+        let resp = builder()
+            .iof_convergence(IofConvergence {
+                tolerance: -0.01,
+                max_iterations: 1,
+            })
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            resp.to_string(),
+            "Invalid params: iof_convergence.tolerance (-0.01) must not be negative, iof_convergence.max_iterations (1) must be at least 2"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+/*
+    The building blocks of the providers, to implement a `PaymentPlan` outside of the crate.
+    A provider only calculates the plans, `PaymentPlan::calculate_down_payment_plan` is calculated with them:
+        - `prepare_calculation`: the due dates and the installment factors of the plans.
+        - `calculate_iof`: the total IOF of a plan.
+        - `calculate_amounts`: the contract, installment and merchant amounts of a plan.
+        - `prepare_xirr_params`, `calculate_eir_monthly` and `calculate_tec_monthly`: the rates of a plan.
+    The blocks of the Iterative provider work with exact decimals on its `IterativeParams`:
+        - `IterativeTimeline`: the due dates, days and factors of the longest plan, shared by the shorter ones.
+        - `calculate_iterative_installments`: the installments of a plan on the timeline.
+        - `converge_iterative_iof`: the IOF of a plan, recalculated with it in the main value until it converges.
+        - `calculate_iterative_iof` and `calculate_iterative_installment_iofs`: a single pass of the IOF, total and per installment.
+*/
+pub use xirr::Payment;
+
+pub use crate::calc::{
+    inner_xirr::{
+        eir::calculate_eir_monthly, periodic_rate, prepare_xirr_params,
+        prepare_xirr_params_with_amounts, tec::calculate_tec_monthly,
+    },
+    providers::{
+        iterative::{
+            installment::{calc as calculate_iterative_installments, InstallmentData},
+            iof::{
+                calc as calculate_iterative_iof,
+                calc_installments as calculate_iterative_installment_iofs,
+                converge as converge_iterative_iof, ConvergedIof as IterativeConvergedIof,
+            },
+            timeline::Timeline as IterativeTimeline,
+            InnerParams as IterativeParams, Iterative,
+        },
+        simple::{
+            amounts::{calculate_amounts, AmountsResponse},
+            iof::calculate_iof,
+            prepare::{prepare_calculation, PreparedCalculation},
+            Simple, CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+        },
+    },
+    PaymentPlan,
+};
+
+#[cfg(test)]
+mod test {
+    use rust_decimal::Decimal;
+
+    use super::{
+        calculate_amounts, calculate_eir_monthly, calculate_iof,
+        calculate_iterative_installment_iofs, calculate_iterative_installments,
+        calculate_iterative_iof, calculate_tec_monthly, converge_iterative_iof,
+        prepare_calculation, prepare_xirr_params, Iterative, IterativeParams, IterativeTimeline,
+        PaymentPlan, Simple, CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+    };
+    use crate::{
+        calendar::BrazilianBankHolidays,
+        err::PaymentPlanError,
+        util::{to_decimal, to_f64},
+        DownPaymentParams, Params, Response,
+    };
+
+    // A provider without the daily IOF, only the overall IOF of the contract
+    struct OverallIofOnly;
+
+    impl PaymentPlan for OverallIofOnly {
+        fn calculate_payment_plan(
+            &self,
+            params: Params,
+        ) -> Result<Vec<Response>, PaymentPlanError> {
+            let prepared_calculations = prepare_calculation(params);
+            let due_dates = prepared_calculations
+                .iter()
+                .map(|calc| calc.due_date)
+                .collect();
+            let customer_debit_service_proportion =
+                1.0 - params.debit_service_percentage as f64 / 100.0;
+
+            let mut responses = Vec::new();
+            for calc in prepared_calculations.iter() {
+                let installments = calc.installment as f64;
+                let total_iof = calculate_iof(params, Vec::new(), installments);
+                let amounts = calculate_amounts(
+                    params,
+                    calc.accumulated_days_index,
+                    installments,
+                    customer_debit_service_proportion,
+                    total_iof,
+                );
+                let (eir_params, tec_params) = prepare_xirr_params(
+                    calc.installment,
+                    &due_dates,
+                    amounts.calculation_basis_for_effective_interest_rate,
+                    amounts.customer_amount,
+                );
+
+                responses.push(Response {
+                    installment: calc.installment,
+                    due_date: calc.due_date,
+                    installment_amount: amounts.installment_amount,
+                    total_amount: amounts.total_amount,
+                    total_iof,
+                    eir_monthly: calculate_eir_monthly(
+                        params,
+                        eir_params,
+                        customer_debit_service_proportion,
+                        CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+                    )?,
+                    tec_monthly: calculate_tec_monthly(
+                        params,
+                        tec_params,
+                        CALCULATION_BASIS_FOR_EFFECTIVE_INTEREST_RATE,
+                    )?,
+                    ..Default::default()
+                });
+            }
+            Ok(responses)
+        }
+    }
+
+    #[test]
+    fn test_custom_provider() {
+        let params = Params::builder()
+            .requested_amount(2900.0)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap())
+            .installments(6)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0235)
+            .build()
+            .unwrap();
+
+        let custom = OverallIofOnly.calculate_payment_plan(params).unwrap();
+        let simple = Simple.calculate_payment_plan(params).unwrap();
+
+        assert_eq!(custom.len(), 6);
+        for (custom, simple) in custom.iter().zip(simple.iter()) {
+            assert_eq!(custom.due_date, simple.due_date);
+            assert!(custom.total_iof < simple.total_iof);
+            assert!(custom.installment_amount < simple.installment_amount);
+            assert!(custom.tec_monthly > 0.0);
+        }
+
+        // The down payment plan of the trait uses the plans of the provider
+        let down_payment = OverallIofOnly
+            .calculate_down_payment_plan(DownPaymentParams {
+                params,
+                requested_amount: 500.0,
+                min_installment_amount: 100.0,
+                first_payment_date: chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap(),
+                installments: 2,
+            })
+            .unwrap();
+
+        assert_eq!(down_payment.len(), 2);
+        assert_eq!(down_payment[1].plans.len(), 6);
+    }
+
+    #[test]
+    fn test_iterative_blocks() {
+        let params = Params::builder()
+            .requested_amount(2900.0)
+            .first_payment_date(chrono::NaiveDate::from_ymd_opt(2022, 4, 30).unwrap())
+            .disbursement_date(chrono::NaiveDate::from_ymd_opt(2022, 3, 30).unwrap())
+            .installments(6)
+            .iof_overall(0.0038)
+            .iof_percentage(0.000082)
+            .interest_rate(0.0235)
+            .build()
+            .unwrap();
+
+        let mut inner_params = IterativeParams::new(params, &BrazilianBankHolidays).unwrap();
+        let timeline = IterativeTimeline::new(&inner_params);
+        assert_eq!(timeline.due_dates().len(), 6);
+
+        // A single pass only has the IOF of the requested amount
+        let data = calculate_iterative_installments(&inner_params, &timeline);
+        let single_pass_iof = calculate_iterative_iof(&inner_params, &data);
+
+        let converged = converge_iterative_iof(&mut inner_params, &timeline).unwrap();
+        assert!(converged.iof() > single_pass_iof);
+        assert_eq!(
+            inner_params.main_value(),
+            to_decimal(params.requested_amount) + converged.iof()
+        );
+        assert_eq!(converged.amounts_without_iof(), data.amounts());
+
+        let plan = Iterative
+            .calculate_payment_plan(params)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(to_f64(converged.iof()), plan.total_iof);
+        assert_eq!(converged.iterations(), plan.iof_iterations);
+        assert_eq!(to_f64(converged.data().amount()), plan.installment_amount);
+
+        let iofs = calculate_iterative_installment_iofs(&inner_params, converged.data());
+        assert_eq!(iofs.len(), 6);
+        assert!(iofs.iter().all(|iof| *iof > Decimal::ZERO));
+    }
+}
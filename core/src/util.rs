@@ -1,7 +1,18 @@
 use chrono::{Datelike, Days, Months};
+use rust_decimal::{
+    prelude::{FromPrimitive, ToPrimitive},
+    Decimal, RoundingStrategy,
+};
+
+use std::sync::OnceLock;
+
+use crate::{
+    calendar::{BusinessDayIndex, HolidayCalendar, RuleCalendar},
+    BusinessDayConvention, DueDayPolicy, Periodicity, RoundingMode,
+};
 
 #[allow(deprecated)]
-const BRAZILIAN_BANK_HOLIDAYS: [chrono::NaiveDate; 701] = [
+pub(crate) const BRAZILIAN_BANK_HOLIDAYS: [chrono::NaiveDate; 701] = [
     chrono::NaiveDate::from_ymd(2025, 3, 3),
     chrono::NaiveDate::from_ymd(2025, 3, 4),
     chrono::NaiveDate::from_ymd(2025, 4, 18),
@@ -705,7 +716,30 @@ const BRAZILIAN_BANK_HOLIDAYS: [chrono::NaiveDate; 701] = [
     chrono::NaiveDate::from_ymd(2078, 12, 25),
 ];
 
+static BRAZILIAN_HOLIDAY_RULES: OnceLock<RuleCalendar> = OnceLock::new();
+
+static BRAZILIAN_BUSINESS_DAY_INDEX: OnceLock<BusinessDayIndex> = OnceLock::new();
+
+// The business days of the Brazilian calendar from 2000 to 2100, indexed on the first use
+pub fn brazilian_business_day_index() -> &'static BusinessDayIndex {
+    BRAZILIAN_BUSINESS_DAY_INDEX.get_or_init(|| {
+        BusinessDayIndex::new(
+            &crate::calendar::BrazilianBankHolidays,
+            chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2100, 12, 31).unwrap(),
+        )
+    })
+}
+
+// The dates after the table are calculated by the rules, so the holidays never run out
 pub fn is_holiday(date: chrono::NaiveDate) -> bool {
+    let last = BRAZILIAN_BANK_HOLIDAYS[BRAZILIAN_BANK_HOLIDAYS.len() - 1];
+    if date > last {
+        return BRAZILIAN_HOLIDAY_RULES
+            .get_or_init(RuleCalendar::brazilian)
+            .is_holiday(date);
+    }
+
     let result = BRAZILIAN_BANK_HOLIDAYS.binary_search(&date);
     match result {
         Ok(_) => true,
@@ -713,11 +747,11 @@ pub fn is_holiday(date: chrono::NaiveDate) -> bool {
     }
 }
 
-pub fn is_business_day(date: chrono::NaiveDate) -> bool {
+pub fn is_business_day(date: chrono::NaiveDate, calendar: &dyn HolidayCalendar) -> bool {
     if date.weekday() == chrono::Weekday::Sat || date.weekday() == chrono::Weekday::Sun {
         return false;
     }
-    if is_holiday(date) {
+    if calendar.is_holiday(date) {
         return false;
     }
     return true;
@@ -728,15 +762,129 @@ pub fn add_months(date: chrono::NaiveDate, months: u32) -> chrono::NaiveDate {
     for _ in 0..months {
         due_date = due_date.checked_add_months(Months::new(1)).unwrap();
     }
-    return due_date;
+    due_date
 }
 
-pub fn add_days(date: chrono::NaiveDate, days: i64) -> chrono::NaiveDate {
+pub fn sub_months(date: chrono::NaiveDate, months: u32) -> chrono::NaiveDate {
     let mut due_date = date;
-    for _ in 0..days {
-        due_date = due_date.checked_add_days(Days::new(1)).unwrap();
+    for _ in 0..months {
+        due_date = due_date.checked_sub_months(Months::new(1)).unwrap();
+    }
+    due_date
+}
+
+/*
+    The months are added at once, so the day of the month is only limited by the last month
+    (Jan 31 + 2 months is Mar 31, and not Mar 28 as with `add_months`).
+*/
+fn add_anchored_months(date: chrono::NaiveDate, months: u32) -> chrono::NaiveDate {
+    date.checked_add_months(Months::new(months)).unwrap()
+}
+
+fn sub_anchored_months(date: chrono::NaiveDate, months: u32) -> chrono::NaiveDate {
+    date.checked_sub_months(Months::new(months)).unwrap()
+}
+
+fn with_day_or_last_day(date: chrono::NaiveDate, day: u32) -> chrono::NaiveDate {
+    let next_month = add_months(date.with_day(1).unwrap(), 1);
+    let last_day = next_month.pred_opt().unwrap().day();
+    date.with_day(day.min(last_day)).unwrap()
+}
+
+fn apply_due_day_policy(
+    date: chrono::NaiveDate,
+    periodicity: Periodicity,
+    due_day_policy: DueDayPolicy,
+) -> chrono::NaiveDate {
+    if periodicity != Periodicity::Monthly {
+        return date;
+    }
+    match due_day_policy {
+        DueDayPolicy::Stepped | DueDayPolicy::Anchored => date,
+        DueDayPolicy::EndOfMonth => with_day_or_last_day(date, 31),
+        DueDayPolicy::FixedDay(day) => with_day_or_last_day(date, day),
     }
-    return due_date;
+}
+
+// The first date on or after the first payment date that follows the due day policy
+pub fn first_due_date(
+    first_payment_date: chrono::NaiveDate,
+    periodicity: Periodicity,
+    due_day_policy: DueDayPolicy,
+) -> chrono::NaiveDate {
+    let due_date = apply_due_day_policy(first_payment_date, periodicity, due_day_policy);
+    if due_date >= first_payment_date {
+        return due_date;
+    }
+    apply_due_day_policy(
+        add_months(first_payment_date, 1),
+        periodicity,
+        due_day_policy,
+    )
+}
+
+// The due date `periods` installments after the first one (see `first_due_date`)
+pub fn add_due_periods(
+    first_due_date: chrono::NaiveDate,
+    periods: u32,
+    periodicity: Periodicity,
+    due_day_policy: DueDayPolicy,
+) -> chrono::NaiveDate {
+    let due_date = match (periodicity, due_day_policy) {
+        (Periodicity::Monthly, DueDayPolicy::Anchored) => {
+            add_anchored_months(first_due_date, periods)
+        }
+        _ => add_periods(first_due_date, periods, periodicity),
+    };
+    apply_due_day_policy(due_date, periodicity, due_day_policy)
+}
+
+// The due date `periods` installments before the first one (see `first_due_date`)
+pub fn sub_due_periods(
+    first_due_date: chrono::NaiveDate,
+    periods: u32,
+    periodicity: Periodicity,
+    due_day_policy: DueDayPolicy,
+) -> chrono::NaiveDate {
+    let due_date = match (periodicity, due_day_policy) {
+        (Periodicity::Monthly, DueDayPolicy::Anchored) => {
+            sub_anchored_months(first_due_date, periods)
+        }
+        _ => sub_periods(first_due_date, periods, periodicity),
+    };
+    apply_due_day_policy(due_date, periodicity, due_day_policy)
+}
+
+fn add_periods(
+    date: chrono::NaiveDate,
+    periods: u32,
+    periodicity: Periodicity,
+) -> chrono::NaiveDate {
+    match periodicity {
+        Periodicity::Monthly => add_months(date, periods),
+        _ => date
+            .checked_add_days(Days::new(periodicity.days() as u64 * periods as u64))
+            .unwrap(),
+    }
+}
+
+fn sub_periods(
+    date: chrono::NaiveDate,
+    periods: u32,
+    periodicity: Periodicity,
+) -> chrono::NaiveDate {
+    match periodicity {
+        Periodicity::Monthly => sub_months(date, periods),
+        _ => date
+            .checked_sub_days(Days::new(periodicity.days() as u64 * periods as u64))
+            .unwrap(),
+    }
+}
+
+// The days are added at once, a negative number of days keeps the date
+pub fn add_days(date: chrono::NaiveDate, days: i64) -> chrono::NaiveDate {
+    date.checked_add_days(Days::new(days.max(0) as u64))
+        .unwrap()
 }
 
 pub fn round_decimal_cases(value: f64, round: i32) -> f64 {
@@ -744,11 +892,42 @@ pub fn round_decimal_cases(value: f64, round: i32) -> f64 {
     (value * factor).round() / factor
 }
 
-pub fn get_next_business_day(date: chrono::NaiveDate) -> chrono::NaiveDate {
+pub fn round_decimal_cases_with(value: f64, round: i32, mode: RoundingMode) -> f64 {
+    let factor = 10f64.powi(round);
+    match mode {
+        RoundingMode::HalfUp => (value * factor).round() / factor,
+        RoundingMode::HalfEven => (value * factor).round_ties_even() / factor,
+        RoundingMode::Truncate => (value * factor).trunc() / factor,
+    }
+}
+
+// The rounding of `round_decimal_cases_with`, without the binary representation errors
+pub fn round_decimal_with(value: Decimal, round: u32, mode: RoundingMode) -> Decimal {
+    let strategy = match mode {
+        RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+        RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+        RoundingMode::Truncate => RoundingStrategy::ToZero,
+    };
+    value.round_dp_with_strategy(round, strategy)
+}
+
+// The shortest decimal that converts back to the same f64 (0.1 is 0.1 and not 0.1000000000000000055...)
+pub fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+pub fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+pub fn get_next_business_day(
+    date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
     let mut date = date;
 
     loop {
-        if is_business_day(date) {
+        if is_business_day(date, calendar) {
             break;
         }
         date = date.checked_add_days(Days::new(1)).unwrap();
@@ -757,13 +936,59 @@ pub fn get_next_business_day(date: chrono::NaiveDate) -> chrono::NaiveDate {
     return date;
 }
 
-pub fn diff_in_business_days(start_date: chrono::NaiveDate, end_date: chrono::NaiveDate) -> i64 {
+pub fn get_previous_business_day(
+    date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
+    let mut date = date;
+
+    while !is_business_day(date, calendar) {
+        date = date.checked_sub_days(Days::new(1)).unwrap();
+    }
+
+    date
+}
+
+pub fn adjust_to_business_day(
+    date: chrono::NaiveDate,
+    convention: BusinessDayConvention,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
+    match convention {
+        BusinessDayConvention::Following => get_next_business_day(date, calendar),
+        BusinessDayConvention::ModifiedFollowing => {
+            let next = get_next_business_day(date, calendar);
+            if next.month() == date.month() {
+                next
+            } else {
+                get_previous_business_day(date, calendar)
+            }
+        }
+        BusinessDayConvention::Preceding => get_previous_business_day(date, calendar),
+    }
+}
+
+pub fn diff_in_business_days(
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> i64 {
+    if end_date <= start_date {
+        return 0;
+    }
+    if let Some(diff) = calendar
+        .business_day_index()
+        .and_then(|index| index.business_days_between(start_date, end_date))
+    {
+        return diff;
+    }
+
     let mut diff = 0;
     let mut date = start_date;
 
     while date < end_date {
         date = date.checked_add_days(Days::new(1)).unwrap();
-        if is_business_day(date) {
+        if is_business_day(date, calendar) {
             diff += 1;
         }
     }
@@ -774,12 +999,20 @@ pub fn diff_in_business_days(start_date: chrono::NaiveDate, end_date: chrono::Na
 pub fn get_non_business_days_between(
     start_date: chrono::NaiveDate,
     end_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
 ) -> Vec<chrono::NaiveDate> {
+    if let Some(days) = calendar
+        .business_day_index()
+        .and_then(|index| index.non_business_days_between(start_date, end_date))
+    {
+        return days.to_vec();
+    }
+
     let mut holidays = Vec::new();
     let mut date = start_date;
 
     while date <= end_date {
-        if !is_business_day(date) {
+        if !is_business_day(date, calendar) {
             holidays.push(date);
         }
         date = date.checked_add_days(Days::new(1)).unwrap();
@@ -787,3 +1020,44 @@ pub fn get_non_business_days_between(
 
     return holidays;
 }
+
+// The business days after `start_date` up to `end_date` (inclusive), negative when `end_date` is before it
+pub fn business_days_between(
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    calendar: &dyn HolidayCalendar,
+) -> i64 {
+    if end_date < start_date {
+        return -diff_in_business_days(end_date, start_date, calendar);
+    }
+    diff_in_business_days(start_date, end_date, calendar)
+}
+
+// The `days`-th business day after `date` (before it when negative), `date` itself for 0
+pub fn add_business_days(
+    date: chrono::NaiveDate,
+    days: i64,
+    calendar: &dyn HolidayCalendar,
+) -> chrono::NaiveDate {
+    if let Some(date) = calendar
+        .business_day_index()
+        .and_then(|index| index.add_business_days(date, days))
+    {
+        return date;
+    }
+
+    let mut date = date;
+    let mut left = days.abs();
+    while left > 0 {
+        date = if days > 0 {
+            date.succ_opt().unwrap()
+        } else {
+            date.pred_opt().unwrap()
+        };
+        if is_business_day(date, calendar) {
+            left -= 1;
+        }
+    }
+
+    date
+}
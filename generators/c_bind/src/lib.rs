@@ -1,6 +1,11 @@
 use crate::{
-    params::{DownPaymentParams, Params},
-    response::{DownPaymentResponse, Response},
+    params::{
+        CustomScheduleParams, DownPaymentParams, EarlySettlementParams, LateChargesParams, Params,
+        Provider,
+    },
+    response::{
+        DownPaymentResponse, EarlySettlementResponse, LateChargesResponse, Response, SelectedPlans,
+    },
 };
 use ::safer_ffi::prelude::*;
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -8,24 +13,67 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 mod params;
 mod response;
 
+// The stable codes of `PaymentPlanError::code`, the hundreds are the kind of the error
 #[derive_ReprC]
-#[repr(u8)]
+#[repr(u16)]
 pub enum PaymentPlanResult {
     Success = 0,
-    InvalidParams = 1,
-    CalculationError = 2,
+    InvalidParams = 100,
+    InvalidNumberOfInstallments = 101,
+    InvalidRequestedAmount = 102,
+    InvalidInterestRate = 103,
+    InvalidPrepaymentAmount = 104,
+    InvalidInstallmentAmount = 105,
+    InvalidInstallmentWeights = 106,
+    InvalidBalloonPercentage = 107,
+    InvalidDueDay = 108,
+    InvalidHolidayCalendar = 109,
+    InvalidPlanInvoices = 110,
+    UnsupportedHolidayCalendar = 111,
+    InvalidDate = 200,
+    CalculationError = 300,
+    XirCalculationError = 301,
+    IofConvergenceError = 302,
+    UnreachableInstallmentAmount = 400,
+    UnreachableTec = 401,
 }
 
 impl From<core_payment_plan::err::PaymentPlanError> for PaymentPlanResult {
     fn from(value: core_payment_plan::err::PaymentPlanError) -> Self {
+        use core_payment_plan::err::PaymentPlanError;
         match value {
-            core_payment_plan::err::PaymentPlanError::CalculationError(_) => {
-                PaymentPlanResult::CalculationError
+            PaymentPlanError::InvalidParams(_) => PaymentPlanResult::InvalidParams,
+            PaymentPlanError::InvalidNumberOfInstallments => {
+                PaymentPlanResult::InvalidNumberOfInstallments
             }
-            core_payment_plan::err::PaymentPlanError::XirCalculationError(_) => {
-                PaymentPlanResult::CalculationError
+            PaymentPlanError::InvalidRequestedAmount => PaymentPlanResult::InvalidRequestedAmount,
+            PaymentPlanError::InvalidInterestRate => PaymentPlanResult::InvalidInterestRate,
+            PaymentPlanError::InvalidPrepaymentAmount => PaymentPlanResult::InvalidPrepaymentAmount,
+            PaymentPlanError::InvalidInstallmentAmount => {
+                PaymentPlanResult::InvalidInstallmentAmount
             }
-            _ => PaymentPlanResult::InvalidParams,
+            PaymentPlanError::InvalidInstallmentWeights => {
+                PaymentPlanResult::InvalidInstallmentWeights
+            }
+            PaymentPlanError::InvalidBalloonPercentage => {
+                PaymentPlanResult::InvalidBalloonPercentage
+            }
+            PaymentPlanError::InvalidDueDay => PaymentPlanResult::InvalidDueDay,
+            PaymentPlanError::InvalidHolidayCalendar(_) => {
+                PaymentPlanResult::InvalidHolidayCalendar
+            }
+            PaymentPlanError::InvalidPlanInvoices => PaymentPlanResult::InvalidPlanInvoices,
+            PaymentPlanError::UnsupportedHolidayCalendar => {
+                PaymentPlanResult::UnsupportedHolidayCalendar
+            }
+            PaymentPlanError::InvalidDate(_) => PaymentPlanResult::InvalidDate,
+            PaymentPlanError::CalculationError(_) => PaymentPlanResult::CalculationError,
+            PaymentPlanError::XirCalculationError(_) => PaymentPlanResult::XirCalculationError,
+            PaymentPlanError::IofConvergenceError { .. } => PaymentPlanResult::IofConvergenceError,
+            PaymentPlanError::UnreachableInstallmentAmount => {
+                PaymentPlanResult::UnreachableInstallmentAmount
+            }
+            PaymentPlanError::UnreachableTec => PaymentPlanResult::UnreachableTec,
         }
     }
 }
@@ -49,6 +97,27 @@ pub fn calculate_down_payment_plan(
     PaymentPlanResult::Success
 }
 
+/// Calculate the down payment plan with the plans of `provider`.
+/// the pointer on `Vec_DownPaymentResponse_t` must be null because the function will allocate the vector
+///
+/// # Safety: The caller must free the vector using `free_down_payment_response_vec`.
+#[ffi_export]
+pub fn calculate_down_payment_plan_with_provider(
+    params: DownPaymentParams,
+    provider: Provider,
+    out_responses: &mut repr_c::Vec<DownPaymentResponse>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::DownPaymentParams = params.into();
+    let result = match core_payment_plan::calculate_down_payment_plan_with(provider.into(), params)
+    {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    let result: Vec<DownPaymentResponse> = result.into_iter().map(|x| x.into()).collect();
+    *out_responses = result.into();
+    PaymentPlanResult::Success
+}
+
 /// Calculate the payment plan.
 /// the pointer on `Vec_Response_t` must be null because the function will allocate the vector
 ///
@@ -68,6 +137,123 @@ pub fn calculate_payment_plan(
     PaymentPlanResult::Success
 }
 
+/// Calculate the payment plan with `provider`.
+/// the pointer on `Vec_Response_t` must be null because the function will allocate the vector
+///
+/// # Safety: The caller must free the vector using `free_response_vec`.
+#[ffi_export]
+pub fn calculate_payment_plan_with_provider(
+    params: Params,
+    provider: Provider,
+    out_responses: &mut repr_c::Vec<Response>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::Params = params.into();
+    let result = match core_payment_plan::calculate_payment_plan_with(provider.into(), params) {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    let result: Vec<Response> = result.into_iter().map(|x| x.into()).collect();
+    *out_responses = result.into();
+    PaymentPlanResult::Success
+}
+
+/// Calculate the plans of the selected numbers of installments only (ex: 3, 6, 10 and 12, or 10 for a single one).
+/// the pointers on `SelectedPlans_t.plans` and `SelectedPlans_t.excluded` will be allocated by the function
+///
+/// # Safety: The caller must free the response using `free_selected_plans`.
+#[ffi_export]
+pub fn calculate_selected_payment_plans(
+    params: Params,
+    installments: c_slice::Ref<'_, u32>,
+    out_response: Out<'_, SelectedPlans>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::Params = params.into();
+    let result = match core_payment_plan::calculate_selected_payment_plans(
+        params,
+        installments.as_slice(),
+    ) {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    out_response.write(result.into());
+    PaymentPlanResult::Success
+}
+
+/// Calculate the plans of the selected numbers of installments only with `provider`.
+/// the pointers on `SelectedPlans_t.plans` and `SelectedPlans_t.excluded` will be allocated by the function
+///
+/// # Safety: The caller must free the response using `free_selected_plans`.
+#[ffi_export]
+pub fn calculate_selected_payment_plans_with_provider(
+    params: Params,
+    installments: c_slice::Ref<'_, u32>,
+    provider: Provider,
+    out_response: Out<'_, SelectedPlans>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::Params = params.into();
+    let result = match core_payment_plan::calculate_selected_payment_plans_with(
+        provider.into(),
+        params,
+        installments.as_slice(),
+    ) {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    out_response.write(result.into());
+    PaymentPlanResult::Success
+}
+
+/// Calculate the plan with one installment due on each of the due dates, always with the Iterative provider.
+/// the pointer on `Response_t.invoices` will be allocated by the function
+///
+/// # Safety: The caller must free the response using `free_response`.
+#[ffi_export]
+pub fn calculate_custom_payment_plan(
+    params: CustomScheduleParams<'_>,
+    out_response: Out<'_, Response>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::CustomScheduleParams = params.into();
+    let result = match core_payment_plan::calculate_custom_payment_plan(&params) {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    out_response.write(result.into());
+    PaymentPlanResult::Success
+}
+
+/// Calculate the early settlement (payoff) of the open invoices of a plan.
+/// the pointer on `EarlySettlementResponse_t.invoices` will be allocated by the function
+///
+/// # Safety: The caller must free the response using `free_early_settlement_response`.
+#[ffi_export]
+pub fn calculate_early_settlement(
+    params: EarlySettlementParams<'_>,
+    out_response: Out<'_, EarlySettlementResponse>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::EarlySettlementParams = params.into();
+    let result = match core_payment_plan::calculate_early_settlement(&params) {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    out_response.write(result.into());
+    PaymentPlanResult::Success
+}
+
+/// Calculate the updated amount due of an invoice paid after its due date.
+#[ffi_export]
+pub fn calculate_late_charges(
+    params: LateChargesParams,
+    out_response: Out<'_, LateChargesResponse>,
+) -> PaymentPlanResult {
+    let params: core_payment_plan::LateChargesParams = params.into();
+    let result = match core_payment_plan::calculate_late_charges(&params) {
+        Ok(res) => res,
+        Err(err) => return err.into(),
+    };
+    out_response.write(result.into());
+    PaymentPlanResult::Success
+}
+
 // Calculate the next disbursement date.
 #[ffi_export]
 pub fn next_disbursement_date(base_date: i64, result: &mut i64) -> PaymentPlanResult {
@@ -151,6 +337,12 @@ pub fn get_non_business_days_between(
     PaymentPlanResult::Success
 }
 
+/// Free the response allocated by the FFI functions.
+#[ffi_export]
+fn free_response(value: Response) {
+    drop(value);
+}
+
 /// Free the response vector allocated by the FFI functions.
 #[ffi_export]
 fn free_response_vec(value: repr_c::Vec<Response>) {
@@ -163,6 +355,18 @@ fn free_down_payment_response_vec(value: repr_c::Vec<DownPaymentResponse>) {
     drop(value);
 }
 
+/// Free the selected plans allocated by the FFI functions.
+#[ffi_export]
+fn free_selected_plans(value: SelectedPlans) {
+    drop(value);
+}
+
+/// Free the early settlement response allocated by the FFI functions.
+#[ffi_export]
+fn free_early_settlement_response(value: EarlySettlementResponse) {
+    drop(value);
+}
+
 /// Free the i64 vector allocated by the FFI functions.
 #[ffi_export]
 fn free_i64_vec(value: repr_c::Vec<i64>) {
@@ -2,6 +2,165 @@ use ::safer_ffi::prelude::*;
 
 use chrono::{DateTime, Utc};
 
+use crate::response::Invoice;
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum AmortizationSystem {
+    Price = 0,
+    Sac = 1,
+}
+
+impl From<AmortizationSystem> for core_payment_plan::AmortizationSystem {
+    fn from(value: AmortizationSystem) -> Self {
+        match value {
+            AmortizationSystem::Price => core_payment_plan::AmortizationSystem::Price,
+            AmortizationSystem::Sac => core_payment_plan::AmortizationSystem::Sac,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum GracePeriod {
+    Capitalized = 0,
+    InterestOnly = 1,
+}
+
+impl From<GracePeriod> for core_payment_plan::GracePeriod {
+    fn from(value: GracePeriod) -> Self {
+        match value {
+            GracePeriod::Capitalized => core_payment_plan::GracePeriod::Capitalized,
+            GracePeriod::InterestOnly => core_payment_plan::GracePeriod::InterestOnly,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum Periodicity {
+    Monthly = 0,
+    Biweekly = 1,
+    Weekly = 2,
+}
+
+impl From<Periodicity> for core_payment_plan::Periodicity {
+    fn from(value: Periodicity) -> Self {
+        match value {
+            Periodicity::Monthly => core_payment_plan::Periodicity::Monthly,
+            Periodicity::Biweekly => core_payment_plan::Periodicity::Biweekly,
+            Periodicity::Weekly => core_payment_plan::Periodicity::Weekly,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum BusinessDayConvention {
+    Following = 0,
+    ModifiedFollowing = 1,
+    Preceding = 2,
+}
+
+impl From<BusinessDayConvention> for core_payment_plan::BusinessDayConvention {
+    fn from(value: BusinessDayConvention) -> Self {
+        match value {
+            BusinessDayConvention::Following => core_payment_plan::BusinessDayConvention::Following,
+            BusinessDayConvention::ModifiedFollowing => {
+                core_payment_plan::BusinessDayConvention::ModifiedFollowing
+            }
+            BusinessDayConvention::Preceding => core_payment_plan::BusinessDayConvention::Preceding,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum Provider {
+    Simple = 0,
+    Iterative = 1,
+}
+
+impl From<Provider> for core_payment_plan::Provider {
+    fn from(value: Provider) -> Self {
+        match value {
+            Provider::Simple => core_payment_plan::Provider::Simple,
+            Provider::Iterative => core_payment_plan::Provider::Iterative,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum RoundingMode {
+    HalfUp = 0,
+    HalfEven = 1,
+    Truncate = 2,
+}
+
+impl From<RoundingMode> for core_payment_plan::RoundingMode {
+    fn from(value: RoundingMode) -> Self {
+        match value {
+            RoundingMode::HalfUp => core_payment_plan::RoundingMode::HalfUp,
+            RoundingMode::HalfEven => core_payment_plan::RoundingMode::HalfEven,
+            RoundingMode::Truncate => core_payment_plan::RoundingMode::Truncate,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum ResidualAllocation {
+    None = 0,
+    FirstInstallment = 1,
+    LastInstallment = 2,
+}
+
+impl From<ResidualAllocation> for core_payment_plan::ResidualAllocation {
+    fn from(value: ResidualAllocation) -> Self {
+        match value {
+            ResidualAllocation::None => core_payment_plan::ResidualAllocation::None,
+            ResidualAllocation::FirstInstallment => {
+                core_payment_plan::ResidualAllocation::FirstInstallment
+            }
+            ResidualAllocation::LastInstallment => {
+                core_payment_plan::ResidualAllocation::LastInstallment
+            }
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum DueDayPolicy {
+    Stepped = 0,
+    Anchored = 1,
+    EndOfMonth = 2,
+    FixedDay = 3, // On the `due_day` of every month
+}
+
+impl DueDayPolicy {
+    fn with_due_day(self, due_day: u32) -> core_payment_plan::DueDayPolicy {
+        match self {
+            DueDayPolicy::Stepped => core_payment_plan::DueDayPolicy::Stepped,
+            DueDayPolicy::Anchored => core_payment_plan::DueDayPolicy::Anchored,
+            DueDayPolicy::EndOfMonth => core_payment_plan::DueDayPolicy::EndOfMonth,
+            DueDayPolicy::FixedDay => core_payment_plan::DueDayPolicy::FixedDay(due_day),
+        }
+    }
+}
+
+impl From<core_payment_plan::DueDayPolicy> for DueDayPolicy {
+    fn from(value: core_payment_plan::DueDayPolicy) -> Self {
+        match value {
+            core_payment_plan::DueDayPolicy::Stepped => DueDayPolicy::Stepped,
+            core_payment_plan::DueDayPolicy::Anchored => DueDayPolicy::Anchored,
+            core_payment_plan::DueDayPolicy::EndOfMonth => DueDayPolicy::EndOfMonth,
+            core_payment_plan::DueDayPolicy::FixedDay(_) => DueDayPolicy::FixedDay,
+        }
+    }
+}
+
 #[derive_ReprC]
 #[repr(C)]
 pub struct Params {
@@ -18,6 +177,17 @@ pub struct Params {
     pub min_installment_amount: f64,
     pub max_total_amount: f64,
     pub disbursement_only_on_business_days: bool,
+    pub amortization_system: AmortizationSystem,
+    pub grace_period: GracePeriod,
+    pub balloon_percentage: f64,
+    pub periodicity: Periodicity,
+    pub due_day_policy: DueDayPolicy,
+    pub due_day: u32, // The day of the month (1-31) with the FixedDay policy
+    pub rounding_mode: RoundingMode,
+    pub residual_allocation: ResidualAllocation,
+    pub business_day_convention: BusinessDayConvention,
+    pub iof_tolerance: f64, // The IOF convergence tolerance, 0 for the default
+    pub iof_max_iterations: u32, // The IOF convergence max iterations, 0 for the default
 }
 
 impl Into<core_payment_plan::Params> for Params {
@@ -46,10 +216,38 @@ impl Into<core_payment_plan::Params> for Params {
             min_installment_amount: self.min_installment_amount,
             max_total_amount: self.max_total_amount,
             disbursement_only_on_business_days: self.disbursement_only_on_business_days,
+            amortization_system: self.amortization_system.into(),
+            grace_period: self.grace_period.into(),
+            balloon_percentage: self.balloon_percentage,
+            periodicity: self.periodicity.into(),
+            due_day_policy: self.due_day_policy.with_due_day(self.due_day),
+            rounding_policy: core_payment_plan::RoundingPolicy {
+                mode: self.rounding_mode.into(),
+                residual_allocation: self.residual_allocation.into(),
+            },
+            business_day_convention: self.business_day_convention.into(),
+            iof_convergence: iof_convergence(self.iof_tolerance, self.iof_max_iterations),
         }
     }
 }
 
+// The default convergence for the fields that are 0
+fn iof_convergence(tolerance: f64, max_iterations: u32) -> core_payment_plan::IofConvergence {
+    let default = core_payment_plan::IofConvergence::default();
+    core_payment_plan::IofConvergence {
+        tolerance: if tolerance == 0.0 {
+            default.tolerance
+        } else {
+            tolerance
+        },
+        max_iterations: if max_iterations == 0 {
+            default.max_iterations
+        } else {
+            max_iterations
+        },
+    }
+}
+
 #[derive_ReprC]
 #[repr(C)]
 pub struct DownPaymentParams {
@@ -76,3 +274,85 @@ impl Into<core_payment_plan::DownPaymentParams> for DownPaymentParams {
         }
     }
 }
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct CustomScheduleParams<'a> {
+    pub params: Params, // The params of the plan, the installments and first payment date are taken from the due dates
+    pub due_dates_ms: c_slice::Ref<'a, i64>, // The due date of each installment, in ascending order
+    pub weights: c_slice::Ref<'a, f64>, // The relative size of each installment (ex: 2.0 is twice the others), empty for equal installments
+}
+
+impl From<CustomScheduleParams<'_>> for core_payment_plan::CustomScheduleParams {
+    fn from(value: CustomScheduleParams<'_>) -> Self {
+        let due_dates = value
+            .due_dates_ms
+            .iter()
+            .map(|due_date_ms| {
+                let due_date: DateTime<Utc> = chrono::DateTime::from_timestamp_millis(*due_date_ms)
+                    .expect("Invalid due date timestamp");
+                due_date.date_naive()
+            })
+            .collect();
+
+        Self {
+            params: value.params.into(),
+            due_dates,
+            weights: value.weights.to_vec(),
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct EarlySettlementParams<'a> {
+    pub invoices: c_slice::Ref<'a, Invoice>, // The invoices still open (ex: the invoices of a plan)
+    pub interest_rate: f64,
+    pub settlement_date_ms: i64,
+    pub disbursement_only_on_business_days: bool,
+}
+
+impl From<EarlySettlementParams<'_>> for core_payment_plan::EarlySettlementParams {
+    fn from(value: EarlySettlementParams<'_>) -> Self {
+        let settlement_date: DateTime<Utc> =
+            chrono::DateTime::from_timestamp_millis(value.settlement_date_ms)
+                .expect("Invalid settlement date timestamp");
+        let settlement_date = settlement_date.date_naive();
+
+        Self {
+            invoices: value.invoices.iter().map(Into::into).collect(),
+            interest_rate: value.interest_rate,
+            settlement_date,
+            disbursement_only_on_business_days: value.disbursement_only_on_business_days,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct LateChargesParams {
+    pub invoice: Invoice, // The overdue invoice (ex: an invoice of a plan)
+    pub interest_rate: f64,
+    pub payment_date_ms: i64,
+    pub fine_rate: f64, // The fine over the invoice amount (ex: 0.02)
+    pub mora_rate: f64, // The monthly moratory interest rate (ex: 0.01)
+    pub iof_percentage: f64,
+}
+
+impl From<LateChargesParams> for core_payment_plan::LateChargesParams {
+    fn from(value: LateChargesParams) -> Self {
+        let payment_date: DateTime<Utc> =
+            chrono::DateTime::from_timestamp_millis(value.payment_date_ms)
+                .expect("Invalid payment date timestamp");
+        let payment_date = payment_date.date_naive();
+
+        Self {
+            invoice: (&value.invoice).into(),
+            interest_rate: value.interest_rate,
+            payment_date,
+            fine_rate: value.fine_rate,
+            mora_rate: value.mora_rate,
+            iof_percentage: value.iof_percentage,
+        }
+    }
+}
@@ -2,6 +2,8 @@ use ::safer_ffi::prelude::*;
 
 use chrono::{DateTime, NaiveDateTime, Utc};
 
+use crate::params::DueDayPolicy;
+
 #[derive_ReprC]
 #[repr(C)]
 pub struct Invoice {
@@ -10,7 +12,14 @@ pub struct Invoice {
     pub accumulated_factor: f64,
     pub main_iof_tac: f64,
     pub debit_service: f64,
+    pub amount: f64,
     pub due_date_ms: i64,
+    pub opening_balance: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub iof: f64,
+    pub tac: f64,
+    pub closing_balance: f64,
 }
 
 impl From<core_payment_plan::Invoice> for Invoice {
@@ -26,7 +35,37 @@ impl From<core_payment_plan::Invoice> for Invoice {
             accumulated_factor: value.accumulated_factor,
             debit_service: value.debit_service,
             main_iof_tac: value.main_iof_tac,
+            amount: value.amount,
             due_date_ms: due_date,
+            opening_balance: value.opening_balance,
+            interest: value.interest,
+            principal: value.principal,
+            iof: value.iof,
+            tac: value.tac,
+            closing_balance: value.closing_balance,
+        }
+    }
+}
+
+impl From<&Invoice> for core_payment_plan::Invoice {
+    fn from(value: &Invoice) -> Self {
+        let due_date: DateTime<Utc> = chrono::DateTime::from_timestamp_millis(value.due_date_ms)
+            .expect("Invalid due date timestamp");
+
+        Self {
+            accumulated_days: value.accumulated_days,
+            factor: value.factor,
+            accumulated_factor: value.accumulated_factor,
+            main_iof_tac: value.main_iof_tac,
+            debit_service: value.debit_service,
+            amount: value.amount,
+            due_date: due_date.date_naive(),
+            opening_balance: value.opening_balance,
+            interest: value.interest,
+            principal: value.principal,
+            iof: value.iof,
+            tac: value.tac,
+            closing_balance: value.closing_balance,
         }
     }
 }
@@ -58,6 +97,8 @@ pub struct Response {
     pub tec_yearly: f64,
     pub eir_monthly: f64,
     pub tec_monthly: f64,
+    pub eir_periodic: f64,
+    pub tec_periodic: f64,
     pub total_iof: f64,
     pub contract_amount: f64,
     pub contract_amount_without_tac: f64,
@@ -67,6 +108,11 @@ pub struct Response {
     pub pre_disbursement_amount: f64,
     pub paid_total_iof: f64,
     pub paid_contract_amount: f64,
+    pub due_day_policy: DueDayPolicy,
+    pub due_day: u32, // The day of the month with the FixedDay policy, 0 otherwise
+    pub rounding_residual: f64,
+    pub iof_iterations: u32,
+    pub iof_residual: f64,
     pub invoices: repr_c::Vec<Invoice>,
 }
 
@@ -115,6 +161,8 @@ impl From<core_payment_plan::Response> for Response {
             tec_yearly: value.tec_yearly,
             eir_monthly: value.eir_monthly,
             tec_monthly: value.tec_monthly,
+            eir_periodic: value.eir_periodic,
+            tec_periodic: value.tec_periodic,
             total_iof: value.total_iof,
             contract_amount: value.contract_amount,
             contract_amount_without_tac: value.contract_amount_without_tac,
@@ -124,6 +172,14 @@ impl From<core_payment_plan::Response> for Response {
             pre_disbursement_amount: value.pre_disbursement_amount,
             paid_total_iof: value.paid_total_iof,
             paid_contract_amount: value.paid_contract_amount,
+            due_day_policy: value.due_day_policy.into(),
+            due_day: match value.due_day_policy {
+                core_payment_plan::DueDayPolicy::FixedDay(day) => day,
+                _ => 0,
+            },
+            rounding_residual: value.rounding_residual,
+            iof_iterations: value.iof_iterations,
+            iof_residual: value.iof_residual,
             invoices,
         }
     }
@@ -163,3 +219,147 @@ impl From<core_payment_plan::DownPaymentResponse> for DownPaymentResponse {
         }
     }
 }
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct EarlySettlementInvoice {
+    pub due_date_ms: i64,
+    pub amount: f64,
+    pub days: i64,
+    pub present_value: f64,
+    pub discount: f64,
+}
+
+impl From<core_payment_plan::EarlySettlementInvoice> for EarlySettlementInvoice {
+    fn from(value: core_payment_plan::EarlySettlementInvoice) -> Self {
+        let due_date: NaiveDateTime = value.due_date.into();
+        let due_date: DateTime<Utc> = DateTime::from_naive_utc_and_offset(due_date, Utc);
+
+        Self {
+            due_date_ms: due_date.timestamp_millis(),
+            amount: value.amount,
+            days: value.days,
+            present_value: value.present_value,
+            discount: value.discount,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct EarlySettlementResponse {
+    pub settlement_date_ms: i64,
+    pub total_amount: f64,
+    pub settlement_amount: f64,
+    pub discount: f64,
+    pub invoices: repr_c::Vec<EarlySettlementInvoice>,
+}
+
+impl From<core_payment_plan::EarlySettlementResponse> for EarlySettlementResponse {
+    fn from(value: core_payment_plan::EarlySettlementResponse) -> Self {
+        let settlement_date: NaiveDateTime = value.settlement_date.into();
+        let settlement_date: DateTime<Utc> =
+            DateTime::from_naive_utc_and_offset(settlement_date, Utc);
+
+        let invoices: repr_c::Vec<EarlySettlementInvoice> = value
+            .invoices
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<EarlySettlementInvoice>>()
+            .into();
+
+        Self {
+            settlement_date_ms: settlement_date.timestamp_millis(),
+            total_amount: value.total_amount,
+            settlement_amount: value.settlement_amount,
+            discount: value.discount,
+            invoices,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct LateChargesResponse {
+    pub days_late: i64,
+    pub amount: f64,
+    pub fine: f64,
+    pub mora_interest: f64,
+    pub remunerative_interest: f64,
+    pub iof: f64,
+    pub total_amount: f64,
+}
+
+impl From<core_payment_plan::LateChargesResponse> for LateChargesResponse {
+    fn from(value: core_payment_plan::LateChargesResponse) -> Self {
+        Self {
+            days_late: value.days_late,
+            amount: value.amount,
+            fine: value.fine,
+            mora_interest: value.mora_interest,
+            remunerative_interest: value.remunerative_interest,
+            iof: value.iof,
+            total_amount: value.total_amount,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(u8)]
+pub enum ExclusionReason {
+    MinInstallmentAmount = 0,
+    MaxTotalAmount = 1,
+}
+
+impl From<core_payment_plan::ExclusionReason> for ExclusionReason {
+    fn from(value: core_payment_plan::ExclusionReason) -> Self {
+        match value {
+            core_payment_plan::ExclusionReason::MinInstallmentAmount => {
+                ExclusionReason::MinInstallmentAmount
+            }
+            core_payment_plan::ExclusionReason::MaxTotalAmount => ExclusionReason::MaxTotalAmount,
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct ExcludedInstallment {
+    pub installment: u32,
+    pub reason: ExclusionReason,
+}
+
+impl From<core_payment_plan::ExcludedInstallment> for ExcludedInstallment {
+    fn from(value: core_payment_plan::ExcludedInstallment) -> Self {
+        Self {
+            installment: value.installment,
+            reason: value.reason.into(),
+        }
+    }
+}
+
+#[derive_ReprC]
+#[repr(C)]
+pub struct SelectedPlans {
+    pub plans: repr_c::Vec<Response>, // The plans of the selected installments, sorted by them
+    pub excluded: repr_c::Vec<ExcludedInstallment>, // The selected installments without a plan, sorted
+}
+
+impl From<core_payment_plan::SelectedPlans> for SelectedPlans {
+    fn from(value: core_payment_plan::SelectedPlans) -> Self {
+        let plans: repr_c::Vec<Response> = value
+            .plans
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<Response>>()
+            .into();
+        let excluded: repr_c::Vec<ExcludedInstallment> = value
+            .excluded
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<ExcludedInstallment>>()
+            .into();
+
+        Self { plans, excluded }
+    }
+}
@@ -1,4 +1,11 @@
-use core_payment_plan::{DownPaymentParams, DownPaymentResponse, Invoice, Params, Response};
+use core_payment_plan::{
+    AmortizationSystem, BusinessDayConvention, CustomScheduleParams, DownPaymentParams,
+    DownPaymentResponse, DueDayPolicy, EarlySettlementInvoice, EarlySettlementParams,
+    EarlySettlementResponse, ExcludedInstallment, ExclusionReason, GracePeriod, Invoice,
+    IofConvergence, LateChargesParams, LateChargesResponse, Params, Periodicity, Provider,
+    ResidualAllocation, Response, RoundingMode, RoundingPolicy, SelectedPlans,
+    DEFAULT_LATE_FINE_RATE, DEFAULT_LATE_MORA_RATE,
+};
 
 use neon::{
     context::{Context, FunctionContext},
@@ -8,7 +15,7 @@ use neon::{
     types::{JsArray, JsDate, JsNumber, JsObject, JsValue},
 };
 
-use crate::parser::{self, any_to_bool, any_to_number};
+use crate::parser::{self, any_to_bool, any_to_number, any_to_string};
 
 pub fn cast_js_object_to_param(
     cx: &mut FunctionContext,
@@ -33,6 +40,18 @@ pub fn cast_js_object_to_param(
         obj.get_opt(cx, "minInstallmentAmount")?;
     let disbursement_only_on_business_days: Option<Handle<JsValue>> =
         obj.get_opt(cx, "disbursementOnlyOnBusinessDays")?;
+    let amortization_system: Option<Handle<JsValue>> = obj.get_opt(cx, "amortizationSystem")?;
+    let grace_period: Option<Handle<JsValue>> = obj.get_opt(cx, "gracePeriod")?;
+    let balloon_percentage: Option<Handle<JsValue>> = obj.get_opt(cx, "balloonPercentage")?;
+    let periodicity: Option<Handle<JsValue>> = obj.get_opt(cx, "periodicity")?;
+    let due_day_policy: Option<Handle<JsValue>> = obj.get_opt(cx, "dueDayPolicy")?;
+    let due_day: Option<Handle<JsValue>> = obj.get_opt(cx, "dueDay")?;
+    let rounding_mode: Option<Handle<JsValue>> = obj.get_opt(cx, "roundingMode")?;
+    let residual_allocation: Option<Handle<JsValue>> = obj.get_opt(cx, "residualAllocation")?;
+    let business_day_convention: Option<Handle<JsValue>> =
+        obj.get_opt(cx, "businessDayConvention")?;
+    let iof_tolerance: Option<Handle<JsValue>> = obj.get_opt(cx, "iofTolerance")?;
+    let iof_max_iterations: Option<Handle<JsValue>> = obj.get_opt(cx, "iofMaxIterations")?;
 
     let requested_amount = any_to_number(cx, requested_amount)?;
     let installments = any_to_number(cx, installments)? as u32;
@@ -56,6 +75,59 @@ pub fn cast_js_object_to_param(
         None => false,
     };
 
+    let amortization_system = match amortization_system {
+        Some(value) => cast_js_value_to_amortization_system(cx, value)?,
+        None => AmortizationSystem::default(),
+    };
+
+    let grace_period = match grace_period {
+        Some(value) => cast_js_value_to_grace_period(cx, value)?,
+        None => GracePeriod::default(),
+    };
+
+    let balloon_percentage = match balloon_percentage {
+        Some(value) => any_to_number(cx, value)?,
+        None => 0.0,
+    };
+
+    let periodicity = match periodicity {
+        Some(value) => cast_js_value_to_periodicity(cx, value)?,
+        None => Periodicity::default(),
+    };
+
+    let due_day = match due_day {
+        Some(value) => any_to_number(cx, value)? as u32,
+        None => 0,
+    };
+
+    let due_day_policy = match due_day_policy {
+        Some(value) => cast_js_value_to_due_day_policy(cx, value, due_day)?,
+        None => DueDayPolicy::default(),
+    };
+
+    let rounding_mode = match rounding_mode {
+        Some(value) => cast_js_value_to_rounding_mode(cx, value)?,
+        None => RoundingMode::default(),
+    };
+
+    let residual_allocation = match residual_allocation {
+        Some(value) => cast_js_value_to_residual_allocation(cx, value)?,
+        None => ResidualAllocation::default(),
+    };
+
+    let business_day_convention = match business_day_convention {
+        Some(value) => cast_js_value_to_business_day_convention(cx, value)?,
+        None => BusinessDayConvention::default(),
+    };
+
+    let mut iof_convergence = IofConvergence::default();
+    if let Some(value) = iof_tolerance {
+        iof_convergence.tolerance = any_to_number(cx, value)?;
+    }
+    if let Some(value) = iof_max_iterations {
+        iof_convergence.max_iterations = any_to_number(cx, value)? as u32;
+    }
+
     Ok(Params {
         max_total_amount,
         min_installment_amount,
@@ -70,10 +142,129 @@ pub fn cast_js_object_to_param(
         iof_percentage,
         interest_rate,
         disbursement_only_on_business_days,
+        amortization_system,
+        grace_period,
+        balloon_percentage,
+        periodicity,
+        due_day_policy,
+        rounding_policy: RoundingPolicy {
+            mode: rounding_mode,
+            residual_allocation,
+        },
+        business_day_convention,
+        iof_convergence,
     })
 }
 
-fn cast_response_to_js_object<'a, C: Context<'a>>(
+fn cast_js_value_to_amortization_system(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<AmortizationSystem> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "price" => Ok(AmortizationSystem::Price),
+        "sac" => Ok(AmortizationSystem::Sac),
+        _ => cx.throw_error(format!("Invalid amortization system: {}", value)),
+    }
+}
+
+fn cast_js_value_to_grace_period(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<GracePeriod> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "capitalized" => Ok(GracePeriod::Capitalized),
+        "interestonly" => Ok(GracePeriod::InterestOnly),
+        _ => cx.throw_error(format!("Invalid grace period: {}", value)),
+    }
+}
+
+fn cast_js_value_to_periodicity(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<Periodicity> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "monthly" => Ok(Periodicity::Monthly),
+        "biweekly" => Ok(Periodicity::Biweekly),
+        "weekly" => Ok(Periodicity::Weekly),
+        _ => cx.throw_error(format!("Invalid periodicity: {}", value)),
+    }
+}
+
+fn cast_js_value_to_business_day_convention(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<BusinessDayConvention> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "following" => Ok(BusinessDayConvention::Following),
+        "modifiedfollowing" => Ok(BusinessDayConvention::ModifiedFollowing),
+        "preceding" => Ok(BusinessDayConvention::Preceding),
+        _ => cx.throw_error(format!("Invalid business day convention: {}", value)),
+    }
+}
+
+// The provider of the optional argument, the default one when it is not given
+pub fn cast_js_value_to_provider(
+    cx: &mut FunctionContext,
+    value: Option<Handle<JsValue>>,
+) -> NeonResult<Provider> {
+    let value = match value {
+        Some(value) => any_to_string(cx, value)?,
+        None => String::new(),
+    };
+    match value.to_lowercase().as_str() {
+        "" => Ok(Provider::default()),
+        "simple" => Ok(Provider::Simple),
+        "iterative" => Ok(Provider::Iterative),
+        _ => cx.throw_error(format!("Invalid provider: {}", value)),
+    }
+}
+
+fn cast_js_value_to_rounding_mode(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<RoundingMode> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "halfup" => Ok(RoundingMode::HalfUp),
+        "halfeven" => Ok(RoundingMode::HalfEven),
+        "truncate" => Ok(RoundingMode::Truncate),
+        _ => cx.throw_error(format!("Invalid rounding mode: {}", value)),
+    }
+}
+
+fn cast_js_value_to_residual_allocation(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<ResidualAllocation> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "none" => Ok(ResidualAllocation::None),
+        "firstinstallment" => Ok(ResidualAllocation::FirstInstallment),
+        "lastinstallment" => Ok(ResidualAllocation::LastInstallment),
+        _ => cx.throw_error(format!("Invalid residual allocation: {}", value)),
+    }
+}
+
+fn cast_js_value_to_due_day_policy(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+    due_day: u32,
+) -> NeonResult<DueDayPolicy> {
+    let value = any_to_string(cx, value)?;
+    match value.to_lowercase().as_str() {
+        "" | "stepped" => Ok(DueDayPolicy::Stepped),
+        "anchored" => Ok(DueDayPolicy::Anchored),
+        "endofmonth" => Ok(DueDayPolicy::EndOfMonth),
+        "fixedday" => Ok(DueDayPolicy::FixedDay(due_day)),
+        _ => cx.throw_error(format!("Invalid due day policy: {}", value)),
+    }
+}
+
+pub fn cast_response_to_js_object<'a, C: Context<'a>>(
     cx: &mut C,
     response: Response,
 ) -> NeonResult<Handle<'a, JsObject>> {
@@ -102,6 +293,8 @@ fn cast_response_to_js_object<'a, C: Context<'a>>(
     let tec_yearly = JsNumber::new(cx, response.tec_yearly);
     let eir_monthly = JsNumber::new(cx, response.eir_monthly);
     let tec_monthly = JsNumber::new(cx, response.tec_monthly);
+    let eir_periodic = JsNumber::new(cx, response.eir_periodic);
+    let tec_periodic = JsNumber::new(cx, response.tec_periodic);
     let total_iof = JsNumber::new(cx, response.total_iof);
     let contract_amount = JsNumber::new(cx, response.contract_amount);
     let contract_amount_without_tac = JsNumber::new(cx, response.contract_amount_without_tac);
@@ -111,6 +304,17 @@ fn cast_response_to_js_object<'a, C: Context<'a>>(
     let pre_disbursement_amount = JsNumber::new(cx, response.pre_disbursement_amount);
     let paid_total_iof = JsNumber::new(cx, response.paid_total_iof);
     let paid_contract_amount = JsNumber::new(cx, response.paid_contract_amount);
+    let (due_day_policy, due_day) = match response.due_day_policy {
+        DueDayPolicy::Stepped => ("stepped", 0),
+        DueDayPolicy::Anchored => ("anchored", 0),
+        DueDayPolicy::EndOfMonth => ("endOfMonth", 0),
+        DueDayPolicy::FixedDay(day) => ("fixedDay", day),
+    };
+    let due_day_policy = cx.string(due_day_policy);
+    let due_day = JsNumber::new(cx, due_day);
+    let rounding_residual = JsNumber::new(cx, response.rounding_residual);
+    let iof_iterations = JsNumber::new(cx, response.iof_iterations);
+    let iof_residual = JsNumber::new(cx, response.iof_residual);
     let invoices = cast_vec_invoice_to_js_array(cx, response.invoices)?;
 
     let obj = JsObject::new(cx);
@@ -154,6 +358,8 @@ fn cast_response_to_js_object<'a, C: Context<'a>>(
     obj.set(cx, "tecYearly", tec_yearly)?;
     obj.set(cx, "eirMonthly", eir_monthly)?;
     obj.set(cx, "tecMonthly", tec_monthly)?;
+    obj.set(cx, "eirPeriodic", eir_periodic)?;
+    obj.set(cx, "tecPeriodic", tec_periodic)?;
     obj.set(cx, "totalIOF", total_iof)?;
     obj.set(cx, "contractAmount", contract_amount)?;
     obj.set(cx, "contractAmountWithoutTAC", contract_amount_without_tac)?;
@@ -163,6 +369,11 @@ fn cast_response_to_js_object<'a, C: Context<'a>>(
     obj.set(cx, "preDisbursementAmount", pre_disbursement_amount)?;
     obj.set(cx, "paidTotalIOF", paid_total_iof)?;
     obj.set(cx, "paidContractAmount", paid_contract_amount)?;
+    obj.set(cx, "dueDayPolicy", due_day_policy)?;
+    obj.set(cx, "dueDay", due_day)?;
+    obj.set(cx, "roundingResidual", rounding_residual)?;
+    obj.set(cx, "iofIterations", iof_iterations)?;
+    obj.set(cx, "iofResidual", iof_residual)?;
     obj.set(cx, "invoices", invoices)?;
 
     Ok(obj)
@@ -180,6 +391,55 @@ pub fn cast_vec_response_to_js_array<'a, C: Context<'a>>(
     Ok(array)
 }
 
+pub fn cast_js_array_to_installments(
+    cx: &mut FunctionContext,
+    array: Handle<JsArray>,
+) -> NeonResult<Vec<u32>> {
+    let values = array.to_vec(cx)?;
+    let mut installments = Vec::with_capacity(values.len());
+    for value in values {
+        installments.push(any_to_number(cx, value)? as u32);
+    }
+    Ok(installments)
+}
+
+fn cast_excluded_installment_to_js_object<'a, C: Context<'a>>(
+    cx: &mut C,
+    excluded: ExcludedInstallment,
+) -> NeonResult<Handle<'a, JsObject>> {
+    let installment = JsNumber::new(cx, excluded.installment);
+    let reason = match excluded.reason {
+        ExclusionReason::MinInstallmentAmount => "minInstallmentAmount",
+        ExclusionReason::MaxTotalAmount => "maxTotalAmount",
+    };
+    let reason = cx.string(reason);
+
+    let obj = JsObject::new(cx);
+    obj.set(cx, "installment", installment)?;
+    obj.set(cx, "reason", reason)?;
+
+    Ok(obj)
+}
+
+pub fn cast_selected_plans_to_js_object<'a, C: Context<'a>>(
+    cx: &mut C,
+    selected: SelectedPlans,
+) -> NeonResult<Handle<'a, JsObject>> {
+    let plans = cast_vec_response_to_js_array(cx, selected.plans)?;
+
+    let excluded = JsArray::new(cx, selected.excluded.len());
+    for (i, installment) in selected.excluded.into_iter().enumerate() {
+        let obj = cast_excluded_installment_to_js_object(cx, installment)?;
+        excluded.set(cx, i as u32, obj)?;
+    }
+
+    let obj = JsObject::new(cx);
+    obj.set(cx, "plans", plans)?;
+    obj.set(cx, "excluded", excluded)?;
+
+    Ok(obj)
+}
+
 pub fn cast_vec_invoice_to_js_array<'a, C: Context<'a>>(
     cx: &mut C,
     invoices: Vec<Invoice>,
@@ -203,12 +463,26 @@ pub fn cast_invoice_to_js_object<'a, C: Context<'a>>(
     let accumulated_factor = JsNumber::new(cx, invoice.accumulated_factor as f64);
     let main_iof_tac = JsNumber::new(cx, invoice.main_iof_tac as f64);
     let debit_service = JsNumber::new(cx, invoice.debit_service as f64);
+    let amount = JsNumber::new(cx, invoice.amount);
+    let opening_balance = JsNumber::new(cx, invoice.opening_balance);
+    let interest = JsNumber::new(cx, invoice.interest);
+    let principal = JsNumber::new(cx, invoice.principal);
+    let iof = JsNumber::new(cx, invoice.iof);
+    let tac = JsNumber::new(cx, invoice.tac);
+    let closing_balance = JsNumber::new(cx, invoice.closing_balance);
     obj.set(cx, "accumulatedDays", accumulated_days)?;
     obj.set(cx, "factor", factor)?;
     obj.set(cx, "dueDate", due_date)?;
     obj.set(cx, "accumulatedFactor", accumulated_factor)?;
     obj.set(cx, "mainIOFTAC", main_iof_tac)?;
     obj.set(cx, "debitService", debit_service)?;
+    obj.set(cx, "amount", amount)?;
+    obj.set(cx, "openingBalance", opening_balance)?;
+    obj.set(cx, "interest", interest)?;
+    obj.set(cx, "principal", principal)?;
+    obj.set(cx, "iof", iof)?;
+    obj.set(cx, "tac", tac)?;
+    obj.set(cx, "closingBalance", closing_balance)?;
 
     Ok(obj)
 }
@@ -239,6 +513,37 @@ pub fn cast_js_object_to_down_payment_param(
     })
 }
 
+pub fn cast_js_object_to_custom_schedule_param(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+) -> NeonResult<CustomScheduleParams> {
+    let params: Handle<JsObject> = obj.get(cx, "params")?;
+    let js_due_dates: Handle<JsArray> = obj.get(cx, "dueDates")?;
+    let js_weights: Option<Handle<JsArray>> = obj.get_opt(cx, "weights")?;
+
+    let params = cast_js_object_to_param(cx, params)?;
+
+    let js_due_dates = js_due_dates.to_vec(cx)?;
+    let mut due_dates = Vec::with_capacity(js_due_dates.len());
+    for js_due_date in js_due_dates {
+        let js_due_date: Handle<JsDate> = js_due_date.downcast_or_throw(cx)?;
+        due_dates.push(parser::js_date_to_naive(cx, js_due_date)?);
+    }
+
+    let mut weights = Vec::new();
+    if let Some(js_weights) = js_weights {
+        for js_weight in js_weights.to_vec(cx)? {
+            weights.push(any_to_number(cx, js_weight)?);
+        }
+    }
+
+    Ok(CustomScheduleParams {
+        params,
+        due_dates,
+        weights,
+    })
+}
+
 fn cast_down_payment_response_to_js_object<'a, C: Context<'a>>(
     cx: &mut C,
     response: DownPaymentResponse,
@@ -271,3 +576,160 @@ pub fn cast_vec_down_payment_response_to_js_array<'a, C: Context<'a>>(
     }
     Ok(array)
 }
+
+pub fn cast_js_object_to_early_settlement_param(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+) -> NeonResult<EarlySettlementParams> {
+    let js_invoices: Handle<JsArray> = obj.get(cx, "invoices")?;
+    let interest_rate: Handle<JsValue> = obj.get(cx, "interestRate")?;
+    let settlement_date: Handle<JsDate> = obj.get(cx, "settlementDate")?;
+    let disbursement_only_on_business_days: Option<Handle<JsValue>> =
+        obj.get_opt(cx, "disbursementOnlyOnBusinessDays")?;
+
+    let js_invoices = js_invoices.to_vec(cx)?;
+    let mut invoices = Vec::with_capacity(js_invoices.len());
+    for js_invoice in js_invoices {
+        let js_invoice: Handle<JsObject> = js_invoice.downcast_or_throw(cx)?;
+        let amount: Handle<JsValue> = js_invoice.get(cx, "amount")?;
+        let due_date: Handle<JsDate> = js_invoice.get(cx, "dueDate")?;
+
+        invoices.push(Invoice {
+            amount: any_to_number(cx, amount)?,
+            due_date: parser::js_date_to_naive(cx, due_date)?,
+            ..Default::default()
+        });
+    }
+
+    let interest_rate = any_to_number(cx, interest_rate)?;
+    let settlement_date = parser::js_date_to_naive(cx, settlement_date)?;
+
+    let disbursement_only_on_business_days = match disbursement_only_on_business_days {
+        Some(value) => any_to_bool(cx, value)?,
+        None => false,
+    };
+
+    Ok(EarlySettlementParams {
+        invoices,
+        interest_rate,
+        settlement_date,
+        disbursement_only_on_business_days,
+    })
+}
+
+fn cast_early_settlement_invoice_to_js_object<'a, C: Context<'a>>(
+    cx: &mut C,
+    invoice: EarlySettlementInvoice,
+) -> NeonResult<Handle<'a, JsObject>> {
+    let due_date = parser::naive_to_js_date(cx, invoice.due_date)?;
+    let amount = JsNumber::new(cx, invoice.amount);
+    let days = JsNumber::new(cx, invoice.days as f64);
+    let present_value = JsNumber::new(cx, invoice.present_value);
+    let discount = JsNumber::new(cx, invoice.discount);
+
+    let obj = JsObject::new(cx);
+    obj.set(cx, "dueDate", due_date)?;
+    obj.set(cx, "amount", amount)?;
+    obj.set(cx, "days", days)?;
+    obj.set(cx, "presentValue", present_value)?;
+    obj.set(cx, "discount", discount)?;
+
+    Ok(obj)
+}
+
+pub fn cast_early_settlement_response_to_js_object<'a, C: Context<'a>>(
+    cx: &mut C,
+    response: EarlySettlementResponse,
+) -> NeonResult<Handle<'a, JsObject>> {
+    let settlement_date = parser::naive_to_js_date(cx, response.settlement_date)?;
+    let total_amount = JsNumber::new(cx, response.total_amount);
+    let settlement_amount = JsNumber::new(cx, response.settlement_amount);
+    let discount = JsNumber::new(cx, response.discount);
+
+    let invoices = JsArray::new(cx, response.invoices.len());
+    for (i, invoice) in response.invoices.into_iter().enumerate() {
+        let obj = cast_early_settlement_invoice_to_js_object(cx, invoice)?;
+        invoices.set(cx, i as u32, obj)?;
+    }
+
+    let obj = JsObject::new(cx);
+    obj.set(cx, "settlementDate", settlement_date)?;
+    obj.set(cx, "totalAmount", total_amount)?;
+    obj.set(cx, "settlementAmount", settlement_amount)?;
+    obj.set(cx, "discount", discount)?;
+    obj.set(cx, "invoices", invoices)?;
+
+    Ok(obj)
+}
+
+pub fn cast_js_object_to_late_charges_param(
+    cx: &mut FunctionContext,
+    obj: Handle<JsObject>,
+) -> NeonResult<LateChargesParams> {
+    let js_invoice: Handle<JsObject> = obj.get(cx, "invoice")?;
+    let interest_rate: Handle<JsValue> = obj.get(cx, "interestRate")?;
+    let payment_date: Handle<JsDate> = obj.get(cx, "paymentDate")?;
+    let fine_rate: Option<Handle<JsValue>> = obj.get_opt(cx, "fineRate")?;
+    let mora_rate: Option<Handle<JsValue>> = obj.get_opt(cx, "moraRate")?;
+    let iof_percentage: Handle<JsValue> = obj.get(cx, "iofPercentage")?;
+
+    let amount: Handle<JsValue> = js_invoice.get(cx, "amount")?;
+    let due_date: Handle<JsDate> = js_invoice.get(cx, "dueDate")?;
+    let main_iof_tac: Handle<JsValue> = js_invoice.get(cx, "mainIOFTAC")?;
+    let accumulated_days: Handle<JsValue> = js_invoice.get(cx, "accumulatedDays")?;
+
+    let invoice = Invoice {
+        amount: any_to_number(cx, amount)?,
+        due_date: parser::js_date_to_naive(cx, due_date)?,
+        main_iof_tac: any_to_number(cx, main_iof_tac)?,
+        accumulated_days: any_to_number(cx, accumulated_days)? as i64,
+        ..Default::default()
+    };
+
+    let interest_rate = any_to_number(cx, interest_rate)?;
+    let payment_date = parser::js_date_to_naive(cx, payment_date)?;
+    let iof_percentage = any_to_number(cx, iof_percentage)?;
+
+    let fine_rate = match fine_rate {
+        Some(value) => any_to_number(cx, value)?,
+        None => DEFAULT_LATE_FINE_RATE,
+    };
+
+    let mora_rate = match mora_rate {
+        Some(value) => any_to_number(cx, value)?,
+        None => DEFAULT_LATE_MORA_RATE,
+    };
+
+    Ok(LateChargesParams {
+        invoice,
+        interest_rate,
+        payment_date,
+        fine_rate,
+        mora_rate,
+        iof_percentage,
+    })
+}
+
+pub fn cast_late_charges_response_to_js_object<'a, C: Context<'a>>(
+    cx: &mut C,
+    response: LateChargesResponse,
+) -> NeonResult<Handle<'a, JsObject>> {
+    let days_late = JsNumber::new(cx, response.days_late as f64);
+    let amount = JsNumber::new(cx, response.amount);
+    let fine = JsNumber::new(cx, response.fine);
+    let mora_interest = JsNumber::new(cx, response.mora_interest);
+    let remunerative_interest = JsNumber::new(cx, response.remunerative_interest);
+    let iof = JsNumber::new(cx, response.iof);
+    let total_amount = JsNumber::new(cx, response.total_amount);
+
+    let obj = JsObject::new(cx);
+    obj.set(cx, "daysLate", days_late)?;
+    obj.set(cx, "amount", amount)?;
+    obj.set(cx, "fine", fine)?;
+    obj.set(cx, "moraInterest", mora_interest)?;
+    obj.set(cx, "remunerativeInterest", remunerative_interest)?;
+    obj.set(cx, "iof", iof)?;
+    obj.set(cx, "totalAmount", total_amount)?;
+
+    Ok(obj)
+}
@@ -1,41 +1,130 @@
 use cast::{
-    cast_js_object_to_down_payment_param, cast_js_object_to_param,
+    cast_early_settlement_response_to_js_object, cast_js_array_to_installments,
+    cast_js_object_to_custom_schedule_param, cast_js_object_to_down_payment_param,
+    cast_js_object_to_early_settlement_param, cast_js_object_to_late_charges_param,
+    cast_js_object_to_param, cast_js_value_to_provider, cast_late_charges_response_to_js_object,
+    cast_response_to_js_object, cast_selected_plans_to_js_object,
     cast_vec_down_payment_response_to_js_array, cast_vec_response_to_js_array,
 };
 
+use core_payment_plan::err::ErrorKind;
 use neon::{prelude::*, types::JsDate};
 
 mod cast;
 mod parser;
 
+// Throws an Error with the `code` and the `kind` of the `PaymentPlanError`
+fn throw_payment_plan_error<'a, T: Value>(
+    cx: &mut FunctionContext<'a>,
+    error: core_payment_plan::err::PaymentPlanError,
+) -> JsResult<'a, T> {
+    let kind = match error.kind() {
+        ErrorKind::Validation => "validation",
+        ErrorKind::DateRange => "dateRange",
+        ErrorKind::Convergence => "convergence",
+        ErrorKind::Unreachable => "unreachable",
+    };
+    let js_error = cx.error(error.to_string())?;
+    let code = cx.number(error.code());
+    js_error.set(cx, "code", code)?;
+    let kind = cx.string(kind);
+    js_error.set(cx, "kind", kind)?;
+    cx.throw(js_error)
+}
+
 fn calculate_plan(mut cx: FunctionContext) -> JsResult<JsArray> {
     let js_obj: Handle<JsObject> = cx.argument(0)?;
     let params = cast_js_object_to_param(&mut cx, js_obj)?;
-    let result = core_payment_plan::calculate_payment_plan(params);
+    let provider = cx.argument_opt(1);
+    let provider = cast_js_value_to_provider(&mut cx, provider)?;
+    let result = core_payment_plan::calculate_payment_plan_with(provider, params);
     let result = match result {
         Ok(plan) => plan,
         Err(e) => {
-            return cx.throw_error(e.to_string());
+            return throw_payment_plan_error(&mut cx, e);
         }
     };
     let result = cast_vec_response_to_js_array(&mut cx, result)?;
     Ok(result)
 }
 
+fn calculate_selected_plans(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let params = cast_js_object_to_param(&mut cx, js_obj)?;
+    let js_installments: Handle<JsArray> = cx.argument(1)?;
+    let installments = cast_js_array_to_installments(&mut cx, js_installments)?;
+    let provider = cx.argument_opt(2);
+    let provider = cast_js_value_to_provider(&mut cx, provider)?;
+    let result =
+        core_payment_plan::calculate_selected_payment_plans_with(provider, params, &installments);
+    let result = match result {
+        Ok(selected) => selected,
+        Err(e) => {
+            return throw_payment_plan_error(&mut cx, e);
+        }
+    };
+    let result = cast_selected_plans_to_js_object(&mut cx, result)?;
+    Ok(result)
+}
+
+fn calculate_custom_plan(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let params = cast_js_object_to_custom_schedule_param(&mut cx, js_obj)?;
+    let result = core_payment_plan::calculate_custom_payment_plan(&params);
+    let result = match result {
+        Ok(plan) => plan,
+        Err(e) => {
+            return throw_payment_plan_error(&mut cx, e);
+        }
+    };
+    let result = cast_response_to_js_object(&mut cx, result)?;
+    Ok(result)
+}
+
 fn calculate_down_payment_plan(mut cx: FunctionContext) -> JsResult<JsArray> {
     let js_obj: Handle<JsObject> = cx.argument(0)?;
     let params = cast_js_object_to_down_payment_param(&mut cx, js_obj)?;
-    let result = core_payment_plan::calculate_down_payment_plan(params);
+    let provider = cx.argument_opt(1);
+    let provider = cast_js_value_to_provider(&mut cx, provider)?;
+    let result = core_payment_plan::calculate_down_payment_plan_with(provider, params);
     let result = match result {
         Ok(plan) => plan,
         Err(e) => {
-            return cx.throw_error(e.to_string());
+            return throw_payment_plan_error(&mut cx, e);
         }
     };
     let result = cast_vec_down_payment_response_to_js_array(&mut cx, result)?;
     Ok(result)
 }
 
+fn calculate_early_settlement(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let params = cast_js_object_to_early_settlement_param(&mut cx, js_obj)?;
+    let result = core_payment_plan::calculate_early_settlement(&params);
+    let result = match result {
+        Ok(settlement) => settlement,
+        Err(e) => {
+            return throw_payment_plan_error(&mut cx, e);
+        }
+    };
+    let result = cast_early_settlement_response_to_js_object(&mut cx, result)?;
+    Ok(result)
+}
+
+fn calculate_late_charges(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let js_obj: Handle<JsObject> = cx.argument(0)?;
+    let params = cast_js_object_to_late_charges_param(&mut cx, js_obj)?;
+    let result = core_payment_plan::calculate_late_charges(&params);
+    let result = match result {
+        Ok(charges) => charges,
+        Err(e) => {
+            return throw_payment_plan_error(&mut cx, e);
+        }
+    };
+    let result = cast_late_charges_response_to_js_object(&mut cx, result)?;
+    Ok(result)
+}
+
 fn next_disbursement_date(mut cx: FunctionContext) -> JsResult<JsDate> {
     let js_date: Handle<JsDate> = cx.argument(0)?;
     let date = parser::js_date_to_naive(&mut cx, js_date)?;
@@ -75,7 +164,11 @@ fn get_non_business_days_between(mut cx: FunctionContext) -> JsResult<JsArray> {
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("calculatePlan", calculate_plan)?;
+    cx.export_function("calculateSelectedPlans", calculate_selected_plans)?;
+    cx.export_function("calculateCustomPlan", calculate_custom_plan)?;
     cx.export_function("calculateDownPaymentPlan", calculate_down_payment_plan)?;
+    cx.export_function("calculateEarlySettlement", calculate_early_settlement)?;
+    cx.export_function("calculateLateCharges", calculate_late_charges)?;
     cx.export_function("nextDisbursementDate", next_disbursement_date)?;
     cx.export_function("disbursementDateRange", disbursement_date_range)?;
     cx.export_function("getNonBusinessDaysBetween", get_non_business_days_between)?;
@@ -54,6 +54,21 @@ pub fn any_to_bool<'a>(cx: &mut FunctionContext<'a>, js_any: Handle<JsValue>) ->
     }
 }
 
+pub fn any_to_string<'a>(
+    cx: &mut FunctionContext<'a>,
+    js_any: Handle<JsValue>,
+) -> NeonResult<String> {
+    if let Ok(str) = js_any.downcast::<JsString, _>(cx) {
+        Ok(str.value(cx))
+    } else if js_any.is_a::<JsNull, _>(cx) || js_any.is_a::<JsUndefined, _>(cx) {
+        Ok(String::new())
+    } else {
+        let error = format!("Value cannot be converted to string: {:?}", js_any);
+        let js_error = cx.string(error);
+        cx.throw(js_error)
+    }
+}
+
 pub fn js_date_to_naive<'a, C: Context<'a>>(
     cx: &mut C,
     js_date: Handle<JsDate>,
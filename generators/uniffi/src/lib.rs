@@ -7,25 +7,34 @@ use std::{
 };
 
 use chrono::{DateTime, NaiveDateTime, Utc};
-use params::{InternalDownPaymentParams, InternalParams};
-use response::{InternalDownPaymentResponse, InternalResponse};
+use core_payment_plan::err::ErrorKind;
+use params::{
+    InternalCustomScheduleParams, InternalDownPaymentParams, InternalEarlySettlementParams,
+    InternalLateChargesParams, InternalParams, InternalProvider,
+};
+use response::{
+    InternalDownPaymentResponse, InternalEarlySettlementResponse, InternalLateChargesResponse,
+    InternalResponse, InternalSelectedPlans,
+};
 
+// One variant for each kind of error, with the stable code of `PaymentPlanError::code`
 #[derive(uniffi::Error, Debug)]
 pub enum Error {
-    InvalidParams,
-    CalculationError,
+    InvalidParams { code: u16, message: String },
+    InvalidDate { code: u16, message: String },
+    CalculationError { code: u16, message: String },
+    Unreachable { code: u16, message: String },
 }
 
 impl From<core_payment_plan::err::PaymentPlanError> for Error {
     fn from(value: core_payment_plan::err::PaymentPlanError) -> Self {
-        match value {
-            core_payment_plan::err::PaymentPlanError::CalculationError(_) => {
-                Error::CalculationError
-            }
-            core_payment_plan::err::PaymentPlanError::XirCalculationError(_) => {
-                Error::CalculationError
-            }
-            _ => Error::InvalidParams,
+        let code = value.code();
+        let message = value.to_string();
+        match value.kind() {
+            ErrorKind::Validation => Error::InvalidParams { code, message },
+            ErrorKind::DateRange => Error::InvalidDate { code, message },
+            ErrorKind::Convergence => Error::CalculationError { code, message },
+            ErrorKind::Unreachable => Error::Unreachable { code, message },
         }
     }
 }
@@ -33,32 +42,82 @@ impl From<core_payment_plan::err::PaymentPlanError> for Error {
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::InvalidParams => write!(f, "Invalid parameters provided"),
-            Error::CalculationError => write!(f, "Error during calculation"),
+            Error::InvalidParams { code, message }
+            | Error::InvalidDate { code, message }
+            | Error::CalculationError { code, message }
+            | Error::Unreachable { code, message } => write!(f, "[{}] {}", code, message),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
-#[uniffi::export]
+#[uniffi::export(default(provider = None))]
 pub fn calculate_down_payment_plan(
     params: InternalDownPaymentParams,
+    provider: Option<InternalProvider>,
 ) -> Result<Vec<InternalDownPaymentResponse>, Error> {
     let params: core_payment_plan::DownPaymentParams = params.into();
-    let result = core_payment_plan::calculate_down_payment_plan(params)?;
+    let provider = provider.map(Into::into).unwrap_or_default();
+    let result = core_payment_plan::calculate_down_payment_plan_with(provider, params)?;
     let result: Vec<InternalDownPaymentResponse> = result.into_iter().map(|x| x.into()).collect();
     Ok(result)
 }
 
-#[uniffi::export]
-pub fn calculate_payment_plan(params: InternalParams) -> Result<Vec<InternalResponse>, Error> {
+#[uniffi::export(default(provider = None))]
+pub fn calculate_payment_plan(
+    params: InternalParams,
+    provider: Option<InternalProvider>,
+) -> Result<Vec<InternalResponse>, Error> {
     let params: core_payment_plan::Params = params.into();
-    let result = core_payment_plan::calculate_payment_plan(params)?;
+    let provider = provider.map(Into::into).unwrap_or_default();
+    let result = core_payment_plan::calculate_payment_plan_with(provider, params)?;
     let result: Vec<InternalResponse> = result.into_iter().map(|x| x.into()).collect();
     Ok(result)
 }
 
+// The plans of the selected numbers of installments only (ex: [3, 6, 10, 12], or [10] for a single one)
+#[uniffi::export(default(provider = None))]
+pub fn calculate_selected_payment_plans(
+    params: InternalParams,
+    installments: Vec<u32>,
+    provider: Option<InternalProvider>,
+) -> Result<InternalSelectedPlans, Error> {
+    let params: core_payment_plan::Params = params.into();
+    let provider = provider.map(Into::into).unwrap_or_default();
+    let result =
+        core_payment_plan::calculate_selected_payment_plans_with(provider, params, &installments)?;
+    Ok(result.into())
+}
+
+// The plan with one installment due on each of the due dates, always calculated by the Iterative provider
+#[uniffi::export]
+pub fn calculate_custom_payment_plan(
+    params: InternalCustomScheduleParams,
+) -> Result<InternalResponse, Error> {
+    let params: core_payment_plan::CustomScheduleParams = params.into();
+    let result = core_payment_plan::calculate_custom_payment_plan(&params)?;
+    Ok(result.into())
+}
+
+#[uniffi::export]
+pub fn calculate_early_settlement(
+    params: InternalEarlySettlementParams,
+) -> Result<InternalEarlySettlementResponse, Error> {
+    let params: core_payment_plan::EarlySettlementParams = params.into();
+    let result = core_payment_plan::calculate_early_settlement(&params)?;
+    Ok(result.into())
+}
+
+#[uniffi::export]
+pub fn calculate_late_charges(
+    params: InternalLateChargesParams,
+) -> Result<InternalLateChargesResponse, Error> {
+    let params: core_payment_plan::LateChargesParams = params.into();
+    let result = core_payment_plan::calculate_late_charges(&params)?;
+    Ok(result.into())
+}
+
 #[uniffi::export]
 pub fn next_disbursement_date(base_date: SystemTime) -> SystemTime {
     let base_date: DateTime<Utc> = base_date.into();
@@ -2,6 +2,174 @@ use std::time::SystemTime;
 
 use chrono::{DateTime, Utc};
 
+use crate::response::InternalInvoice;
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalAmortizationSystem {
+    #[default]
+    Price,
+    Sac,
+}
+
+impl From<InternalAmortizationSystem> for core_payment_plan::AmortizationSystem {
+    fn from(value: InternalAmortizationSystem) -> Self {
+        match value {
+            InternalAmortizationSystem::Price => core_payment_plan::AmortizationSystem::Price,
+            InternalAmortizationSystem::Sac => core_payment_plan::AmortizationSystem::Sac,
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalGracePeriod {
+    #[default]
+    Capitalized,
+    InterestOnly,
+}
+
+impl From<InternalGracePeriod> for core_payment_plan::GracePeriod {
+    fn from(value: InternalGracePeriod) -> Self {
+        match value {
+            InternalGracePeriod::Capitalized => core_payment_plan::GracePeriod::Capitalized,
+            InternalGracePeriod::InterestOnly => core_payment_plan::GracePeriod::InterestOnly,
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalPeriodicity {
+    #[default]
+    Monthly,
+    Biweekly,
+    Weekly,
+}
+
+impl From<InternalPeriodicity> for core_payment_plan::Periodicity {
+    fn from(value: InternalPeriodicity) -> Self {
+        match value {
+            InternalPeriodicity::Monthly => core_payment_plan::Periodicity::Monthly,
+            InternalPeriodicity::Biweekly => core_payment_plan::Periodicity::Biweekly,
+            InternalPeriodicity::Weekly => core_payment_plan::Periodicity::Weekly,
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalBusinessDayConvention {
+    #[default]
+    Following,
+    ModifiedFollowing,
+    Preceding,
+}
+
+impl From<InternalBusinessDayConvention> for core_payment_plan::BusinessDayConvention {
+    fn from(value: InternalBusinessDayConvention) -> Self {
+        match value {
+            InternalBusinessDayConvention::Following => {
+                core_payment_plan::BusinessDayConvention::Following
+            }
+            InternalBusinessDayConvention::ModifiedFollowing => {
+                core_payment_plan::BusinessDayConvention::ModifiedFollowing
+            }
+            InternalBusinessDayConvention::Preceding => {
+                core_payment_plan::BusinessDayConvention::Preceding
+            }
+        }
+    }
+}
+
+#[derive(uniffi::Enum)]
+pub enum InternalProvider {
+    Simple,
+    Iterative,
+}
+
+impl From<InternalProvider> for core_payment_plan::Provider {
+    fn from(value: InternalProvider) -> Self {
+        match value {
+            InternalProvider::Simple => core_payment_plan::Provider::Simple,
+            InternalProvider::Iterative => core_payment_plan::Provider::Iterative,
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalRoundingMode {
+    #[default]
+    HalfUp,
+    HalfEven,
+    Truncate,
+}
+
+impl From<InternalRoundingMode> for core_payment_plan::RoundingMode {
+    fn from(value: InternalRoundingMode) -> Self {
+        match value {
+            InternalRoundingMode::HalfUp => core_payment_plan::RoundingMode::HalfUp,
+            InternalRoundingMode::HalfEven => core_payment_plan::RoundingMode::HalfEven,
+            InternalRoundingMode::Truncate => core_payment_plan::RoundingMode::Truncate,
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalResidualAllocation {
+    #[default]
+    None,
+    FirstInstallment,
+    LastInstallment,
+}
+
+impl From<InternalResidualAllocation> for core_payment_plan::ResidualAllocation {
+    fn from(value: InternalResidualAllocation) -> Self {
+        match value {
+            InternalResidualAllocation::None => core_payment_plan::ResidualAllocation::None,
+            InternalResidualAllocation::FirstInstallment => {
+                core_payment_plan::ResidualAllocation::FirstInstallment
+            }
+            InternalResidualAllocation::LastInstallment => {
+                core_payment_plan::ResidualAllocation::LastInstallment
+            }
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum InternalDueDayPolicy {
+    #[default]
+    Stepped,
+    Anchored,
+    EndOfMonth,
+    FixedDay {
+        day: u32,
+    },
+}
+
+impl From<InternalDueDayPolicy> for core_payment_plan::DueDayPolicy {
+    fn from(value: InternalDueDayPolicy) -> Self {
+        match value {
+            InternalDueDayPolicy::Stepped => core_payment_plan::DueDayPolicy::Stepped,
+            InternalDueDayPolicy::Anchored => core_payment_plan::DueDayPolicy::Anchored,
+            InternalDueDayPolicy::EndOfMonth => core_payment_plan::DueDayPolicy::EndOfMonth,
+            InternalDueDayPolicy::FixedDay { day } => {
+                core_payment_plan::DueDayPolicy::FixedDay(day)
+            }
+        }
+    }
+}
+
+impl From<core_payment_plan::DueDayPolicy> for InternalDueDayPolicy {
+    fn from(value: core_payment_plan::DueDayPolicy) -> Self {
+        match value {
+            core_payment_plan::DueDayPolicy::Stepped => InternalDueDayPolicy::Stepped,
+            core_payment_plan::DueDayPolicy::Anchored => InternalDueDayPolicy::Anchored,
+            core_payment_plan::DueDayPolicy::EndOfMonth => InternalDueDayPolicy::EndOfMonth,
+            core_payment_plan::DueDayPolicy::FixedDay(day) => {
+                InternalDueDayPolicy::FixedDay { day }
+            }
+        }
+    }
+}
+
 #[derive(uniffi::Record)]
 pub struct InternalParams {
     pub requested_amount: f64,
@@ -17,6 +185,26 @@ pub struct InternalParams {
     pub min_installment_amount: f64,
     pub max_total_amount: f64,
     pub disbursement_only_on_business_days: bool,
+    #[uniffi(default = None)]
+    pub amortization_system: Option<InternalAmortizationSystem>,
+    #[uniffi(default = None)]
+    pub grace_period: Option<InternalGracePeriod>,
+    #[uniffi(default = None)]
+    pub balloon_percentage: Option<f64>,
+    #[uniffi(default = None)]
+    pub periodicity: Option<InternalPeriodicity>,
+    #[uniffi(default = None)]
+    pub due_day_policy: Option<InternalDueDayPolicy>,
+    #[uniffi(default = None)]
+    pub rounding_mode: Option<InternalRoundingMode>,
+    #[uniffi(default = None)]
+    pub residual_allocation: Option<InternalResidualAllocation>,
+    #[uniffi(default = None)]
+    pub business_day_convention: Option<InternalBusinessDayConvention>,
+    #[uniffi(default = None)]
+    pub iof_tolerance: Option<f64>,
+    #[uniffi(default = None)]
+    pub iof_max_iterations: Option<u32>,
 }
 
 impl Into<core_payment_plan::Params> for InternalParams {
@@ -26,6 +214,7 @@ impl Into<core_payment_plan::Params> for InternalParams {
 
         let disbursement_date = disbursement_date.date_naive();
         let first_payment_date = first_payment_date.date_naive();
+        let default_convergence = core_payment_plan::IofConvergence::default();
 
         core_payment_plan::Params {
             requested_amount: self.requested_amount,
@@ -41,6 +230,22 @@ impl Into<core_payment_plan::Params> for InternalParams {
             min_installment_amount: self.min_installment_amount,
             max_total_amount: self.max_total_amount,
             disbursement_only_on_business_days: self.disbursement_only_on_business_days,
+            amortization_system: self.amortization_system.unwrap_or_default().into(),
+            grace_period: self.grace_period.unwrap_or_default().into(),
+            balloon_percentage: self.balloon_percentage.unwrap_or_default(),
+            periodicity: self.periodicity.unwrap_or_default().into(),
+            due_day_policy: self.due_day_policy.unwrap_or_default().into(),
+            rounding_policy: core_payment_plan::RoundingPolicy {
+                mode: self.rounding_mode.unwrap_or_default().into(),
+                residual_allocation: self.residual_allocation.unwrap_or_default().into(),
+            },
+            business_day_convention: self.business_day_convention.unwrap_or_default().into(),
+            iof_convergence: core_payment_plan::IofConvergence {
+                tolerance: self.iof_tolerance.unwrap_or(default_convergence.tolerance),
+                max_iterations: self
+                    .iof_max_iterations
+                    .unwrap_or(default_convergence.max_iterations),
+            },
         }
     }
 }
@@ -68,3 +273,84 @@ impl Into<core_payment_plan::DownPaymentParams> for InternalDownPaymentParams {
         }
     }
 }
+
+#[derive(uniffi::Record)]
+pub struct InternalCustomScheduleParams {
+    pub params: InternalParams, // The params of the plan, the installments and first payment date are taken from the due dates
+    pub due_dates: Vec<SystemTime>, // The due date of each installment, in ascending order
+    #[uniffi(default = [])]
+    pub weights: Vec<f64>, // The relative size of each installment (ex: 2.0 is twice the others), empty for equal installments
+}
+
+impl From<InternalCustomScheduleParams> for core_payment_plan::CustomScheduleParams {
+    fn from(value: InternalCustomScheduleParams) -> Self {
+        let due_dates = value
+            .due_dates
+            .into_iter()
+            .map(|due_date| {
+                let due_date: DateTime<Utc> = due_date.into();
+                due_date.date_naive()
+            })
+            .collect();
+
+        Self {
+            params: value.params.into(),
+            due_dates,
+            weights: value.weights,
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct InternalEarlySettlementParams {
+    pub invoices: Vec<InternalInvoice>, // The invoices still open (ex: the invoices of a plan)
+    pub interest_rate: f64,
+    pub settlement_date: SystemTime,
+    pub disbursement_only_on_business_days: bool,
+}
+
+impl From<InternalEarlySettlementParams> for core_payment_plan::EarlySettlementParams {
+    fn from(value: InternalEarlySettlementParams) -> Self {
+        let settlement_date: DateTime<Utc> = value.settlement_date.into();
+        let settlement_date = settlement_date.date_naive();
+
+        Self {
+            invoices: value.invoices.into_iter().map(Into::into).collect(),
+            interest_rate: value.interest_rate,
+            settlement_date,
+            disbursement_only_on_business_days: value.disbursement_only_on_business_days,
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct InternalLateChargesParams {
+    pub invoice: InternalInvoice, // The overdue invoice (ex: an invoice of a plan)
+    pub interest_rate: f64,
+    pub payment_date: SystemTime,
+    #[uniffi(default = None)]
+    pub fine_rate: Option<f64>, // Defaults to 0.02
+    #[uniffi(default = None)]
+    pub mora_rate: Option<f64>, // Defaults to 0.01
+    pub iof_percentage: f64,
+}
+
+impl From<InternalLateChargesParams> for core_payment_plan::LateChargesParams {
+    fn from(value: InternalLateChargesParams) -> Self {
+        let payment_date: DateTime<Utc> = value.payment_date.into();
+        let payment_date = payment_date.date_naive();
+
+        Self {
+            invoice: value.invoice.into(),
+            interest_rate: value.interest_rate,
+            payment_date,
+            fine_rate: value
+                .fine_rate
+                .unwrap_or(core_payment_plan::DEFAULT_LATE_FINE_RATE),
+            mora_rate: value
+                .mora_rate
+                .unwrap_or(core_payment_plan::DEFAULT_LATE_MORA_RATE),
+            iof_percentage: value.iof_percentage,
+        }
+    }
+}
@@ -2,6 +2,8 @@ use std::time::SystemTime;
 
 use chrono::{DateTime, NaiveDateTime, Utc};
 
+use crate::params::InternalDueDayPolicy;
+
 #[derive(uniffi::Record)]
 pub struct InternalInvoice {
     pub accumulated_days: i64,
@@ -9,7 +11,14 @@ pub struct InternalInvoice {
     pub accumulated_factor: f64,
     pub main_iof_tac: f64,
     pub debit_service: f64,
+    pub amount: f64,
     pub due_date: SystemTime,
+    pub opening_balance: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub iof: f64,
+    pub tac: f64,
+    pub closing_balance: f64,
 }
 
 impl From<core_payment_plan::Invoice> for InternalInvoice {
@@ -26,7 +35,36 @@ impl From<core_payment_plan::Invoice> for InternalInvoice {
             accumulated_factor: value.accumulated_factor,
             main_iof_tac: value.main_iof_tac,
             debit_service: value.debit_service,
+            amount: value.amount,
             due_date,
+            opening_balance: value.opening_balance,
+            interest: value.interest,
+            principal: value.principal,
+            iof: value.iof,
+            tac: value.tac,
+            closing_balance: value.closing_balance,
+        }
+    }
+}
+
+impl From<InternalInvoice> for core_payment_plan::Invoice {
+    fn from(value: InternalInvoice) -> Self {
+        let due_date: DateTime<Utc> = value.due_date.into();
+
+        Self {
+            accumulated_days: value.accumulated_days,
+            factor: value.factor,
+            accumulated_factor: value.accumulated_factor,
+            main_iof_tac: value.main_iof_tac,
+            debit_service: value.debit_service,
+            amount: value.amount,
+            due_date: due_date.date_naive(),
+            opening_balance: value.opening_balance,
+            interest: value.interest,
+            principal: value.principal,
+            iof: value.iof,
+            tac: value.tac,
+            closing_balance: value.closing_balance,
         }
     }
 }
@@ -57,6 +95,8 @@ pub struct InternalResponse {
     pub tec_yearly: f64,
     pub eir_monthly: f64,
     pub tec_monthly: f64,
+    pub eir_periodic: f64,
+    pub tec_periodic: f64,
     pub total_iof: f64,
     pub contract_amount: f64,
     pub contract_amount_without_tac: f64,
@@ -66,6 +106,10 @@ pub struct InternalResponse {
     pub pre_disbursement_amount: f64,
     pub paid_total_iof: f64,
     pub paid_contract_amount: f64,
+    pub due_day_policy: InternalDueDayPolicy,
+    pub rounding_residual: f64,
+    pub iof_iterations: u32,
+    pub iof_residual: f64,
     pub invoices: Vec<InternalInvoice>,
 }
 
@@ -110,6 +154,8 @@ impl From<core_payment_plan::Response> for InternalResponse {
             tec_yearly: value.tec_yearly,
             eir_monthly: value.eir_monthly,
             tec_monthly: value.tec_monthly,
+            eir_periodic: value.eir_periodic,
+            tec_periodic: value.tec_periodic,
             total_iof: value.total_iof,
             contract_amount: value.contract_amount,
             contract_amount_without_tac: value.contract_amount_without_tac,
@@ -119,6 +165,10 @@ impl From<core_payment_plan::Response> for InternalResponse {
             pre_disbursement_amount: value.pre_disbursement_amount,
             paid_total_iof: value.paid_total_iof,
             paid_contract_amount: value.paid_contract_amount,
+            due_day_policy: value.due_day_policy.into(),
+            rounding_residual: value.rounding_residual,
+            iof_iterations: value.iof_iterations,
+            iof_residual: value.iof_residual,
             invoices: value.invoices.into_iter().map(Into::into).collect(),
         }
     }
@@ -152,3 +202,132 @@ impl From<core_payment_plan::DownPaymentResponse> for InternalDownPaymentRespons
         }
     }
 }
+
+#[derive(uniffi::Record)]
+pub struct InternalEarlySettlementInvoice {
+    pub due_date: SystemTime,
+    pub amount: f64,
+    pub days: i64,
+    pub present_value: f64,
+    pub discount: f64,
+}
+
+impl From<core_payment_plan::EarlySettlementInvoice> for InternalEarlySettlementInvoice {
+    fn from(value: core_payment_plan::EarlySettlementInvoice) -> Self {
+        let due_date: NaiveDateTime = value.due_date.into();
+        let due_date: DateTime<Utc> = DateTime::from_naive_utc_and_offset(due_date, Utc);
+        //add 10 hours to the due date
+        let due_date: DateTime<Utc> = due_date + chrono::Duration::hours(10);
+        let due_date: SystemTime = due_date.into();
+
+        Self {
+            due_date,
+            amount: value.amount,
+            days: value.days,
+            present_value: value.present_value,
+            discount: value.discount,
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct InternalEarlySettlementResponse {
+    pub settlement_date: SystemTime,
+    pub total_amount: f64,
+    pub settlement_amount: f64,
+    pub discount: f64,
+    pub invoices: Vec<InternalEarlySettlementInvoice>,
+}
+
+impl From<core_payment_plan::EarlySettlementResponse> for InternalEarlySettlementResponse {
+    fn from(value: core_payment_plan::EarlySettlementResponse) -> Self {
+        let settlement_date: NaiveDateTime = value.settlement_date.into();
+        let settlement_date: DateTime<Utc> =
+            DateTime::from_naive_utc_and_offset(settlement_date, Utc);
+        //add 10 hours to the settlement date
+        let settlement_date: DateTime<Utc> = settlement_date + chrono::Duration::hours(10);
+        let settlement_date: SystemTime = settlement_date.into();
+
+        Self {
+            settlement_date,
+            total_amount: value.total_amount,
+            settlement_amount: value.settlement_amount,
+            discount: value.discount,
+            invoices: value.invoices.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct InternalLateChargesResponse {
+    pub days_late: i64,
+    pub amount: f64,
+    pub fine: f64,
+    pub mora_interest: f64,
+    pub remunerative_interest: f64,
+    pub iof: f64,
+    pub total_amount: f64,
+}
+
+impl From<core_payment_plan::LateChargesResponse> for InternalLateChargesResponse {
+    fn from(value: core_payment_plan::LateChargesResponse) -> Self {
+        Self {
+            days_late: value.days_late,
+            amount: value.amount,
+            fine: value.fine,
+            mora_interest: value.mora_interest,
+            remunerative_interest: value.remunerative_interest,
+            iof: value.iof,
+            total_amount: value.total_amount,
+        }
+    }
+}
+
+#[derive(uniffi::Enum)]
+pub enum InternalExclusionReason {
+    MinInstallmentAmount,
+    MaxTotalAmount,
+}
+
+impl From<core_payment_plan::ExclusionReason> for InternalExclusionReason {
+    fn from(value: core_payment_plan::ExclusionReason) -> Self {
+        match value {
+            core_payment_plan::ExclusionReason::MinInstallmentAmount => {
+                InternalExclusionReason::MinInstallmentAmount
+            }
+            core_payment_plan::ExclusionReason::MaxTotalAmount => {
+                InternalExclusionReason::MaxTotalAmount
+            }
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct InternalExcludedInstallment {
+    pub installment: u32,
+    pub reason: InternalExclusionReason,
+}
+
+impl From<core_payment_plan::ExcludedInstallment> for InternalExcludedInstallment {
+    fn from(value: core_payment_plan::ExcludedInstallment) -> Self {
+        Self {
+            installment: value.installment,
+            reason: value.reason.into(),
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct InternalSelectedPlans {
+    pub plans: Vec<InternalResponse>,
+    pub excluded: Vec<InternalExcludedInstallment>,
+}
+
+impl From<core_payment_plan::SelectedPlans> for InternalSelectedPlans {
+    fn from(value: core_payment_plan::SelectedPlans) -> Self {
+        Self {
+            plans: value.plans.into_iter().map(Into::into).collect(),
+            excluded: value.excluded.into_iter().map(Into::into).collect(),
+        }
+    }
+}
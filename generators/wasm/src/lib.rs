@@ -1,8 +1,15 @@
+use core_payment_plan::err::ErrorKind;
 use js_sys::Array;
 use types::{
     date::Date,
-    param::{DownPaymentParams, Params},
-    response::{DownPaymentResponse, PaymentPlanResponse},
+    param::{
+        CustomScheduleParams, DownPaymentParams, EarlySettlementParams, LateChargesParams, Params,
+        Provider,
+    },
+    response::{
+        DownPaymentResponse, EarlySettlementResponse, LateChargesResponse, PaymentPlanResponse,
+        SelectedPlansResponse,
+    },
 };
 use wasm_bindgen::prelude::*;
 
@@ -14,14 +21,18 @@ mod types;
     js_name = "calculateDownPaymentPlan",
     unchecked_return_type = "Array<DownPaymentResponse>"
 )]
-pub fn calculate_down_payment_plan(p: DownPaymentParams) -> Result<Array, JsError> {
+pub fn calculate_down_payment_plan(
+    p: DownPaymentParams,
+    provider: Option<Provider>,
+) -> Result<Array, JsError> {
     let core_params: core_payment_plan::DownPaymentParams = match p.try_into() {
         Ok(params) => params,
         Err(e) => return Err(e),
     };
-    let results = match core_payment_plan::calculate_down_payment_plan(core_params) {
+    let provider = provider.map(Into::into).unwrap_or_default();
+    let results = match core_payment_plan::calculate_down_payment_plan_with(provider, core_params) {
         Ok(r) => r,
-        Err(e) => return Err(JsError::new(&e.to_string())),
+        Err(e) => return Err(payment_plan_error(e)),
     };
 
     let array = Array::new_with_length(results.len() as u32);
@@ -53,15 +64,16 @@ pub fn next_disbursement_date(base_date: js_sys::Date) -> Result<js_sys::Date, J
     js_name = "calculatePaymentPlan",
     unchecked_return_type = "Array<PaymentPlanResponse>"
 )]
-pub fn calculate_payment_plan(p: Params) -> Result<Array, JsError> {
+pub fn calculate_payment_plan(p: Params, provider: Option<Provider>) -> Result<Array, JsError> {
     let core_params: core_payment_plan::Params = match p.try_into() {
         Ok(params) => params,
         Err(e) => return Err(e),
     };
 
-    let results = match core_payment_plan::calculate_payment_plan(core_params) {
+    let provider = provider.map(Into::into).unwrap_or_default();
+    let results = match core_payment_plan::calculate_payment_plan_with(provider, core_params) {
         Ok(r) => r,
-        Err(e) => return Err(JsError::new(&e.to_string())),
+        Err(e) => return Err(payment_plan_error(e)),
     };
 
     let array = Array::new_with_length(results.len() as u32);
@@ -74,6 +86,85 @@ pub fn calculate_payment_plan(p: Params) -> Result<Array, JsError> {
     return Ok(array);
 }
 
+// The plans of the selected numbers of installments only (ex: [3, 6, 10, 12], or [10] for a single one)
+#[allow(non_snake_case)]
+#[wasm_bindgen(
+    js_name = "calculateSelectedPaymentPlans",
+    unchecked_return_type = "SelectedPlansResponse"
+)]
+pub fn calculate_selected_payment_plans(
+    p: Params,
+    installments: Vec<u32>,
+    provider: Option<Provider>,
+) -> Result<JsValue, JsError> {
+    let core_params: core_payment_plan::Params = p.try_into()?;
+
+    let provider = provider.map(Into::into).unwrap_or_default();
+    let result = match core_payment_plan::calculate_selected_payment_plans_with(
+        provider,
+        core_params,
+        &installments,
+    ) {
+        Ok(r) => r,
+        Err(e) => return Err(payment_plan_error(e)),
+    };
+
+    let result: SelectedPlansResponse = result.into();
+    Ok(result.into())
+}
+
+// The plan with one installment due on each of the due dates, always calculated by the Iterative provider
+#[allow(non_snake_case)]
+#[wasm_bindgen(
+    js_name = "calculateCustomPaymentPlan",
+    unchecked_return_type = "PaymentPlanResponse"
+)]
+pub fn calculate_custom_payment_plan(p: CustomScheduleParams) -> Result<JsValue, JsError> {
+    let core_params: core_payment_plan::CustomScheduleParams = p.try_into()?;
+
+    let result = match core_payment_plan::calculate_custom_payment_plan(&core_params) {
+        Ok(r) => r,
+        Err(e) => return Err(payment_plan_error(e)),
+    };
+
+    let result: PaymentPlanResponse = result.into();
+    Ok(result.into())
+}
+
+#[allow(non_snake_case)]
+#[wasm_bindgen(
+    js_name = "calculateEarlySettlement",
+    unchecked_return_type = "EarlySettlementResponse"
+)]
+pub fn calculate_early_settlement(p: EarlySettlementParams) -> Result<JsValue, JsError> {
+    let core_params: core_payment_plan::EarlySettlementParams = p.try_into()?;
+
+    let result = match core_payment_plan::calculate_early_settlement(&core_params) {
+        Ok(r) => r,
+        Err(e) => return Err(payment_plan_error(e)),
+    };
+
+    let result: EarlySettlementResponse = result.into();
+    Ok(result.into())
+}
+
+#[allow(non_snake_case)]
+#[wasm_bindgen(
+    js_name = "calculateLateCharges",
+    unchecked_return_type = "LateChargesResponse"
+)]
+pub fn calculate_late_charges(p: LateChargesParams) -> Result<JsValue, JsError> {
+    let core_params: core_payment_plan::LateChargesParams = p.try_into()?;
+
+    let result = match core_payment_plan::calculate_late_charges(&core_params) {
+        Ok(r) => r,
+        Err(e) => return Err(payment_plan_error(e)),
+    };
+
+    let result: LateChargesResponse = result.into();
+    Ok(result.into())
+}
+
 #[allow(non_snake_case)]
 #[wasm_bindgen(
     js_name = "disbursementDateRange",
@@ -132,3 +223,18 @@ pub fn get_non_business_days_between(
 
     return Ok(array);
 }
+
+// The error thrown with the `code` and the `kind` of the `PaymentPlanError`
+fn payment_plan_error(error: core_payment_plan::err::PaymentPlanError) -> JsError {
+    let js_error = JsError::new(&error.to_string());
+    let value: JsValue = js_error.clone().into();
+    let kind = match error.kind() {
+        ErrorKind::Validation => "validation",
+        ErrorKind::DateRange => "dateRange",
+        ErrorKind::Convergence => "convergence",
+        ErrorKind::Unreachable => "unreachable",
+    };
+    let _ = js_sys::Reflect::set(&value, &"code".into(), &error.code().into());
+    let _ = js_sys::Reflect::set(&value, &"kind".into(), &kind.into());
+    js_error
+}
@@ -4,6 +4,167 @@ use wasm_bindgen::JsError;
 
 use super::date::Date;
 
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum AmortizationSystem {
+    #[default]
+    Price,
+    Sac,
+}
+
+impl From<AmortizationSystem> for core_payment_plan::AmortizationSystem {
+    fn from(value: AmortizationSystem) -> Self {
+        match value {
+            AmortizationSystem::Price => core_payment_plan::AmortizationSystem::Price,
+            AmortizationSystem::Sac => core_payment_plan::AmortizationSystem::Sac,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum GracePeriod {
+    #[default]
+    Capitalized,
+    InterestOnly,
+}
+
+impl From<GracePeriod> for core_payment_plan::GracePeriod {
+    fn from(value: GracePeriod) -> Self {
+        match value {
+            GracePeriod::Capitalized => core_payment_plan::GracePeriod::Capitalized,
+            GracePeriod::InterestOnly => core_payment_plan::GracePeriod::InterestOnly,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum Periodicity {
+    #[default]
+    Monthly,
+    Biweekly,
+    Weekly,
+}
+
+impl From<Periodicity> for core_payment_plan::Periodicity {
+    fn from(value: Periodicity) -> Self {
+        match value {
+            Periodicity::Monthly => core_payment_plan::Periodicity::Monthly,
+            Periodicity::Biweekly => core_payment_plan::Periodicity::Biweekly,
+            Periodicity::Weekly => core_payment_plan::Periodicity::Weekly,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum BusinessDayConvention {
+    #[default]
+    Following,
+    ModifiedFollowing,
+    Preceding,
+}
+
+impl From<BusinessDayConvention> for core_payment_plan::BusinessDayConvention {
+    fn from(value: BusinessDayConvention) -> Self {
+        match value {
+            BusinessDayConvention::Following => core_payment_plan::BusinessDayConvention::Following,
+            BusinessDayConvention::ModifiedFollowing => {
+                core_payment_plan::BusinessDayConvention::ModifiedFollowing
+            }
+            BusinessDayConvention::Preceding => core_payment_plan::BusinessDayConvention::Preceding,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum Provider {
+    Simple,
+    Iterative,
+}
+
+impl From<Provider> for core_payment_plan::Provider {
+    fn from(value: Provider) -> Self {
+        match value {
+            Provider::Simple => core_payment_plan::Provider::Simple,
+            Provider::Iterative => core_payment_plan::Provider::Iterative,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum RoundingMode {
+    #[default]
+    HalfUp,
+    HalfEven,
+    Truncate,
+}
+
+impl From<RoundingMode> for core_payment_plan::RoundingMode {
+    fn from(value: RoundingMode) -> Self {
+        match value {
+            RoundingMode::HalfUp => core_payment_plan::RoundingMode::HalfUp,
+            RoundingMode::HalfEven => core_payment_plan::RoundingMode::HalfEven,
+            RoundingMode::Truncate => core_payment_plan::RoundingMode::Truncate,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum ResidualAllocation {
+    #[default]
+    None,
+    FirstInstallment,
+    LastInstallment,
+}
+
+impl From<ResidualAllocation> for core_payment_plan::ResidualAllocation {
+    fn from(value: ResidualAllocation) -> Self {
+        match value {
+            ResidualAllocation::None => core_payment_plan::ResidualAllocation::None,
+            ResidualAllocation::FirstInstallment => {
+                core_payment_plan::ResidualAllocation::FirstInstallment
+            }
+            ResidualAllocation::LastInstallment => {
+                core_payment_plan::ResidualAllocation::LastInstallment
+            }
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Deserialize, Clone, Copy, Default)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub enum DueDayPolicy {
+    #[default]
+    Stepped,
+    Anchored,
+    EndOfMonth,
+    FixedDay, // On the `dueDay` of every month
+}
+
+impl DueDayPolicy {
+    fn with_due_day(self, due_day: u32) -> core_payment_plan::DueDayPolicy {
+        match self {
+            DueDayPolicy::Stepped => core_payment_plan::DueDayPolicy::Stepped,
+            DueDayPolicy::Anchored => core_payment_plan::DueDayPolicy::Anchored,
+            DueDayPolicy::EndOfMonth => core_payment_plan::DueDayPolicy::EndOfMonth,
+            DueDayPolicy::FixedDay => core_payment_plan::DueDayPolicy::FixedDay(due_day),
+        }
+    }
+}
+
 #[allow(non_snake_case)]
 #[derive(Tsify, Debug, Deserialize, Clone)]
 #[tsify(from_wasm_abi)]
@@ -22,12 +183,46 @@ pub struct Params {
     pub min_installment_amount: f64,
     pub max_total_amount: f64,
     pub disbursement_only_on_business_days: bool,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub amortization_system: Option<AmortizationSystem>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub grace_period: Option<GracePeriod>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub balloon_percentage: Option<f64>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub periodicity: Option<Periodicity>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub due_day_policy: Option<DueDayPolicy>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub due_day: Option<u32>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub rounding_mode: Option<RoundingMode>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub residual_allocation: Option<ResidualAllocation>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub business_day_convention: Option<BusinessDayConvention>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub iof_tolerance: Option<f64>,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub iof_max_iterations: Option<u32>,
 }
 
 impl TryInto<core_payment_plan::Params> for Params {
     type Error = JsError;
 
     fn try_into(self) -> Result<core_payment_plan::Params, Self::Error> {
+        let default_convergence = core_payment_plan::IofConvergence::default();
         Ok(core_payment_plan::Params {
             requested_amount: self.requested_amount,
             first_payment_date: self.first_payment_date.try_into()?,
@@ -42,6 +237,25 @@ impl TryInto<core_payment_plan::Params> for Params {
             min_installment_amount: self.min_installment_amount,
             max_total_amount: self.max_total_amount,
             disbursement_only_on_business_days: self.disbursement_only_on_business_days,
+            amortization_system: self.amortization_system.unwrap_or_default().into(),
+            grace_period: self.grace_period.unwrap_or_default().into(),
+            balloon_percentage: self.balloon_percentage.unwrap_or_default(),
+            periodicity: self.periodicity.unwrap_or_default().into(),
+            due_day_policy: self
+                .due_day_policy
+                .unwrap_or_default()
+                .with_due_day(self.due_day.unwrap_or_default()),
+            rounding_policy: core_payment_plan::RoundingPolicy {
+                mode: self.rounding_mode.unwrap_or_default().into(),
+                residual_allocation: self.residual_allocation.unwrap_or_default().into(),
+            },
+            business_day_convention: self.business_day_convention.unwrap_or_default().into(),
+            iof_convergence: core_payment_plan::IofConvergence {
+                tolerance: self.iof_tolerance.unwrap_or(default_convergence.tolerance),
+                max_iterations: self
+                    .iof_max_iterations
+                    .unwrap_or(default_convergence.max_iterations),
+            },
         })
     }
 }
@@ -71,3 +285,132 @@ impl TryInto<core_payment_plan::DownPaymentParams> for DownPaymentParams {
         })
     }
 }
+
+#[allow(non_snake_case)]
+#[derive(Tsify, Debug, Deserialize, Clone)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomScheduleParams {
+    pub params: Params, // The params of the plan, the installments and first payment date are taken from the due dates
+    pub due_dates: Vec<Date>, // The due date of each installment, in ascending order
+    #[serde(default)]
+    #[tsify(optional)]
+    pub weights: Option<Vec<f64>>, // The relative size of each installment (ex: 2.0 is twice the others), empty for equal installments
+}
+
+impl TryInto<core_payment_plan::CustomScheduleParams> for CustomScheduleParams {
+    type Error = JsError;
+
+    fn try_into(self) -> Result<core_payment_plan::CustomScheduleParams, Self::Error> {
+        let mut due_dates = Vec::with_capacity(self.due_dates.len());
+        for due_date in self.due_dates {
+            due_dates.push(due_date.try_into()?);
+        }
+
+        Ok(core_payment_plan::CustomScheduleParams {
+            params: self.params.try_into()?,
+            due_dates,
+            weights: self.weights.unwrap_or_default(),
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Tsify, Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInvoice {
+    pub amount: f64,
+    pub due_date: Date,
+}
+
+#[allow(non_snake_case)]
+#[derive(Tsify, Debug, Deserialize, Clone)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct EarlySettlementParams {
+    pub invoices: Vec<OpenInvoice>, // The invoices still open (ex: the invoices of a plan)
+    pub interest_rate: f64,
+    pub settlement_date: Date,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub disbursement_only_on_business_days: Option<bool>,
+}
+
+impl TryInto<core_payment_plan::EarlySettlementParams> for EarlySettlementParams {
+    type Error = JsError;
+
+    fn try_into(self) -> Result<core_payment_plan::EarlySettlementParams, Self::Error> {
+        let mut invoices = Vec::with_capacity(self.invoices.len());
+        for invoice in self.invoices {
+            invoices.push(core_payment_plan::Invoice {
+                amount: invoice.amount,
+                due_date: invoice.due_date.try_into()?,
+                ..Default::default()
+            });
+        }
+
+        Ok(core_payment_plan::EarlySettlementParams {
+            invoices,
+            interest_rate: self.interest_rate,
+            settlement_date: self.settlement_date.try_into()?,
+            disbursement_only_on_business_days: self
+                .disbursement_only_on_business_days
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Tsify, Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OverdueInvoice {
+    pub amount: f64,
+    pub due_date: Date,
+    #[serde(rename = "mainIOFTAC")]
+    pub main_iof_tac: f64,
+    pub accumulated_days: i64,
+}
+
+#[allow(non_snake_case)]
+#[derive(Tsify, Debug, Deserialize, Clone)]
+#[tsify(from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct LateChargesParams {
+    pub invoice: OverdueInvoice, // The overdue invoice (ex: an invoice of a plan)
+    pub interest_rate: f64,
+    pub payment_date: Date,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub fine_rate: Option<f64>, // Defaults to 0.02
+    #[serde(default)]
+    #[tsify(optional)]
+    pub mora_rate: Option<f64>, // Defaults to 0.01
+    pub iof_percentage: f64,
+}
+
+impl TryInto<core_payment_plan::LateChargesParams> for LateChargesParams {
+    type Error = JsError;
+
+    fn try_into(self) -> Result<core_payment_plan::LateChargesParams, Self::Error> {
+        let invoice = core_payment_plan::Invoice {
+            amount: self.invoice.amount,
+            due_date: self.invoice.due_date.try_into()?,
+            main_iof_tac: self.invoice.main_iof_tac,
+            accumulated_days: self.invoice.accumulated_days,
+            ..Default::default()
+        };
+
+        Ok(core_payment_plan::LateChargesParams {
+            invoice,
+            interest_rate: self.interest_rate,
+            payment_date: self.payment_date.try_into()?,
+            fine_rate: self
+                .fine_rate
+                .unwrap_or(core_payment_plan::DEFAULT_LATE_FINE_RATE),
+            mora_rate: self
+                .mora_rate
+                .unwrap_or(core_payment_plan::DEFAULT_LATE_MORA_RATE),
+            iof_percentage: self.iof_percentage,
+        })
+    }
+}
@@ -13,7 +13,14 @@ pub struct Invoice {
     #[serde(rename = "mainIOFTAC")]
     pub main_iof_tac: f64,
     pub debit_service: f64,
+    pub amount: f64,
     pub due_date: Date,
+    pub opening_balance: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub iof: f64,
+    pub tac: f64,
+    pub closing_balance: f64,
 }
 
 impl From<core_payment_plan::Invoice> for Invoice {
@@ -24,7 +31,14 @@ impl From<core_payment_plan::Invoice> for Invoice {
             accumulated_factor: value.accumulated_factor,
             main_iof_tac: value.main_iof_tac,
             debit_service: value.debit_service,
+            amount: value.amount,
             due_date: value.due_date.into(),
+            opening_balance: value.opening_balance,
+            interest: value.interest,
+            principal: value.principal,
+            iof: value.iof,
+            tac: value.tac,
+            closing_balance: value.closing_balance,
         }
     }
 }
@@ -45,7 +59,14 @@ impl Into<js_sys::Object> for Invoice {
         );
         let _ = js_sys::Reflect::set(&obj, &"mainIOFTAC".into(), &self.main_iof_tac.into());
         let _ = js_sys::Reflect::set(&obj, &"debitService".into(), &self.debit_service.into());
+        let _ = js_sys::Reflect::set(&obj, &"amount".into(), &self.amount.into());
         let _ = js_sys::Reflect::set(&obj, &"dueDate".into(), &self.due_date.into());
+        let _ = js_sys::Reflect::set(&obj, &"openingBalance".into(), &self.opening_balance.into());
+        let _ = js_sys::Reflect::set(&obj, &"interest".into(), &self.interest.into());
+        let _ = js_sys::Reflect::set(&obj, &"principal".into(), &self.principal.into());
+        let _ = js_sys::Reflect::set(&obj, &"iof".into(), &self.iof.into());
+        let _ = js_sys::Reflect::set(&obj, &"tac".into(), &self.tac.into());
+        let _ = js_sys::Reflect::set(&obj, &"closingBalance".into(), &self.closing_balance.into());
         obj
     }
 }
@@ -78,6 +99,8 @@ pub struct PaymentPlanResponse {
     pub tec_yearly: f64,
     pub eir_monthly: f64,
     pub tec_monthly: f64,
+    pub eir_periodic: f64,
+    pub tec_periodic: f64,
     #[serde(rename = "totalIOF")]
     pub total_iof: f64,
     pub contract_amount: f64,
@@ -95,6 +118,11 @@ pub struct PaymentPlanResponse {
     pub paid_contract_amount: f64,
     #[serde(rename = "preDisbursementAmount")]
     pub pre_disbursement_amount: f64,
+    pub due_day_policy: String, // stepped, anchored, endOfMonth or fixedDay
+    pub due_day: u32,           // The day of the month with the fixedDay policy, 0 otherwise
+    pub rounding_residual: f64,
+    pub iof_iterations: u32,
+    pub iof_residual: f64,
     pub invoices: Vec<Invoice>,
 }
 
@@ -125,6 +153,8 @@ impl From<core_payment_plan::Response> for PaymentPlanResponse {
             tec_yearly: value.tec_yearly,
             eir_monthly: value.eir_monthly,
             tec_monthly: value.tec_monthly,
+            eir_periodic: value.eir_periodic,
+            tec_periodic: value.tec_periodic,
             total_iof: value.total_iof,
             contract_amount: value.contract_amount,
             contract_amount_without_tac: value.contract_amount_without_tac,
@@ -134,6 +164,20 @@ impl From<core_payment_plan::Response> for PaymentPlanResponse {
             disbursement_date: value.disbursement_date.into(),
             paid_total_iof: value.paid_total_iof,
             paid_contract_amount: value.paid_contract_amount,
+            due_day_policy: match value.due_day_policy {
+                core_payment_plan::DueDayPolicy::Stepped => "stepped",
+                core_payment_plan::DueDayPolicy::Anchored => "anchored",
+                core_payment_plan::DueDayPolicy::EndOfMonth => "endOfMonth",
+                core_payment_plan::DueDayPolicy::FixedDay(_) => "fixedDay",
+            }
+            .to_string(),
+            due_day: match value.due_day_policy {
+                core_payment_plan::DueDayPolicy::FixedDay(day) => day,
+                _ => 0,
+            },
+            rounding_residual: value.rounding_residual,
+            iof_iterations: value.iof_iterations,
+            iof_residual: value.iof_residual,
             pre_disbursement_amount: value.pre_disbursement_amount,
             invoices: value.invoices.into_iter().map(|i| i.into()).collect(),
         }
@@ -210,6 +254,8 @@ impl Into<js_sys::Object> for PaymentPlanResponse {
         let _ = js_sys::Reflect::set(&obj, &"tecYearly".into(), &self.tec_yearly.into());
         let _ = js_sys::Reflect::set(&obj, &"eirMonthly".into(), &self.eir_monthly.into());
         let _ = js_sys::Reflect::set(&obj, &"tecMonthly".into(), &self.tec_monthly.into());
+        let _ = js_sys::Reflect::set(&obj, &"eirPeriodic".into(), &self.eir_periodic.into());
+        let _ = js_sys::Reflect::set(&obj, &"tecPeriodic".into(), &self.tec_periodic.into());
         let _ = js_sys::Reflect::set(&obj, &"totalIOF".into(), &self.total_iof.into());
         let _ = js_sys::Reflect::set(&obj, &"contractAmount".into(), &self.contract_amount.into());
         let _ = js_sys::Reflect::set(
@@ -237,6 +283,15 @@ impl Into<js_sys::Object> for PaymentPlanResponse {
             &"preDisbursementAmount".into(),
             &self.pre_disbursement_amount.into(),
         );
+        let _ = js_sys::Reflect::set(&obj, &"dueDayPolicy".into(), &self.due_day_policy.into());
+        let _ = js_sys::Reflect::set(&obj, &"dueDay".into(), &self.due_day.into());
+        let _ = js_sys::Reflect::set(
+            &obj,
+            &"roundingResidual".into(),
+            &self.rounding_residual.into(),
+        );
+        let _ = js_sys::Reflect::set(&obj, &"iofIterations".into(), &self.iof_iterations.into());
+        let _ = js_sys::Reflect::set(&obj, &"iofResidual".into(), &self.iof_residual.into());
         let array = js_sys::Array::new_with_length(self.invoices.len() as u32);
         for (i, invoice) in self.invoices.into_iter().enumerate() {
             let js_invoice: js_sys::Object = invoice.into();
@@ -313,3 +368,221 @@ impl From<core_payment_plan::DownPaymentResponse> for DownPaymentResponse {
         }
     }
 }
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct EarlySettlementInvoice {
+    pub due_date: Date,
+    pub amount: f64,
+    pub days: i32,
+    pub present_value: f64,
+    pub discount: f64,
+}
+
+impl From<core_payment_plan::EarlySettlementInvoice> for EarlySettlementInvoice {
+    fn from(value: core_payment_plan::EarlySettlementInvoice) -> Self {
+        Self {
+            due_date: value.due_date.into(),
+            amount: value.amount,
+            days: value.days as i32,
+            present_value: value.present_value,
+            discount: value.discount,
+        }
+    }
+}
+
+impl From<EarlySettlementInvoice> for js_sys::Object {
+    fn from(value: EarlySettlementInvoice) -> Self {
+        let obj = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&obj, &"dueDate".into(), &value.due_date.into());
+        let _ = js_sys::Reflect::set(&obj, &"amount".into(), &value.amount.into());
+        let _ = js_sys::Reflect::set(&obj, &"days".into(), &value.days.into());
+        let _ = js_sys::Reflect::set(&obj, &"presentValue".into(), &value.present_value.into());
+        let _ = js_sys::Reflect::set(&obj, &"discount".into(), &value.discount.into());
+        obj
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct EarlySettlementResponse {
+    pub settlement_date: Date,
+    pub total_amount: f64,
+    pub settlement_amount: f64,
+    pub discount: f64,
+    pub invoices: Vec<EarlySettlementInvoice>,
+}
+
+impl From<core_payment_plan::EarlySettlementResponse> for EarlySettlementResponse {
+    fn from(value: core_payment_plan::EarlySettlementResponse) -> Self {
+        Self {
+            settlement_date: value.settlement_date.into(),
+            total_amount: value.total_amount,
+            settlement_amount: value.settlement_amount,
+            discount: value.discount,
+            invoices: value.invoices.into_iter().map(|i| i.into()).collect(),
+        }
+    }
+}
+
+impl From<EarlySettlementResponse> for js_sys::Object {
+    fn from(value: EarlySettlementResponse) -> Self {
+        let obj = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(
+            &obj,
+            &"settlementDate".into(),
+            &value.settlement_date.into(),
+        );
+        let _ = js_sys::Reflect::set(&obj, &"totalAmount".into(), &value.total_amount.into());
+        let _ = js_sys::Reflect::set(
+            &obj,
+            &"settlementAmount".into(),
+            &value.settlement_amount.into(),
+        );
+        let _ = js_sys::Reflect::set(&obj, &"discount".into(), &value.discount.into());
+
+        let array = js_sys::Array::new_with_length(value.invoices.len() as u32);
+        for (i, invoice) in value.invoices.into_iter().enumerate() {
+            let js_invoice: js_sys::Object = invoice.into();
+            let _ = js_sys::Reflect::set(&array, &i.into(), &js_invoice.into());
+        }
+        let _ = js_sys::Reflect::set(&obj, &"invoices".into(), &array.into());
+        obj
+    }
+}
+
+impl From<EarlySettlementResponse> for JsValue {
+    fn from(value: EarlySettlementResponse) -> Self {
+        let obj: js_sys::Object = value.into();
+        obj.into()
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct LateChargesResponse {
+    pub days_late: i32,
+    pub amount: f64,
+    pub fine: f64,
+    pub mora_interest: f64,
+    pub remunerative_interest: f64,
+    pub iof: f64,
+    pub total_amount: f64,
+}
+
+impl From<core_payment_plan::LateChargesResponse> for LateChargesResponse {
+    fn from(value: core_payment_plan::LateChargesResponse) -> Self {
+        Self {
+            days_late: value.days_late as i32,
+            amount: value.amount,
+            fine: value.fine,
+            mora_interest: value.mora_interest,
+            remunerative_interest: value.remunerative_interest,
+            iof: value.iof,
+            total_amount: value.total_amount,
+        }
+    }
+}
+
+impl From<LateChargesResponse> for js_sys::Object {
+    fn from(value: LateChargesResponse) -> Self {
+        let obj = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&obj, &"daysLate".into(), &value.days_late.into());
+        let _ = js_sys::Reflect::set(&obj, &"amount".into(), &value.amount.into());
+        let _ = js_sys::Reflect::set(&obj, &"fine".into(), &value.fine.into());
+        let _ = js_sys::Reflect::set(&obj, &"moraInterest".into(), &value.mora_interest.into());
+        let _ = js_sys::Reflect::set(
+            &obj,
+            &"remunerativeInterest".into(),
+            &value.remunerative_interest.into(),
+        );
+        let _ = js_sys::Reflect::set(&obj, &"iof".into(), &value.iof.into());
+        let _ = js_sys::Reflect::set(&obj, &"totalAmount".into(), &value.total_amount.into());
+        obj
+    }
+}
+
+impl From<LateChargesResponse> for JsValue {
+    fn from(value: LateChargesResponse) -> Self {
+        let obj: js_sys::Object = value.into();
+        obj.into()
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct ExcludedInstallment {
+    pub installment: u32,
+    pub reason: String, // minInstallmentAmount or maxTotalAmount
+}
+
+impl From<core_payment_plan::ExcludedInstallment> for ExcludedInstallment {
+    fn from(value: core_payment_plan::ExcludedInstallment) -> Self {
+        Self {
+            installment: value.installment,
+            reason: match value.reason {
+                core_payment_plan::ExclusionReason::MinInstallmentAmount => "minInstallmentAmount",
+                core_payment_plan::ExclusionReason::MaxTotalAmount => "maxTotalAmount",
+            }
+            .to_string(),
+        }
+    }
+}
+
+impl From<ExcludedInstallment> for js_sys::Object {
+    fn from(value: ExcludedInstallment) -> Self {
+        let obj = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&obj, &"installment".into(), &value.installment.into());
+        let _ = js_sys::Reflect::set(&obj, &"reason".into(), &value.reason.into());
+        obj
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectedPlansResponse {
+    pub plans: Vec<PaymentPlanResponse>,
+    pub excluded: Vec<ExcludedInstallment>,
+}
+
+impl From<core_payment_plan::SelectedPlans> for SelectedPlansResponse {
+    fn from(value: core_payment_plan::SelectedPlans) -> Self {
+        Self {
+            plans: value.plans.into_iter().map(|p| p.into()).collect(),
+            excluded: value.excluded.into_iter().map(|e| e.into()).collect(),
+        }
+    }
+}
+
+impl From<SelectedPlansResponse> for js_sys::Object {
+    fn from(value: SelectedPlansResponse) -> Self {
+        let obj = js_sys::Object::new();
+
+        let plans = js_sys::Array::new_with_length(value.plans.len() as u32);
+        for (i, plan) in value.plans.into_iter().enumerate() {
+            let js_plan: js_sys::Object = plan.into();
+            let _ = js_sys::Reflect::set(&plans, &i.into(), &js_plan.into());
+        }
+        let _ = js_sys::Reflect::set(&obj, &"plans".into(), &plans.into());
+
+        let excluded = js_sys::Array::new_with_length(value.excluded.len() as u32);
+        for (i, installment) in value.excluded.into_iter().enumerate() {
+            let js_installment: js_sys::Object = installment.into();
+            let _ = js_sys::Reflect::set(&excluded, &i.into(), &js_installment.into());
+        }
+        let _ = js_sys::Reflect::set(&obj, &"excluded".into(), &excluded.into());
+        obj
+    }
+}
+
+impl From<SelectedPlansResponse> for JsValue {
+    fn from(value: SelectedPlansResponse) -> Self {
+        let obj: js_sys::Object = value.into();
+        obj.into()
+    }
+}
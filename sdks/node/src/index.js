@@ -1,6 +1,10 @@
 const funcs = require('../native/index.node');
 const __calculatePlan = funcs.calculatePlan;
+const __calculateSelectedPlans = funcs.calculateSelectedPlans;
+const __calculateCustomPlan = funcs.calculateCustomPlan;
 const __calculateDownPaymentPlan = funcs.calculateDownPaymentPlan;
+const __calculateEarlySettlement = funcs.calculateEarlySettlement;
+const __calculateLateCharges = funcs.calculateLateCharges;
 const __nextDisbursementDate = funcs.nextDisbursementDate;
 const __disbursementDateRange = funcs.disbursementDateRange;
 const __getNonBusinessDaysBetween = funcs.getNonBusinessDaysBetween;
@@ -21,6 +25,17 @@ const __getNonBusinessDaysBetween = funcs.getNonBusinessDaysBetween;
  * @property {number} [minInstallmentAmount]
  * @property {number} [maxTotalAmount]
  * @property {boolean} [disbursementOnlyOnBusinessDays]
+ * @property {'price' | 'sac'} [amortizationSystem]
+ * @property {'capitalized' | 'interestOnly'} [gracePeriod]
+ * @property {number} [balloonPercentage]
+ * @property {'monthly' | 'biweekly' | 'weekly'} [periodicity]
+ * @property {'stepped' | 'anchored' | 'endOfMonth' | 'fixedDay'} [dueDayPolicy]
+ * @property {number} [dueDay]
+ * @property {'halfUp' | 'halfEven' | 'truncate'} [roundingMode]
+ * @property {'none' | 'firstInstallment' | 'lastInstallment'} [residualAllocation]
+ * @property {'following' | 'modifiedFollowing' | 'preceding'} [businessDayConvention]
+ * @property {number} [iofTolerance]
+ * @property {number} [iofMaxIterations]
  */
 
 /**
@@ -30,7 +45,14 @@ const __getNonBusinessDaysBetween = funcs.getNonBusinessDaysBetween;
  * @property {number} accumulatedFactor
  * @property {number} mainIOFTAC
  * @property {number} debitService
+ * @property {number} amount
  * @property {Date} dueDate
+ * @property {number} openingBalance
+ * @property {number} interest
+ * @property {number} principal
+ * @property {number} iof
+ * @property {number} tac
+ * @property {number} closingBalance
  */
 
 /**
@@ -59,6 +81,8 @@ const __getNonBusinessDaysBetween = funcs.getNonBusinessDaysBetween;
  * @property {number} tecYearly
  * @property {number} eirMonthly
  * @property {number} tecMonthly
+ * @property {number} eirPeriodic
+ * @property {number} tecPeriodic
  * @property {number} totalIOF
  * @property {number} contractAmount
  * @property {number} contractAmountWithoutTAC
@@ -68,6 +92,11 @@ const __getNonBusinessDaysBetween = funcs.getNonBusinessDaysBetween;
  * @property {number} preDisbursementAmount
  * @property {number} paidTotalIOF
  * @property {number} paidContractAmount
+ * @property {'stepped' | 'anchored' | 'endOfMonth' | 'fixedDay'} dueDayPolicy
+ * @property {number} dueDay
+ * @property {number} roundingResidual
+ * @property {number} iofIterations
+ * @property {number} iofResidual
  * @property {Invoice[]} invoices
  */
 
@@ -89,20 +118,146 @@ const __getNonBusinessDaysBetween = funcs.getNonBusinessDaysBetween;
  * @property {PaymentPlanResponse[]} plans
  */
 
+/**
+ * @typedef {Object} CustomSchedulePlanParams
+ * @property {PaymentPlanParams} params
+ * @property {Date[]} dueDates
+ * @property {number[]} [weights]
+ */
+
+/**
+ * @typedef {Object} OpenInvoice
+ * @property {number} amount
+ * @property {Date} dueDate
+ */
+
+/**
+ * @typedef {Object} EarlySettlementParams
+ * @property {OpenInvoice[]} invoices
+ * @property {number} interestRate
+ * @property {Date} settlementDate
+ * @property {boolean} [disbursementOnlyOnBusinessDays]
+ */
+
+/**
+ * @typedef {Object} EarlySettlementInvoice
+ * @property {Date} dueDate
+ * @property {number} amount
+ * @property {number} days
+ * @property {number} presentValue
+ * @property {number} discount
+ */
+
+/**
+ * @typedef {Object} EarlySettlementResponse
+ * @property {Date} settlementDate
+ * @property {number} totalAmount
+ * @property {number} settlementAmount
+ * @property {number} discount
+ * @property {EarlySettlementInvoice[]} invoices
+ */
+
+/**
+ * @typedef {Object} OverdueInvoice
+ * @property {number} amount
+ * @property {Date} dueDate
+ * @property {number} mainIOFTAC
+ * @property {number} accumulatedDays
+ */
+
+/**
+ * @typedef {Object} LateChargesParams
+ * @property {OverdueInvoice} invoice
+ * @property {number} interestRate
+ * @property {Date} paymentDate
+ * @property {number} [fineRate]
+ * @property {number} [moraRate]
+ * @property {number} iofPercentage
+ */
+
+/**
+ * @typedef {Object} LateChargesResponse
+ * @property {number} daysLate
+ * @property {number} amount
+ * @property {number} fine
+ * @property {number} moraInterest
+ * @property {number} remunerativeInterest
+ * @property {number} iof
+ * @property {number} totalAmount
+ */
+
+/**
+ * @typedef {'minInstallmentAmount' | 'maxTotalAmount'} ExclusionReason
+ */
+
+/**
+ * @typedef {Object} ExcludedInstallment
+ * @property {number} installment
+ * @property {ExclusionReason} reason
+ */
+
+/**
+ * @typedef {Object} SelectedPlansResponse
+ * @property {PaymentPlanResponse[]} plans
+ * @property {ExcludedInstallment[]} excluded
+ */
+
+/**
+ * @typedef {'simple' | 'iterative'} Provider
+ */
+
 /**
  * @param {PaymentPlanParams} params
+ * @param {Provider} [provider]
  * @returns {PaymentPlanResponse[]}
  */
-function calculatePlan(params) {
-  return __calculatePlan(params);
+function calculatePlan(params, provider) {
+  return __calculatePlan(params, provider);
+}
+
+/**
+ * The plans of the selected numbers of installments only (ex: [3, 6, 10, 12], or [10] for a single one)
+ * @param {PaymentPlanParams} params
+ * @param {number[]} installments
+ * @param {Provider} [provider]
+ * @returns {SelectedPlansResponse}
+ */
+function calculateSelectedPlans(params, installments, provider) {
+  return __calculateSelectedPlans(params, installments, provider);
+}
+
+/**
+ * The plan with one installment due on each of the due dates, always calculated by the Iterative provider
+ * @param {CustomSchedulePlanParams} params
+ * @returns {PaymentPlanResponse}
+ */
+function calculateCustomPlan(params) {
+  return __calculateCustomPlan(params);
 }
 
 /**
  * @param {DownPaymentPlanParams} params
+ * @param {Provider} [provider]
  * @returns {DownPaymentPlanResponse[]}
  */
-function calculateDownPaymentPlan(params) {
-  return __calculateDownPaymentPlan(params);
+function calculateDownPaymentPlan(params, provider) {
+  return __calculateDownPaymentPlan(params, provider);
+}
+
+/**
+ * @param {EarlySettlementParams} params
+ * @returns {EarlySettlementResponse}
+ */
+function calculateEarlySettlement(params) {
+  return __calculateEarlySettlement(params);
+}
+
+/**
+ * @param {LateChargesParams} params
+ * @returns {LateChargesResponse}
+ */
+function calculateLateCharges(params) {
+  return __calculateLateCharges(params);
 }
 
 /**
@@ -133,7 +288,11 @@ function getNonBusinessDaysBetween(start_date, end_date) {
 
 module.exports = {
   calculatePlan,
+  calculateSelectedPlans,
+  calculateCustomPlan,
   calculateDownPaymentPlan,
+  calculateEarlySettlement,
+  calculateLateCharges,
   nextDisbursementDate,
   disbursementDateRange,
   getNonBusinessDaysBetween,
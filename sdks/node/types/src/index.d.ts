@@ -12,14 +12,33 @@ export type PaymentPlanParams = {
     minInstallmentAmount?: number;
     maxTotalAmount?: number;
     disbursementOnlyOnBusinessDays?: boolean;
+    amortizationSystem?: 'price' | 'sac';
+    gracePeriod?: 'capitalized' | 'interestOnly';
+    balloonPercentage?: number;
+    periodicity?: 'monthly' | 'biweekly' | 'weekly';
+    dueDayPolicy?: 'stepped' | 'anchored' | 'endOfMonth' | 'fixedDay';
+    dueDay?: number;
+    roundingMode?: 'halfUp' | 'halfEven' | 'truncate';
+    residualAllocation?: 'none' | 'firstInstallment' | 'lastInstallment';
+    businessDayConvention?: 'following' | 'modifiedFollowing' | 'preceding';
+    iofTolerance?: number;
+    iofMaxIterations?: number;
 };
+export type Provider = 'simple' | 'iterative';
 export type Invoice = {
     accumulatedDays: number;
     factor: number;
     accumulatedFactor: number;
     mainIOFTAC: number;
     debitService: number;
+    amount: number;
     dueDate: Date;
+    openingBalance: number;
+    interest: number;
+    principal: number;
+    iof: number;
+    tac: number;
+    closingBalance: number;
 };
 export type PaymentPlanResponse = {
     installment: number;
@@ -46,6 +65,8 @@ export type PaymentPlanResponse = {
     tecYearly: number;
     eirMonthly: number;
     tecMonthly: number;
+    eirPeriodic: number;
+    tecPeriodic: number;
     totalIOF: number;
     contractAmount: number;
     contractAmountWithoutTAC: number;
@@ -55,6 +76,11 @@ export type PaymentPlanResponse = {
     preDisbursementAmount: number;
     paidTotalIOF: number;
     paidContractAmount: number;
+    dueDayPolicy: 'stepped' | 'anchored' | 'endOfMonth' | 'fixedDay';
+    dueDay: number;
+    roundingResidual: number;
+    iofIterations: number;
+    iofResidual: number;
     invoices: Invoice[];
 };
 export type DownPaymentPlanParams = {
@@ -71,6 +97,67 @@ export type DownPaymentPlanResponse = {
     firstPaymentDate: Date;
     plans: PaymentPlanResponse[];
 };
+export type CustomSchedulePlanParams = {
+    params: PaymentPlanParams;
+    dueDates: Date[];
+    weights?: number[];
+};
+export type OpenInvoice = {
+    amount: number;
+    dueDate: Date;
+};
+export type EarlySettlementParams = {
+    invoices: OpenInvoice[];
+    interestRate: number;
+    settlementDate: Date;
+    disbursementOnlyOnBusinessDays?: boolean;
+};
+export type EarlySettlementInvoice = {
+    dueDate: Date;
+    amount: number;
+    days: number;
+    presentValue: number;
+    discount: number;
+};
+export type EarlySettlementResponse = {
+    settlementDate: Date;
+    totalAmount: number;
+    settlementAmount: number;
+    discount: number;
+    invoices: EarlySettlementInvoice[];
+};
+export type OverdueInvoice = {
+    amount: number;
+    dueDate: Date;
+    mainIOFTAC: number;
+    accumulatedDays: number;
+};
+export type LateChargesParams = {
+    invoice: OverdueInvoice;
+    interestRate: number;
+    paymentDate: Date;
+    fineRate?: number;
+    moraRate?: number;
+    iofPercentage: number;
+};
+export type LateChargesResponse = {
+    daysLate: number;
+    amount: number;
+    fine: number;
+    moraInterest: number;
+    remunerativeInterest: number;
+    iof: number;
+    totalAmount: number;
+};
+export type ExclusionReason = 'minInstallmentAmount' | 'maxTotalAmount';
+export type ExcludedInstallment = {
+    installment: number;
+    reason: ExclusionReason;
+};
+export type SelectedPlansResponse = {
+    plans: PaymentPlanResponse[];
+    excluded: ExcludedInstallment[];
+};
 /**
  * @typedef {Object} PaymentPlanParams
  * @property {number} requestedAmount
@@ -86,6 +173,17 @@ export type DownPaymentPlanResponse = {
  * @property {number} [minInstallmentAmount]
  * @property {number} [maxTotalAmount]
  * @property {boolean} [disbursementOnlyOnBusinessDays]
+ * @property {'price' | 'sac'} [amortizationSystem]
+ * @property {'capitalized' | 'interestOnly'} [gracePeriod]
+ * @property {number} [balloonPercentage]
+ * @property {'monthly' | 'biweekly' | 'weekly'} [periodicity]
+ * @property {'stepped' | 'anchored' | 'endOfMonth' | 'fixedDay'} [dueDayPolicy]
+ * @property {number} [dueDay]
+ * @property {'halfUp' | 'halfEven' | 'truncate'} [roundingMode]
+ * @property {'none' | 'firstInstallment' | 'lastInstallment'} [residualAllocation]
+ * @property {'following' | 'modifiedFollowing' | 'preceding'} [businessDayConvention]
+ * @property {number} [iofTolerance]
+ * @property {number} [iofMaxIterations]
  */
 /**
  * @typedef {Object} Invoice
@@ -94,7 +192,14 @@ export type DownPaymentPlanResponse = {
  * @property {number} accumulatedFactor
  * @property {number} mainIOFTAC
  * @property {number} debitService
+ * @property {number} amount
  * @property {Date} dueDate
+ * @property {number} openingBalance
+ * @property {number} interest
+ * @property {number} principal
+ * @property {number} iof
+ * @property {number} tac
+ * @property {number} closingBalance
  */
 /**
  * @typedef {Object} PaymentPlanResponse
@@ -122,6 +227,8 @@ export type DownPaymentPlanResponse = {
  * @property {number} tecYearly
  * @property {number} eirMonthly
  * @property {number} tecMonthly
+ * @property {number} eirPeriodic
+ * @property {number} tecPeriodic
  * @property {number} totalIOF
  * @property {number} contractAmount
  * @property {number} contractAmountWithoutTAC
@@ -131,6 +238,11 @@ export type DownPaymentPlanResponse = {
  * @property {number} preDisbursementAmount
  * @property {number} paidTotalIOF
  * @property {number} paidContractAmount
+ * @property {'stepped' | 'anchored' | 'endOfMonth' | 'fixedDay'} dueDayPolicy
+ * @property {number} dueDay
+ * @property {number} roundingResidual
+ * @property {number} iofIterations
+ * @property {number} iofResidual
  * @property {Invoice[]} invoices
  */
 /**
@@ -149,16 +261,118 @@ export type DownPaymentPlanResponse = {
  * @property {Date} firstPaymentDate
  * @property {PaymentPlanResponse[]} plans
  */
+/**
+ * @typedef {Object} CustomSchedulePlanParams
+ * @property {PaymentPlanParams} params
+ * @property {Date[]} dueDates
+ * @property {number[]} [weights]
+ */
+/**
+ * @typedef {Object} OpenInvoice
+ * @property {number} amount
+ * @property {Date} dueDate
+ */
+/**
+ * @typedef {Object} EarlySettlementParams
+ * @property {OpenInvoice[]} invoices
+ * @property {number} interestRate
+ * @property {Date} settlementDate
+ * @property {boolean} [disbursementOnlyOnBusinessDays]
+ */
+/**
+ * @typedef {Object} EarlySettlementInvoice
+ * @property {Date} dueDate
+ * @property {number} amount
+ * @property {number} days
+ * @property {number} presentValue
+ * @property {number} discount
+ */
+/**
+ * @typedef {Object} EarlySettlementResponse
+ * @property {Date} settlementDate
+ * @property {number} totalAmount
+ * @property {number} settlementAmount
+ * @property {number} discount
+ * @property {EarlySettlementInvoice[]} invoices
+ */
+/**
+ * @typedef {Object} OverdueInvoice
+ * @property {number} amount
+ * @property {Date} dueDate
+ * @property {number} mainIOFTAC
+ * @property {number} accumulatedDays
+ */
+/**
+ * @typedef {Object} LateChargesParams
+ * @property {OverdueInvoice} invoice
+ * @property {number} interestRate
+ * @property {Date} paymentDate
+ * @property {number} [fineRate]
+ * @property {number} [moraRate]
+ * @property {number} iofPercentage
+ */
+/**
+ * @typedef {Object} LateChargesResponse
+ * @property {number} daysLate
+ * @property {number} amount
+ * @property {number} fine
+ * @property {number} moraInterest
+ * @property {number} remunerativeInterest
+ * @property {number} iof
+ * @property {number} totalAmount
+ */
+/**
+ * @typedef {'minInstallmentAmount' | 'maxTotalAmount'} ExclusionReason
+ */
+/**
+ * @typedef {Object} ExcludedInstallment
+ * @property {number} installment
+ * @property {ExclusionReason} reason
+ */
+/**
+ * @typedef {Object} SelectedPlansResponse
+ * @property {PaymentPlanResponse[]} plans
+ * @property {ExcludedInstallment[]} excluded
+ */
+/**
+ * @typedef {'simple' | 'iterative'} Provider
+ */
 /**
  * @param {PaymentPlanParams} params
+ * @param {Provider} [provider]
  * @returns {PaymentPlanResponse[]}
  */
-export function calculatePlan(params: PaymentPlanParams): PaymentPlanResponse[];
+export function calculatePlan(params: PaymentPlanParams, provider?: Provider): PaymentPlanResponse[];
+/**
+ * The plans of the selected numbers of installments only (ex: [3, 6, 10, 12], or [10] for a single one)
+ * @param {PaymentPlanParams} params
+ * @param {number[]} installments
+ * @param {Provider} [provider]
+ * @returns {SelectedPlansResponse}
+ */
+export function calculateSelectedPlans(params: PaymentPlanParams, installments: number[], provider?: Provider): SelectedPlansResponse;
+/**
+ * The plan with one installment due on each of the due dates, always calculated by the Iterative provider
+ * @param {CustomSchedulePlanParams} params
+ * @returns {PaymentPlanResponse}
+ */
+export function calculateCustomPlan(params: CustomSchedulePlanParams): PaymentPlanResponse;
 /**
  * @param {DownPaymentPlanParams} params
+ * @param {Provider} [provider]
  * @returns {DownPaymentPlanResponse[]}
  */
-export function calculateDownPaymentPlan(params: DownPaymentPlanParams): DownPaymentPlanResponse[];
+export function calculateDownPaymentPlan(params: DownPaymentPlanParams, provider?: Provider): DownPaymentPlanResponse[];
+/**
+ * @param {EarlySettlementParams} params
+ * @returns {EarlySettlementResponse}
+ */
+export function calculateEarlySettlement(params: EarlySettlementParams): EarlySettlementResponse;
+/**
+ * @param {LateChargesParams} params
+ * @returns {LateChargesResponse}
+ */
+export function calculateLateCharges(params: LateChargesParams): LateChargesResponse;
 /**
  * @param {Date} date
  * @returns {Date}